@@ -1,21 +1,50 @@
 use eframe::egui;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::NaiveDate;
 use clipboard::{ClipboardContext, ClipboardProvider};
 
-use crate::notes::NotesManager;
-use crate::state::{AppState, UiState, LoadMode};
-use crate::ui::{AppTheme, WindowManager, PanelManager, ThemeMode, SidePanelActions, CentralPanelActions, SettingsActions};
+use crate::backup::{self, BACKUP_DIR_NAME, PendingRestore, RestoreMode};
+use crate::cli::{self, CliError};
+use crate::error::AppError;
+use crate::instance::{IpcReply, IpcRequest, IpcServer};
+use crate::notifications::NotificationCenter;
+use crate::quick_picker::GlobalHotkey;
+use crate::settings::{AppSettings, SettingsManager};
+use crate::snippet::{self, BuiltinValues, CopyTarget, SnippetForm};
+use crate::storage;
+use crate::state::{AppState, EditConflict, UiState, LoadMode};
+use crate::ui::{AppTheme, WindowManager, PanelManager, ThemeMode, SidePanelActions, CentralPanelActions, SettingsActions, SnippetFormActions, QuickPickerActions, TagEditorActions, TrashAction, BulkAction, ConflictResolution};
+use crate::ui::keymap::{Keymap, ShortcutAction};
+use crate::validation::ValidationRules;
+use crate::watcher::DataWatcher;
+use crate::log_error;
 use uuid::Uuid;
 
+/// Как часто открытое приложение проверяет, не наступил ли новый день для резервной копии
+const DAILY_BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
 /// Упрощенная главная структура приложения после рефакторинга
 pub struct App {
     app_state: AppState,
     ui_state: UiState,
     theme: AppTheme,
+    settings: AppSettings,
+    settings_manager: SettingsManager,
+    /// Глобальная горячая клавиша быстрого выбора (None, если система ее не поддерживает)
+    global_hotkey: Option<GlobalHotkey>,
+    /// Команды от повторного запуска и командной строки (None, если сокет недоступен)
+    ipc_server: Option<IpcServer>,
+    /// Изменения файлов заметок другими программами (None для SQLite или если слежение недоступно)
+    data_watcher: Option<DataWatcher>,
+    backup_dir: PathBuf,
+    /// День последней проверки ежедневной копии; приложение может быть открыто несколько дней
+    daily_backup_date: NaiveDate,
 }
 
 impl App {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    /// `open_quick_picker` - запуск с флагом `--quick-picker`: сразу открыть быстрый выбор и свернуть главное окно
+    pub fn new(_cc: &eframe::CreationContext<'_>, open_quick_picker: bool) -> Self {
         // Настраиваем поддержку IME для кириллицы на Linux
         #[cfg(target_os = "linux")]
         {
@@ -26,18 +55,112 @@ impl App {
             _cc.egui_ctx.set_ime_cursor_pos(egui::Vec2::ZERO);
         }
         
-        // Создаем менеджер заметок
-        let notes_dir = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".fast-bind")
-            .join("data");
+        let base_dir = storage::default_base_dir();
+        
+        // Загружаем сохранённые настройки (при ошибке используются значения по умолчанию)
+        let settings_manager = SettingsManager::new(&base_dir);
+        let settings = settings_manager.load();
+        
+        let store = storage::open_store(&base_dir, settings.storage_backend);
+        
+        let mut ui_state = UiState::new();
+        Self::sync_ui_with_settings(&mut ui_state, &settings);
+        
+        let mut app_state = AppState::with_store(store, settings.load_mode.clone());
+        app_state.sort_mode = settings.note_sort_mode;
+        if let Err(e) = app_state.purge_expired_trash(settings.trash_retention_days) {
+            ui_state.notifications.error("Ошибка очистки корзины", &e);
+        }
+        let backup_dir = base_dir.join(BACKUP_DIR_NAME);
+        let daily_backup_date = chrono::Local::now().date_naive();
+        Self::run_daily_backup(&app_state, &settings, &backup_dir, daily_backup_date, &mut ui_state.notifications);
         
-        let notes_manager = NotesManager::new(notes_dir);
+        let global_hotkey = Self::register_global_hotkey(&_cc.egui_ctx, &settings, &mut ui_state);
+        let ipc_server = IpcServer::start(&base_dir, &_cc.egui_ctx)
+            .inspect_err(|e| log_error!("start", "ipc", e))
+            .ok();
+        let data_watcher = app_state.store.watched_dir().and_then(|dir| {
+            DataWatcher::start(dir, &_cc.egui_ctx)
+                .inspect_err(|e| log_error!("start", "watcher", e))
+                .ok()
+        });
         
-        Self {
-            app_state: AppState::new(notes_manager),
-            ui_state: UiState::new(),
+        let mut app = Self {
+            app_state,
+            ui_state,
             theme: AppTheme::new(),
+            settings,
+            settings_manager,
+            global_hotkey,
+            ipc_server,
+            data_watcher,
+            backup_dir,
+            daily_backup_date,
+        };
+        if open_quick_picker {
+            _cc.egui_ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+            app.open_quick_picker();
+        }
+        app
+    }
+    
+    /// Регистрирует глобальное сочетание быстрого выбора; ошибка показывается в настройках
+    fn register_global_hotkey(ctx: &egui::Context, settings: &AppSettings, ui_state: &mut UiState) -> Option<GlobalHotkey> {
+        let result = GlobalHotkey::new(ctx).and_then(|mut hotkey| {
+            hotkey.register(&settings.quick_picker_hotkey)?;
+            Ok(hotkey)
+        });
+        match result {
+            Ok(hotkey) => Some(hotkey),
+            Err(e) => {
+                log_error!("register", "hotkey", &e);
+                ui_state.quick_picker_hotkey_status = Some(format!("❌ Горячая клавиша недоступна: {}", e));
+                None
+            }
+        }
+    }
+    
+    /// Переносит сохранённые настройки в состояние интерфейса
+    fn sync_ui_with_settings(ui_state: &mut UiState, settings: &AppSettings) {
+        ui_state.theme_mode = settings.theme_mode;
+        ui_state.preferred_load_mode = settings.load_mode.clone();
+        ui_state.show_performance_stats = settings.show_performance_stats;
+        ui_state.preferred_storage_backend = settings.storage_backend;
+        ui_state.trash_retention_days = settings.trash_retention_days;
+        ui_state.daily_backups_to_keep = settings.daily_backups_to_keep;
+        ui_state.quick_picker_hotkey = settings.quick_picker_hotkey.clone();
+        ui_state.note_sort_mode = settings.note_sort_mode;
+    }
+    
+    /// Создает резервную копию за день `today`, если её ещё нет
+    fn run_daily_backup(app_state: &AppState, settings: &AppSettings, backup_dir: &Path, today: NaiveDate, notifications: &mut NotificationCenter) {
+        if settings.daily_backups_to_keep == 0 {
+            return;
+        }
+        let result = app_state.backup_contents(Some(settings.clone()))
+            .and_then(|contents| backup::run_daily_backup(
+                backup_dir, &contents, settings.daily_backups_to_keep, today
+            ));
+        if let Err(e) = result {
+            notifications.error("Ошибка ежедневного резервного копирования", &e);
+        }
+    }
+    
+    /// Создает ежедневную копию, когда открытое приложение переживает смену даты.
+    /// Окно может подолгу не получать событий, поэтому проверка повторяется по таймеру
+    fn check_daily_backup(&mut self, ctx: &egui::Context) {
+        let today = chrono::Local::now().date_naive();
+        if today != self.daily_backup_date {
+            self.daily_backup_date = today;
+            Self::run_daily_backup(&self.app_state, &self.settings, &self.backup_dir, today, &mut self.ui_state.notifications);
+        }
+        ctx.request_repaint_after(DAILY_BACKUP_CHECK_INTERVAL);
+    }
+    
+    /// Сохраняет текущие настройки на диск
+    fn save_settings(&mut self) {
+        if let Err(e) = self.settings_manager.save(&self.settings) {
+            self.ui_state.notifications.error("Ошибка сохранения настроек", &e);
         }
     }
     
@@ -50,30 +173,46 @@ impl App {
         ) {
             Ok(_note_id) => {
                 self.ui_state.clear_note_form();
+                self.ui_state.notifications.success("Заметка создана");
             }
-            Err(e) => {
-                eprintln!("Ошибка создания заметки: {}", e);
-            }
+            // Ошибки ввода показываются у полей формы
+            Err(e) => match e.validation_errors() {
+                errors if errors.is_empty() => self.ui_state.notifications.error("Ошибка создания заметки", &e),
+                errors => self.ui_state.note_form_errors = errors,
+            },
         }
     }
     
     /// Удаляет выбранную заметку
     fn delete_selected_note(&mut self) {
-        if let Some(idx) = self.ui_state.selected_note {
-            if let Err(e) = self.app_state.delete_note(idx) {
-                eprintln!("Ошибка удаления заметки: {}", e);
+        if self.ui_state.selected_notes.len() > 1 {
+            self.handle_bulk_action(BulkAction::Delete);
+        } else if let Some(note_id) = self.ui_state.selected_note {
+            if let Err(e) = self.app_state.delete_note(note_id) {
+                self.ui_state.notifications.error("Ошибка удаления заметки", &e);
             } else {
                 self.ui_state.selected_note = None;
+                self.ui_state.notifications.success("Заметка перемещена в корзину");
             }
         }
     }
     
     /// Копирует заметку в буфер обмена
-    fn copy_note_to_clipboard(&self) {
-        if let Some(idx) = self.ui_state.selected_note {
-            if let Some(content) = self.app_state.get_note_content(idx) {
-                if let Ok(mut ctx) = ClipboardContext::new() {
-                    let _ = ctx.set_contents(content);
+    fn copy_note_to_clipboard(&mut self) {
+        if self.ui_state.selected_notes.len() > 1 {
+            self.handle_bulk_action(BulkAction::CopyToClipboard);
+        } else if let Some(note_id) = self.ui_state.selected_note {
+            if let Some(content) = self.app_state.get_note_content(note_id) {
+                if snippet::has_placeholders(&content) {
+                    self.copy_snippet(note_id, content, CopyTarget::Clipboard);
+                } else {
+                    match Self::set_clipboard(content) {
+                        Ok(()) => {
+                            self.ui_state.notifications.success("Скопировано в буфер обмена");
+                            self.record_note_use(note_id);
+                        }
+                        Err(e) => self.notify_copy_error(CopyTarget::Clipboard, &e),
+                    }
                 }
             }
         }
@@ -81,62 +220,400 @@ impl App {
     
     /// Копирует заметку в постоянное текстовое поле
     fn copy_note_to_persistent(&mut self) {
-        if let Some(idx) = self.ui_state.selected_note {
-            if let Err(e) = self.app_state.append_note_to_persistent(idx) {
-                eprintln!("Ошибка копирования в постоянный текст: {}", e);
+        if self.ui_state.selected_notes.len() > 1 {
+            self.handle_bulk_action(BulkAction::CopyToPersistent);
+        } else if let Some(note_id) = self.ui_state.selected_note {
+            if let Some(content) = self.app_state.get_note_content(note_id)
+                && snippet::has_placeholders(&content)
+            {
+                self.copy_snippet(note_id, content, CopyTarget::PersistentText);
+            } else {
+                match self.app_state.append_note_to_persistent(note_id) {
+                    Ok(()) => {
+                        self.ui_state.notifications.success("Добавлено в постоянный текст");
+                        self.record_note_use(note_id);
+                    }
+                    Err(e) => self.notify_copy_error(CopyTarget::PersistentText, &e),
+                }
             }
         }
     }
     
+    /// Увеличивает счетчик копирований заметки
+    fn record_note_use(&mut self, note_id: Uuid) {
+        if let Err(e) = self.app_state.record_note_use(note_id) {
+            self.ui_state.notifications.error("Ошибка сохранения статистики заметки", &e);
+        }
+    }
+    
+    /// Копирует заметку с подстановками: встроенные раскрываются сразу,
+    /// а для полей `{{name:...}}` сначала открывается форма
+    fn copy_snippet(&mut self, note_id: Uuid, content: String, target: CopyTarget) {
+        let Some(note) = self.app_state.note(note_id) else {
+            return;
+        };
+        match SnippetForm::new(note.id, &note.title, &content, target) {
+            Some(form) => {
+                self.ui_state.snippet_form = Some(form);
+                self.ui_state.snippet_form_focus = true;
+            }
+            None => {
+                if let Err(e) = self.deliver_snippet(note.id, &content, &Default::default(), target) {
+                    self.notify_copy_error(target, &e);
+                }
+            }
+        }
+    }
+    
+    /// Раскрывает подстановки и помещает текст в буфер обмена или постоянный текст.
+    /// Об успехе сообщает сама, ошибку доставки возвращает вызывающему
+    fn deliver_snippet(&mut self, note_id: Uuid, content: &str, fields: &std::collections::HashMap<String, String>, target: CopyTarget) -> Result<(), AppError> {
+        let builtins = BuiltinValues::new(Self::get_clipboard());
+        let text = snippet::expand(content, &builtins, fields);
+        match target {
+            CopyTarget::Clipboard => {
+                Self::set_clipboard(text)?;
+                self.ui_state.notifications.success("Скопировано в буфер обмена");
+            }
+            CopyTarget::PersistentText => {
+                self.app_state.append_to_persistent(&text)?;
+                self.ui_state.notifications.success("Добавлено в постоянный текст");
+            }
+        }
+        self.record_note_use(note_id);
+        Ok(())
+    }
+    
+    /// Сообщает, что заметку не удалось скопировать
+    fn notify_copy_error(&mut self, target: CopyTarget, error: &AppError) {
+        let context = match target {
+            CopyTarget::Clipboard => "Ошибка копирования в буфер обмена",
+            CopyTarget::PersistentText => "Ошибка копирования в постоянный текст",
+        };
+        self.ui_state.notifications.error(context, error);
+    }
+    
+    /// Выполняет команды, переданные повторным запуском или командной строкой
+    fn handle_ipc_calls(&mut self, ctx: &egui::Context) {
+        let Some(server) = &self.ipc_server else {
+            return;
+        };
+        let calls: Vec<_> = std::iter::from_fn(|| server.try_recv()).collect();
+        for call in calls {
+            let reply = self.run_ipc_request(ctx, &call.request);
+            call.reply(reply);
+        }
+    }
+    
+    fn run_ipc_request(&mut self, ctx: &egui::Context, request: &IpcRequest) -> IpcReply {
+        match request {
+            IpcRequest::ShowWindow => {
+                Self::show_window(ctx);
+                Ok(None)
+            }
+            IpcRequest::QuickPicker => {
+                self.open_quick_picker();
+                Ok(None)
+            }
+            IpcRequest::OpenNote { note } => {
+                let note_id = cli::resolve_note(&self.app_state, note)?;
+                self.app_state.ensure_note_loaded(note_id);
+                self.select_note(note_id);
+                Self::show_window(ctx);
+                Ok(Some(note_id))
+            }
+            IpcRequest::CreateNote { title, group, tags, content } => {
+                cli::add_note(&mut self.app_state, title, group.as_deref(), tags, content.clone()).map(Some)
+            }
+            IpcRequest::CopyNote { note, fields } => {
+                let note_id = cli::resolve_note(&self.app_state, note)?;
+                self.app_state.ensure_note_loaded(note_id);
+                let content = self.app_state.get_note_content(note_id)
+                    .ok_or_else(|| CliError::NotFound(format!("заметка '{}' не найдена", note)))?;
+                let fields = fields.iter().cloned().collect();
+                cli::check_fields(&content, &fields)?;
+                self.deliver_snippet(note_id, &content, &fields, CopyTarget::Clipboard)?;
+                Ok(Some(note_id))
+            }
+        }
+    }
+    
+    /// Разворачивает главное окно и переводит на него фокус
+    fn show_window(ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+    }
+    
+    /// Открывает быстрый выбор с пустым запросом
+    fn open_quick_picker(&mut self) {
+        self.ui_state.show_quick_picker = true;
+        self.ui_state.quick_picker_query.clear();
+        self.ui_state.quick_picker_selected = 0;
+        self.ui_state.quick_picker_focus = true;
+    }
+    
+    /// Обрабатывает действия быстрого выбора: копирует заметку так же, как кнопка в главном окне
+    fn handle_quick_picker_actions(&mut self, ctx: &egui::Context, actions: QuickPickerActions) {
+        if let Some(note_id) = actions.copy_note {
+            if self.app_state.ensure_note_loaded(note_id) {
+                self.select_note(note_id);
+                self.copy_note_to_clipboard();
+                // Полям подстановок нужна форма в главном окне
+                if self.ui_state.snippet_form.is_some() {
+                    Self::show_window(ctx);
+                }
+            } else {
+                self.ui_state.notifications.error("Ошибка быстрого выбора", &AppError::NoteNotFound);
+            }
+            self.ui_state.show_quick_picker = false;
+        } else if actions.close {
+            self.ui_state.show_quick_picker = false;
+        }
+    }
+    
+    /// Обрабатывает действия формы полей подстановок
+    fn handle_snippet_form_actions(&mut self, actions: SnippetFormActions) {
+        if actions.submit {
+            if let Some(form) = self.ui_state.snippet_form.take() {
+                if let Err(e) = self.deliver_snippet(form.note_id, &form.content, &form.values(), form.target) {
+                    self.notify_copy_error(form.target, &e);
+                }
+            }
+        } else if actions.cancel {
+            self.ui_state.snippet_form = None;
+        }
+    }
+    
+    fn get_clipboard() -> String {
+        ClipboardContext::new()
+            .and_then(|mut ctx| ctx.get_contents())
+            .unwrap_or_default()
+    }
+    
+    /// Помещает текст в буфер обмена; недоступный буфер - ошибка ввода-вывода
+    fn set_clipboard(text: String) -> Result<(), AppError> {
+        ClipboardContext::new()
+            .and_then(|mut ctx| ctx.set_contents(text))
+            .map_err(|e| AppError::Io(std::io::Error::other(e.to_string())))
+    }
+    
     /// Сохраняет изменения заметки
     fn save_note_changes(&mut self) {
-        if let Some(idx) = self.ui_state.selected_note {
+        if let Some(note_id) = self.ui_state.selected_note {
             if let Err(e) = self.app_state.update_note(
-                idx,
+                note_id,
                 None, // title не изменяем
                 Some(self.ui_state.new_note_content.clone()),
             ) {
-                eprintln!("Ошибка сохранения заметки: {}", e);
+                self.ui_state.notifications.error("Ошибка сохранения заметки", &e);
             } else {
                 self.ui_state.stop_editing();
+                self.ui_state.notifications.success("Заметка сохранена");
+            }
+        }
+    }
+    
+    /// Перечитывает историю версий, если открыта панель истории другой заметки
+    fn sync_history(&mut self) {
+        let note_id = self.ui_state.selected_note
+            .filter(|_| self.ui_state.show_history);
+        
+        if note_id != self.ui_state.history_note_id {
+            self.ui_state.history_revisions = note_id
+                .map(|id| self.app_state.note_revisions(id))
+                .unwrap_or_default();
+            self.ui_state.history_note_id = note_id;
+            
+            let revisions = &self.ui_state.history_revisions;
+            if !self.ui_state.selected_revision.is_some_and(|id| revisions.iter().any(|r| r.id == id)) {
+                self.ui_state.selected_revision = None;
             }
         }
     }
     
+    /// Пересчитывает результаты поиска для текущего запроса
+    fn refresh_search(&mut self) {
+        self.ui_state.search_results = self.app_state.search_notes(&self.ui_state.search_query);
+    }
+    
     /// Переключает закрепление заметки
-    fn toggle_pin(&mut self, index: usize) {
-        if let Err(e) = self.app_state.toggle_pin(index) {
-            eprintln!("Ошибка переключения закрепления: {}", e);
+    fn toggle_pin(&mut self, note_id: Uuid) {
+        if let Err(e) = self.app_state.toggle_pin(note_id) {
+            self.ui_state.notifications.error("Ошибка переключения закрепления", &e);
         }
     }
     
     /// Выбирает заметку для просмотра/редактирования
-    fn select_note(&mut self, index: usize) {
-        if let Some(note) = self.app_state.notes.get(index) {
-            self.ui_state.selected_note = Some(index);
+    fn select_note(&mut self, note_id: Uuid) {
+        if let Some(note) = self.app_state.note(note_id) {
+            self.ui_state.selected_note = Some(note_id);
+            self.ui_state.selected_notes.clear();
+            self.ui_state.selection_anchor = Some(note.id);
             self.ui_state.new_note_title = note.title.clone();
             self.ui_state.new_note_content = note.content.clone();
         }
     }
     
+    /// Ctrl-клик: добавляет заметку к выбору или убирает из него
+    fn toggle_note_selection(&mut self, note_id: Uuid) {
+        if self.ui_state.selected_notes.is_empty()
+            && let Some(current_id) = self.ui_state.selected_note
+        {
+            self.ui_state.selected_notes.push(current_id);
+        }
+        match self.ui_state.selected_notes.iter().position(|&id| id == note_id) {
+            Some(pos) => { self.ui_state.selected_notes.remove(pos); }
+            None => self.ui_state.selected_notes.push(note_id),
+        }
+        let selected = std::mem::take(&mut self.ui_state.selected_notes);
+        self.set_selection(selected);
+        self.ui_state.selection_anchor = Some(note_id);
+    }
+    
+    /// Shift-клик: выбирает заметки от предыдущей выбранной до указанной в порядке боковой панели
+    fn select_note_range(&mut self, note_id: Uuid) {
+        let order = self.display_order_ids();
+        let anchor = self.ui_state.selection_anchor
+            .and_then(|anchor_id| order.iter().position(|&id| id == anchor_id));
+        let (Some(from), Some(to)) = (anchor, order.iter().position(|&id| id == note_id)) else {
+            self.select_note(note_id);
+            return;
+        };
+        
+        let anchor_id = self.ui_state.selection_anchor;
+        let range = order[from.min(to)..=from.max(to)].to_vec();
+        self.set_selection(range);
+        self.ui_state.selection_anchor = anchor_id;
+    }
+    
+    /// Устанавливает выбор: одна заметка открывается в редакторе, несколько - в панели групповых операций
+    fn set_selection(&mut self, note_ids: Vec<Uuid>) {
+        match note_ids.as_slice() {
+            [] => {
+                self.ui_state.selected_note = None;
+                self.ui_state.selected_notes.clear();
+            }
+            [note_id] => self.select_note(*note_id),
+            _ => {
+                self.ui_state.stop_editing();
+                self.ui_state.selected_note = None;
+                self.ui_state.selected_notes = note_ids;
+                self.ui_state.bulk_status = None;
+            }
+        }
+    }
+    
+    /// Выбранные заметки в порядке боковой панели (заметки из свернутых групп - в конце)
+    fn selected_note_ids(&self) -> Vec<Uuid> {
+        let mut ids: Vec<Uuid> = self.display_order_ids().into_iter()
+            .filter(|id| self.ui_state.selected_notes.contains(id))
+            .collect();
+        for id in &self.ui_state.selected_notes {
+            if !ids.contains(id) && self.app_state.notes.iter().any(|n| n.id == *id) {
+                ids.push(*id);
+            }
+        }
+        ids
+    }
+    
+    /// Id заметок в порядке отображения боковой панели
+    fn display_order_ids(&self) -> Vec<Uuid> {
+        PanelManager::note_display_order(&self.app_state, self.ui_state.tag_filter.as_deref())
+            .into_iter()
+            .map(|idx| self.app_state.notes[idx].id)
+            .collect()
+    }
+    
+    /// Выполняет операцию над всеми выбранными заметками; каждая операция отменяется одним действием
+    fn handle_bulk_action(&mut self, action: BulkAction) {
+        let ids = self.selected_note_ids();
+        let clears_selection = action == BulkAction::Delete;
+        let result: Result<String, AppError> = match action {
+            BulkAction::MoveToGroup(group_id) => self.app_state.move_notes_to_group(&ids, group_id)
+                .map(|count| format!("✔ Перемещено заметок: {}", count)),
+            BulkAction::SetPinned(true) => self.app_state.set_notes_pinned(&ids, true)
+                .map(|count| format!("✔ Закреплено заметок: {}", count)),
+            BulkAction::SetPinned(false) => self.app_state.set_notes_pinned(&ids, false)
+                .map(|count| format!("✔ Откреплено заметок: {}", count)),
+            BulkAction::Delete => self.app_state.delete_notes(&ids)
+                .map(|count| format!("✔ В корзину перемещено заметок: {}", count)),
+            BulkAction::AddTag(tag) => self.app_state.add_tag_to_notes(&ids, &tag)
+                .map(|count| format!("✔ Тег #{} добавлен заметкам: {}", tag, count)),
+            BulkAction::RemoveTag(tag) => self.app_state.remove_tag_from_notes(&ids, &tag)
+                .map(|count| format!("✔ Тег #{} убран у заметок: {}", tag, count)),
+            BulkAction::Export(target_dir) => self.app_state.export_notes(&ids, &target_dir)
+                .map(|report| format!("✔ Экспортировано заметок: {} в {}", report.notes_exported, report.target_dir.display())),
+            BulkAction::CopyToClipboard | BulkAction::CopyToPersistent => {
+                let text = self.app_state.concat_notes(&ids);
+                let delivered = if action == BulkAction::CopyToClipboard {
+                    Self::set_clipboard(text)
+                } else {
+                    self.app_state.append_to_persistent(&text)
+                };
+                delivered.map(|()| {
+                    for &note_id in &ids {
+                        self.record_note_use(note_id);
+                    }
+                    format!("✔ Скопировано заметок: {}", ids.len())
+                })
+            }
+            BulkAction::ClearSelection => {
+                self.set_selection(Vec::new());
+                return;
+            }
+        };
+        
+        self.ui_state.bulk_status = Some(match result {
+            Ok(status) => status,
+            Err(e) => {
+                self.ui_state.notifications.error("Ошибка групповой операции", &e);
+                format!("❌ {}", e)
+            }
+        });
+        if clears_selection {
+            self.set_selection(Vec::new());
+        }
+        if !self.ui_state.search_query.trim().is_empty() {
+            self.refresh_search();
+        }
+    }
+    
     /// Обновляет заголовок заметки
-    fn update_note_title(&mut self, index: usize, title: String) {
-        if let Err(e) = self.app_state.update_note(index, Some(title), None) {
-            eprintln!("Ошибка обновления заголовка: {}", e);
+    fn update_note_title(&mut self, note_id: Uuid, title: String) {
+        if let Err(e) = self.app_state.update_note(note_id, Some(title), None) {
+            self.ui_state.notifications.error("Ошибка обновления заголовка", &e);
         }
     }
     
     /// Переключает сворачивание группы
     fn toggle_group_collapsed(&mut self, group_id: Uuid) {
         if let Err(e) = self.app_state.toggle_group_collapsed(group_id) {
-            eprintln!("Ошибка переключения группы: {}", e);
+            self.ui_state.notifications.error("Ошибка переключения группы", &e);
         }
     }
     
     /// Обрабатывает действия боковой панели
     fn handle_side_panel_actions(&mut self, actions: SidePanelActions) {
-        if let Some(idx) = actions.selected_note {
-            self.select_note(idx);
+        if let Some(note_id) = actions.selected_note {
+            self.select_note(note_id);
+        }
+        if let Some(note_id) = actions.toggled_note_selection {
+            self.toggle_note_selection(note_id);
+        }
+        if let Some(note_id) = actions.selected_note_range {
+            self.select_note_range(note_id);
+        }
+        
+        if actions.search_changed {
+            self.refresh_search();
+        }
+        
+        if let Some(note_id) = actions.selected_search_result {
+            // В ленивом режиме найденная заметка может быть ещё не загружена
+            if self.app_state.ensure_note_loaded(note_id) {
+                self.select_note(note_id);
+            }
         }
         
         if actions.new_note_clicked {
@@ -153,6 +630,10 @@ impl App {
             self.ui_state.show_settings = true;
         }
         
+        if actions.show_notifications_clicked {
+            self.ui_state.show_notifications = true;
+        }
+        
         if actions.show_group_editor_clicked {
             self.ui_state.show_group_editor = true;
         }
@@ -161,16 +642,189 @@ impl App {
             self.toggle_group_collapsed(group_id);
         }
         
+        if let Some(tag_filter) = actions.tag_filter_changed {
+            // В ленивом режиме подгружаем все заметки с выбранным тегом
+            if let Some(ref tag) = tag_filter {
+                self.app_state.load_notes_with_tag(tag);
+            }
+            self.ui_state.tag_filter = tag_filter;
+            self.ui_state.selected_note = None;
+            self.ui_state.selected_notes.clear();
+        }
+        
+        if actions.show_tag_editor_clicked {
+            self.ui_state.show_tag_editor = true;
+        }
+        
+        if let Some(action) = actions.trash_action {
+            self.handle_trash_action(action);
+        }
+        
+        if actions.undo_clicked {
+            self.undo_or_redo(false);
+        }
+        
+        if actions.redo_clicked {
+            self.undo_or_redo(true);
+        }
+        
+        if let Some((group_id, sort_mode)) = actions.group_sort_changed
+            && let Err(e) = self.app_state.set_group_sort_mode(group_id, sort_mode)
+        {
+            self.ui_state.notifications.error("Ошибка сохранения сортировки группы", &e);
+        }
+        
+        if let Some((note_id, group_id, before)) = actions.note_moved
+            && let Err(e) = self.app_state.move_note(note_id, group_id, before)
+        {
+            self.ui_state.notifications.error("Ошибка перемещения заметки", &e);
+        }
+        
+        if let Some((group_id, parent_id)) = actions.group_moved
+            && let Err(e) = self.app_state.move_group(group_id, parent_id)
+        {
+            self.ui_state.notifications.error("Ошибка перемещения группы", &e);
+        }
+        
         // Обработка запроса дозагрузки для ленивой загрузки
         if let Some((visible_start, visible_end)) = actions.load_more_requested {
             if let Err(e) = self.app_state.load_more_if_needed((visible_start, visible_end)) {
-                eprintln!("Ошибка дозагрузки заметок: {}", e);
+                self.ui_state.notifications.error("Ошибка дозагрузки заметок", &e);
+            }
+        }
+    }
+    
+    /// Восстанавливает или окончательно удаляет элементы корзины
+    fn handle_trash_action(&mut self, action: TrashAction) {
+        
+        let result = match action {
+            TrashAction::RestoreNote(id) => self.app_state.restore_trashed_note(id),
+            TrashAction::RestoreGroup(id) => self.app_state.restore_trashed_group(id),
+            TrashAction::PurgeNote(id) => self.app_state.purge_trashed_note(id),
+            TrashAction::PurgeGroup(id) => self.app_state.purge_trashed_group(id),
+            TrashAction::Empty => self.app_state.empty_trash(),
+        };
+        if let Err(e) = result {
+            self.ui_state.notifications.error("Ошибка работы с корзиной", &e);
+        }
+        
+        // Восстановление меняет порядок заметок - сохраняем выделение по ID
+        self.drop_missing_selection();
+    }
+    
+    /// Переносит изменения папки данных, сделанные другими программами.
+    /// Если редактируемую заметку изменили или удалили, несохраненный текст остается до решения пользователя
+    fn sync_external_changes(&mut self, ctx: &egui::Context) {
+        let Some(changes) = self.data_watcher.as_ref().and_then(|w| w.take_changes(ctx)) else {
+            return;
+        };
+        
+        // Заголовок и группа понадобятся, если файл редактируемой заметки удалят
+        let editing = self.ui_state.editing_content
+            .and_then(|id| self.app_state.note(id))
+            .map(|note| (note.id, note.title.clone(), note.group_id));
+        let report = self.app_state.apply_external_changes(&changes);
+        if report.is_empty() {
+            return;
+        }
+        if !report.created.is_empty() || !report.updated.is_empty() || !report.deleted.is_empty() {
+            self.ui_state.notifications.info(format!(
+                "Заметки изменены на диске: +{} новых, {} изменено, {} удалено",
+                report.created.len(), report.updated.len(), report.deleted.len()
+            ));
+        }
+        
+        if let Some((note_id, title, group_id)) = editing {
+            if report.deleted.contains(&note_id) {
+                let content = self.ui_state.new_note_content.clone();
+                self.ui_state.stop_editing();
+                self.ui_state.edit_conflict = Some(EditConflict::Deleted { title, content, group_id });
+            } else if report.updated.contains(&note_id)
+                && self.app_state.note(note_id).is_some_and(|note| note.content != self.ui_state.new_note_content)
+            {
+                self.ui_state.edit_conflict = Some(EditConflict::Modified(note_id));
+            }
+        }
+        
+        // Форма заметки, которую сейчас не редактируют, показывает версию с диска
+        if let Some(note) = self.ui_state.selected_note
+            .filter(|&id| self.ui_state.editing_content != Some(id))
+            .and_then(|id| self.app_state.note(id))
+        {
+            self.ui_state.new_note_title = note.title.clone();
+            self.ui_state.new_note_content = note.content.clone();
+        }
+        
+        self.drop_missing_selection();
+        if !self.ui_state.search_query.trim().is_empty() {
+            self.refresh_search();
+        }
+        self.ui_state.history_note_id = None;
+    }
+    
+    /// Применяет выбор пользователя в конфликте правки
+    fn resolve_edit_conflict(&mut self, resolution: ConflictResolution) {
+        let Some(conflict) = self.ui_state.edit_conflict.take() else {
+            return;
+        };
+        
+        match (resolution, conflict) {
+            (ConflictResolution::ReloadFromDisk, EditConflict::Modified(note_id)) => {
+                self.ui_state.stop_editing();
+                if let Some(note) = self.app_state.note(note_id) {
+                    self.ui_state.new_note_content = note.content.clone();
+                }
+            }
+            (ConflictResolution::SaveAsNew, EditConflict::Deleted { title, content, group_id }) => {
+                // Группу тоже могли удалить
+                let group_id = group_id.filter(|id| self.app_state.groups.iter().any(|g| g.id == *id));
+                match self.app_state.create_note(title.clone(), content.clone(), group_id) {
+                    Ok(note_id) => self.select_note(note_id),
+                    Err(e) => {
+                        self.ui_state.notifications.error("Ошибка сохранения заметки", &e);
+                        self.ui_state.edit_conflict = Some(EditConflict::Deleted { title, content, group_id });
+                    }
+                }
             }
+            // Оставить свой текст или отбросить удаленную заметку - достаточно убрать предупреждение
+            _ => {}
         }
     }
     
+    /// Отменяет (или повторяет) последнюю операцию
+    fn undo_or_redo(&mut self, redo: bool) {
+        let result = if redo { self.app_state.redo() } else { self.app_state.undo() };
+        if let Err(e) = result {
+            self.ui_state.notifications.error("Ошибка отмены операции", &e);
+        }
+        
+        // Редактор мог держать устаревший текст заметки
+        self.ui_state.stop_editing();
+        self.ui_state.history_note_id = None;
+        self.drop_missing_selection();
+    }
+    
+    /// Снимает выделение с заметок, которых больше нет в списке
+    fn drop_missing_selection(&mut self) {
+        self.ui_state.selected_note = self.ui_state.selected_note
+            .filter(|&id| self.app_state.note_index(id).is_some());
+        let notes = &self.app_state.notes;
+        self.ui_state.selected_notes.retain(|id| notes.iter().any(|note| note.id == *id));
+    }
+    
     /// Обрабатывает действия центральной панели
     fn handle_central_panel_actions(&mut self, actions: CentralPanelActions) {
+        // Результаты поиска должны отражать изменения заметок
+        let notes_changed = actions.save_note_clicked
+            || actions.delete_note_clicked
+            || actions.create_note_clicked
+            || actions.toggle_pin.is_some()
+            || actions.update_title.is_some()
+            || actions.update_tags.is_some()
+            || actions.move_to_group.is_some()
+            || actions.restore_revision.is_some()
+            || actions.resolve_conflict.is_some();
+        
         if actions.save_note_clicked {
             self.save_note_changes();
         }
@@ -187,23 +841,124 @@ impl App {
             self.copy_note_to_persistent();
         }
         
-        if let Some(idx) = actions.toggle_pin {
-            self.toggle_pin(idx);
+        if let Some(note_id) = actions.toggle_pin {
+            self.toggle_pin(note_id);
         }
         
-        if let Some((idx, title)) = actions.update_title {
-            self.update_note_title(idx, title);
+        if let Some((note_id, title)) = actions.update_title {
+            self.update_note_title(note_id, title);
+        }
+        
+        if let Some((note_id, revision_id)) = actions.restore_revision {
+            if let Err(e) = self.app_state.restore_revision(note_id, revision_id) {
+                self.ui_state.notifications.error("Ошибка восстановления версии", &e);
+            } else {
+                self.ui_state.selected_revision = None;
+            }
+        }
+        
+        if let Some((note_id, tags)) = actions.update_tags {
+            if let Err(e) = self.app_state.set_note_tags(note_id, tags) {
+                self.ui_state.notifications.error("Ошибка обновления тегов", &e);
+            }
+        }
+        
+        if let Some((note_id, group_id)) = actions.move_to_group
+            && let Err(e) = self.app_state.move_note_to_group(note_id, group_id)
+        {
+            self.ui_state.notifications.error("Ошибка перемещения заметки", &e);
         }
         
         if actions.create_note_clicked {
             self.create_note();
         }
         
+        if let Some(action) = actions.bulk_action {
+            self.handle_bulk_action(action);
+        }
+        
+        if let Some(resolution) = actions.resolve_conflict {
+            self.resolve_edit_conflict(resolution);
+        }
+        
         if actions.persistent_text_changed {
             if let Err(e) = self.app_state.save_persistent_text() {
-                eprintln!("Ошибка сохранения постоянного текста: {}", e);
+                self.ui_state.notifications.error("Ошибка сохранения постоянного текста", &e);
             }
         }
+        
+        if notes_changed && !self.ui_state.search_query.trim().is_empty() {
+            self.refresh_search();
+        }
+        
+        if notes_changed {
+            // История перечитается при следующей отрисовке
+            self.ui_state.history_note_id = None;
+        }
+    }
+    
+    /// Обрабатывает горячие клавиши, преобразуя их в действия панелей
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // Пока в настройках ожидается новое сочетание, горячие клавиши не срабатывают
+        if self.ui_state.capturing_shortcut.is_some() {
+            return;
+        }
+        
+        let triggered = self.settings.keymap.poll(ctx);
+        if triggered.is_empty() {
+            return;
+        }
+        
+        let mut side_actions = SidePanelActions::new();
+        let mut central_actions = CentralPanelActions::new();
+        
+        for action in triggered {
+            match action {
+                ShortcutAction::NewNote => side_actions.new_note(),
+                ShortcutAction::SaveNote => {
+                    if self.ui_state.selected_note.is_none() {
+                        central_actions.create_note();
+                    } else if self.ui_state.editing_content.is_some() {
+                        central_actions.save_note();
+                    }
+                }
+                ShortcutAction::DeleteNote => {
+                    if self.ui_state.selected_note.is_some() || !self.ui_state.selected_notes.is_empty() {
+                        central_actions.delete_note();
+                    }
+                }
+                ShortcutAction::CopyToClipboard => central_actions.copy_to_clipboard(),
+                ShortcutAction::CopyToPersistent => central_actions.copy_to_persistent(),
+                ShortcutAction::TogglePin => {
+                    if let Some(note_id) = self.ui_state.selected_note {
+                        central_actions.toggle_pin(note_id);
+                    }
+                }
+                ShortcutAction::SelectPrevious | ShortcutAction::SelectNext => {
+                    if let Some(note_id) = self.neighbour_note(action == ShortcutAction::SelectNext) {
+                        side_actions.select_note(note_id);
+                    }
+                }
+                ShortcutAction::Undo | ShortcutAction::Redo => self.undo_or_redo(action == ShortcutAction::Redo),
+            }
+        }
+        
+        self.handle_side_panel_actions(side_actions);
+        self.handle_central_panel_actions(central_actions);
+    }
+    
+    /// Находит соседнюю заметку в порядке отображения боковой панели
+    fn neighbour_note(&self, forward: bool) -> Option<Uuid> {
+        let order = self.display_order_ids();
+        let current = self.ui_state.selected_note
+            .and_then(|note_id| order.iter().position(|&id| id == note_id));
+        
+        match (current, forward) {
+            (None, true) => order.first().copied(),
+            (None, false) => order.last().copied(),
+            (Some(pos), true) => order.get(pos + 1).copied(),
+            (Some(pos), false) => pos.checked_sub(1).and_then(|p| order.get(p).copied()),
+        }
     }
     
     /// Отображает основной UI через модульные компоненты
@@ -255,6 +1010,7 @@ impl App {
         let settings_actions = WindowManager::show_settings_window(
             &self.app_state,
             &mut self.ui_state,
+            &self.settings.keymap,
             ctx,
         );
         self.handle_settings_actions(settings_actions);
@@ -273,8 +1029,15 @@ impl App {
         
         // Обрабатываем создание группы после возврата управления
         if let Some((name, parent_id, selected_notes)) = group_creation_data {
-            if let Err(e) = self.app_state.create_group(name, parent_id, selected_notes) {
-                eprintln!("Ошибка создания группы: {}", e);
+            match self.app_state.create_group(name.clone(), parent_id, selected_notes) {
+                Ok(_) => {
+                    self.ui_state.clear_group_form();
+                    self.ui_state.notifications.success(format!("Группа «{}» создана", name));
+                }
+                Err(e) => match e.validation_errors() {
+                    errors if errors.is_empty() => self.ui_state.notifications.error("Ошибка создания группы", &e),
+                    errors => self.ui_state.group_form_errors = errors,
+                },
             }
         }
         
@@ -288,32 +1051,263 @@ impl App {
         // Обрабатываем изменения после возврата управления
         for group_id in groups_to_delete {
             if let Err(e) = self.app_state.delete_group(group_id) {
-                eprintln!("Ошибка удаления группы: {}", e);
+                self.ui_state.notifications.error("Ошибка удаления группы", &e);
             }
         }
         
         for (group_id, name, parent_id) in groups_to_save {
             if let Err(e) = self.app_state.update_group(group_id, name, parent_id) {
-                eprintln!("Ошибка обновления группы: {}", e);
+                self.ui_state.notifications.error("Ошибка обновления группы", &e);
+            }
+        }
+        
+        // Окно управления тегами
+        let tag_actions = WindowManager::show_tag_editor_window(
+            &self.app_state,
+            &mut self.ui_state,
+            ctx,
+        );
+        self.handle_tag_editor_actions(tag_actions);
+        
+        // Форма полей подстановок при копировании
+        let snippet_actions = WindowManager::show_snippet_form_window(&mut self.ui_state, ctx);
+        self.handle_snippet_form_actions(snippet_actions);
+        
+        // История уведомлений и всплывающие уведомления поверх остальных окон
+        let colors = self.theme.colors(ctx);
+        WindowManager::show_notifications_window(&mut self.ui_state, ctx, &colors);
+        WindowManager::show_toasts(&mut self.ui_state, ctx, &colors);
+    }
+    
+    /// Обрабатывает действия окна управления тегами
+    fn handle_tag_editor_actions(&mut self, actions: TagEditorActions) {
+        for (old_tag, new_tag) in actions.renames {
+            match self.app_state.rename_tag(&old_tag, &new_tag) {
+                Ok(_) => {
+                    // Фильтр по переименованному тегу продолжает работать с новым именем
+                    if self.ui_state.tag_filter.as_deref() == Some(old_tag.as_str()) {
+                        self.ui_state.tag_filter = Some(ValidationRules::normalize_tag(&new_tag));
+                    }
+                }
+                Err(e) => self.ui_state.notifications.error("Ошибка переименования тега", &e),
+            }
+        }
+        
+        if let Some((source_tags, target_tag)) = actions.merge {
+            match self.app_state.merge_tags(&source_tags, &target_tag) {
+                Ok(_) => {
+                    if self.ui_state.tag_filter.as_ref().is_some_and(|t| source_tags.contains(t)) {
+                        self.ui_state.tag_filter = Some(ValidationRules::normalize_tag(&target_tag));
+                    }
+                }
+                Err(e) => self.ui_state.notifications.error("Ошибка объединения тегов", &e),
+            }
+        }
+    }
+    
+    /// Проверяет архив и показывает, что изменится при восстановлении
+    fn preview_restore(&mut self, path: PathBuf) {
+        let result = backup::read_backup(&path)
+            .and_then(|(manifest, contents)| {
+                Ok(PendingRestore {
+                    replace_preview: self.app_state.preview_restore(&contents, RestoreMode::Replace)?,
+                    merge_preview: self.app_state.preview_restore(&contents, RestoreMode::Merge)?,
+                    has_settings: contents.settings.is_some(),
+                    path,
+                    manifest,
+                })
+            });
+        match result {
+            Ok(pending) => {
+                self.ui_state.pending_restore = Some(pending);
+                self.ui_state.restore_status = None;
+            }
+            Err(e) => {
+                self.ui_state.notifications.error("Ошибка проверки резервной копии", &e);
+                self.ui_state.pending_restore = None;
+                self.ui_state.restore_status = Some(format!("❌ Копия не прошла проверку: {}", e));
+            }
+        }
+    }
+    
+    /// Восстанавливает данные из архива; в режиме замены применяются и настройки из копии
+    fn restore_backup(&mut self, path: &Path, mode: RestoreMode) {
+        // Архив читается заново: файл мог измениться после предпросмотра
+        let result = backup::read_backup(path)
+            .and_then(|(_, contents)| {
+                let preview = self.app_state.restore_backup(&contents, mode)?;
+                Ok((preview, contents.settings))
+            });
+        
+        match result {
+            Ok((preview, settings)) => {
+                if mode == RestoreMode::Replace && let Some(settings) = settings {
+                    self.apply_restored_settings(settings);
+                }
+                self.ui_state.stop_editing();
+                self.ui_state.selected_note = None;
+                self.ui_state.history_note_id = None;
+                self.ui_state.pending_restore = None;
+                self.ui_state.restore_status = Some(format!(
+                    "✔ Восстановлено: +{} новых, {} изменено, {} удалено заметок",
+                    preview.notes_added, preview.notes_updated, preview.notes_removed
+                ));
+            }
+            Err(e) => {
+                self.ui_state.notifications.error("Ошибка восстановления из копии", &e);
+                self.ui_state.restore_status = Some(format!("❌ Ошибка восстановления: {}", e));
             }
         }
     }
     
+    /// Применяет настройки из резервной копии (формат хранения - после перезапуска)
+    fn apply_restored_settings(&mut self, settings: AppSettings) {
+        if settings.load_mode != self.settings.load_mode
+            && let Err(e) = self.app_state.switch_load_mode(settings.load_mode.clone())
+        {
+            self.ui_state.notifications.error("Ошибка переключения режима загрузки", &e);
+        }
+        Self::sync_ui_with_settings(&mut self.ui_state, &settings);
+        self.settings = settings;
+        self.save_settings();
+    }
+    
     /// Обрабатывает действия окна настроек
     fn handle_settings_actions(&mut self, actions: SettingsActions) {
+        let mut settings_changed = false;
+        
         if let Some(theme) = actions.theme_changed {
             // Тема будет применена в update()
             self.ui_state.theme_mode = theme;
+            self.settings.theme_mode = theme;
+            settings_changed = true;
         }
         
         if let Some(load_mode) = actions.load_mode_changed {
+            self.settings.load_mode = load_mode.clone();
+            settings_changed = true;
             if let Err(e) = self.app_state.switch_load_mode(load_mode) {
-                eprintln!("Ошибка переключения режима загрузки: {}", e);
+                self.ui_state.notifications.error("Ошибка переключения режима загрузки", &e);
             }
         }
         
         if let Some(show_stats) = actions.show_performance_stats_changed {
             self.ui_state.show_performance_stats = show_stats;
+            self.settings.show_performance_stats = show_stats;
+            settings_changed = true;
+        }
+        
+        if let Some(backend) = actions.storage_backend_changed {
+            self.settings.storage_backend = backend;
+            settings_changed = true;
+        }
+        
+        if let Some(days) = actions.trash_retention_changed {
+            self.settings.trash_retention_days = days;
+            settings_changed = true;
+            if let Err(e) = self.app_state.purge_expired_trash(days) {
+                self.ui_state.notifications.error("Ошибка очистки корзины", &e);
+            }
+        }
+        
+        if let Some(sort_mode) = actions.sort_mode_changed {
+            self.app_state.sort_mode = sort_mode;
+            self.settings.note_sort_mode = sort_mode;
+            settings_changed = true;
+        }
+        
+        if let Some(spec) = actions.quick_picker_hotkey_changed {
+            let result = match self.global_hotkey.as_mut() {
+                Some(hotkey) => hotkey.register(&spec),
+                None => crate::quick_picker::parse_hotkey(&spec).map(|_| ()),
+            };
+            self.ui_state.quick_picker_hotkey_status = Some(match result {
+                Ok(()) => {
+                    self.settings.quick_picker_hotkey = spec.trim().to_string();
+                    settings_changed = true;
+                    if spec.trim().is_empty() {
+                        "✔ Горячая клавиша отключена".to_string()
+                    } else {
+                        format!("✔ Быстрый выбор: {}", spec.trim())
+                    }
+                }
+                Err(e) => format!("❌ {}", e),
+            });
+        }
+        
+        if let Some(target_dir) = actions.export_markdown {
+            self.ui_state.export_status = Some(match self.app_state.export_markdown(&target_dir) {
+                Ok(report) => format!(
+                    "✔ Экспортировано заметок: {}, групп: {} в {}",
+                    report.notes_exported, report.groups_exported, report.target_dir.display()
+                ),
+                Err(e) => {
+                    self.ui_state.notifications.error("Ошибка экспорта", &e);
+                    format!("❌ Ошибка экспорта: {}", e)
+                }
+            });
+        }
+        
+        if let Some((source, path)) = actions.import_notes {
+            match self.app_state.import_notes(source, &path) {
+                Ok(report) => {
+                    self.ui_state.import_status = Some(format!(
+                        "✔ Импортировано заметок: {}, создано групп: {}",
+                        report.notes_imported, report.groups_created
+                    ));
+                    self.ui_state.import_skipped = report.skipped.iter()
+                        .map(|skipped| format!("{}: {}", skipped.path.display(), skipped.reason))
+                        .collect();
+                    self.drop_missing_selection();
+                }
+                Err(e) => {
+                    self.ui_state.notifications.error("Ошибка импорта", &e);
+                    self.ui_state.import_status = Some(format!("❌ Ошибка импорта: {}", e));
+                    self.ui_state.import_skipped.clear();
+                }
+            }
+        }
+        
+        if let Some(keep) = actions.daily_backups_changed {
+            self.settings.daily_backups_to_keep = keep;
+            settings_changed = true;
+        }
+        
+        if let Some(path) = actions.create_backup {
+            self.ui_state.backup_status = Some(match self.app_state.create_backup(&path, &self.settings) {
+                Ok(manifest) => format!("✔ Копия создана: {} заметок, {} групп в {}", manifest.note_count, manifest.group_count, path.display()),
+                Err(e) => {
+                    self.ui_state.notifications.error("Ошибка создания резервной копии", &e);
+                    format!("❌ Ошибка создания копии: {}", e)
+                }
+            });
+        }
+        
+        if let Some(path) = actions.preview_restore {
+            self.preview_restore(path);
+        }
+        
+        if actions.cancel_restore {
+            self.ui_state.pending_restore = None;
+            self.ui_state.restore_status = None;
+        }
+        
+        if let Some((path, mode)) = actions.restore_backup {
+            self.restore_backup(&path, mode);
+        }
+        
+        if let Some((action, binding)) = actions.shortcut_changed {
+            self.settings.keymap.rebind(action, binding);
+            settings_changed = true;
+        }
+        
+        if actions.reset_shortcuts {
+            self.settings.keymap = Keymap::default();
+            settings_changed = true;
+        }
+        
+        if settings_changed {
+            self.save_settings();
         }
         
         if actions.close_settings {
@@ -328,10 +1322,25 @@ impl eframe::App for App {
         self.theme.mode = self.ui_state.theme_mode;
         self.theme.apply(ctx);
         
+        // Внешние команды, изменения файлов и горячие клавиши обрабатываются до отрисовки, чтобы UI показал актуальное состояние
+        self.handle_ipc_calls(ctx);
+        self.sync_external_changes(ctx);
+        self.handle_shortcuts(ctx);
+        self.check_daily_backup(ctx);
+        
+        self.sync_history();
+        
         // Отображаем основной UI
         self.show_main_ui(ctx);
         
         // Упрощенные окна без сложных замыканий
         self.show_windows(ctx);
+        
+        // Быстрый выбор по глобальной горячей клавише
+        if self.global_hotkey.as_ref().is_some_and(GlobalHotkey::take_triggered) {
+            self.open_quick_picker();
+        }
+        let picker_actions = WindowManager::show_quick_picker_window(&self.app_state, &mut self.ui_state, ctx);
+        self.handle_quick_picker_actions(ctx, picker_actions);
     }
 } 
\ No newline at end of file
@@ -0,0 +1,305 @@
+/// Резервные копии: все данные приложения в одном zip-архиве с манифестом
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use crate::error::AppError;
+use crate::fs_utils;
+use crate::notes::{Note, NoteGroup};
+use crate::settings::{AppSettings, SETTINGS_FILE_NAME};
+use crate::{log_error, log_info, log_success};
+
+/// Версия формата архива
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Расширение файлов резервных копий
+pub const BACKUP_EXTENSION: &str = "zip";
+
+/// Папка с автоматическими копиями в базовой директории приложения
+pub const BACKUP_DIR_NAME: &str = "backups";
+
+/// Сколько ежедневных копий хранить по умолчанию
+pub const DEFAULT_DAILY_BACKUPS_TO_KEEP: u32 = 7;
+
+/// Префикс имени ежедневной копии (`daily-2024-01-31.zip`)
+const DAILY_BACKUP_PREFIX: &str = "daily-";
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const NOTES_DIR_ENTRY: &str = "notes/";
+const GROUPS_ENTRY: &str = "groups.json";
+const PERSISTENT_TEXT_ENTRY: &str = "persistent_text.txt";
+
+/// Описание архива; лежит в `manifest.json` рядом с данными
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    /// Версия приложения, создавшего копию
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    pub note_count: usize,
+    pub group_count: usize,
+    /// SHA-256 всех файлов архива, кроме манифеста
+    pub checksum: String,
+}
+
+/// Данные, которые попадают в резервную копию
+#[derive(Debug, Clone, Default)]
+pub struct BackupContents {
+    pub notes: Vec<Note>,
+    pub groups: Vec<NoteGroup>,
+    pub persistent_text: String,
+    /// Настройки (в старых или чужих архивах их может не быть)
+    pub settings: Option<AppSettings>,
+}
+
+/// Как применять резервную копию
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RestoreMode {
+    /// Текущие данные полностью заменяются содержимым копии
+    #[default]
+    Replace,
+    /// Заметки и группы из копии добавляются или обновляются по id, остальное не трогается
+    Merge,
+}
+
+/// Что изменится при восстановлении
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestorePreview {
+    pub notes_added: usize,
+    pub notes_updated: usize,
+    pub notes_removed: usize,
+    pub notes_unchanged: usize,
+    pub groups_added: usize,
+    pub groups_removed: usize,
+    pub persistent_text_changed: bool,
+}
+
+/// Проверенная копия, ожидающая подтверждения восстановления
+#[derive(Debug, Clone)]
+pub struct PendingRestore {
+    pub path: PathBuf,
+    pub manifest: BackupManifest,
+    /// Изменения в режиме замены и в режиме объединения
+    pub replace_preview: RestorePreview,
+    pub merge_preview: RestorePreview,
+    pub has_settings: bool,
+}
+
+/// Упаковывает данные в архив `path`. Архив собирается в памяти и записывается атомарно,
+/// поэтому прерванная запись не портит предыдущую копию с тем же именем.
+pub fn write_backup(path: &Path, contents: &BackupContents) -> Result<BackupManifest, AppError> {
+    log_info!("backup", "archive", &format!("Создание копии {}", path.display()));
+
+    let entries = payload_entries(contents)?;
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now(),
+        note_count: contents.notes.len(),
+        group_count: contents.groups.len(),
+        checksum: checksum(&entries),
+    };
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    writer.start_file(MANIFEST_ENTRY, options)?;
+    writer.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+    for (name, data) in &entries {
+        writer.start_file(name.as_str(), options)?;
+        writer.write_all(data)?;
+    }
+    let archive = writer.finish()?.into_inner();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs_utils::atomic_write(path, archive)?;
+
+    log_success!("backup", "archive");
+    Ok(manifest)
+}
+
+/// Читает и проверяет архив: манифест, версию формата, контрольную сумму и число заметок
+pub fn read_backup(path: &Path) -> Result<(BackupManifest, BackupContents), AppError> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+
+    let manifest: BackupManifest = match read_entry(&mut archive, MANIFEST_ENTRY)? {
+        Some(data) => serde_json::from_slice(&data)?,
+        None => return Err(AppError::Backup("в архиве нет manifest.json".to_string())),
+    };
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(AppError::Backup(format!(
+            "формат копии {} новее поддерживаемого ({})", manifest.format_version, BACKUP_FORMAT_VERSION
+        )));
+    }
+
+    let mut entries = BTreeMap::new();
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        if file.is_dir() || file.name() == MANIFEST_ENTRY {
+            continue;
+        }
+        let name = file.name().to_string();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        entries.insert(name, data);
+    }
+    if checksum(&entries) != manifest.checksum {
+        return Err(AppError::Backup("контрольная сумма не совпадает, архив поврежден".to_string()));
+    }
+
+    let mut contents = BackupContents::default();
+    for (name, data) in &entries {
+        if name.starts_with(NOTES_DIR_ENTRY) {
+            contents.notes.push(serde_json::from_slice(data)?);
+        } else if name == GROUPS_ENTRY {
+            contents.groups = serde_json::from_slice(data)?;
+        } else if name == PERSISTENT_TEXT_ENTRY {
+            contents.persistent_text = String::from_utf8_lossy(data).into_owned();
+        } else if name == SETTINGS_FILE_NAME {
+            contents.settings = Some(serde_json::from_slice::<AppSettings>(data)?.migrate());
+        }
+    }
+    if contents.notes.len() != manifest.note_count {
+        return Err(AppError::Backup(format!(
+            "в манифесте {} заметок, в архиве {}", manifest.note_count, contents.notes.len()
+        )));
+    }
+
+    Ok((manifest, contents))
+}
+
+/// Файлы архива (кроме манифеста), отсортированные по имени
+fn payload_entries(contents: &BackupContents) -> Result<BTreeMap<String, Vec<u8>>, AppError> {
+    let mut entries = BTreeMap::new();
+    for note in &contents.notes {
+        entries.insert(format!("{}{}.json", NOTES_DIR_ENTRY, note.id), serde_json::to_vec_pretty(note)?);
+    }
+    entries.insert(GROUPS_ENTRY.to_string(), serde_json::to_vec_pretty(&contents.groups)?);
+    entries.insert(PERSISTENT_TEXT_ENTRY.to_string(), contents.persistent_text.as_bytes().to_vec());
+    if let Some(ref settings) = contents.settings {
+        entries.insert(SETTINGS_FILE_NAME.to_string(), serde_json::to_vec_pretty(settings)?);
+    }
+    Ok(entries)
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<Option<Vec<u8>>, AppError> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)?;
+    Ok(Some(data))
+}
+
+/// SHA-256 по именам и содержимому файлов в порядке имен
+fn checksum(entries: &BTreeMap<String, Vec<u8>>) -> String {
+    let mut hasher = Sha256::new();
+    for (name, data) in entries {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update((data.len() as u64).to_le_bytes());
+        hasher.update(data);
+    }
+    hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Сравнивает текущие данные с копией, не изменяя их
+pub fn preview_restore(current_notes: &[Note], current_groups: &[NoteGroup], current_text: &str, backup: &BackupContents, mode: RestoreMode) -> RestorePreview {
+    let current: HashMap<Uuid, &Note> = current_notes.iter().map(|note| (note.id, note)).collect();
+    let mut preview = RestorePreview::default();
+
+    for note in &backup.notes {
+        match current.get(&note.id) {
+            None => preview.notes_added += 1,
+            Some(existing) if notes_equal(existing, note) => preview.notes_unchanged += 1,
+            Some(_) => preview.notes_updated += 1,
+        }
+    }
+
+    let current_group_ids: HashSet<Uuid> = current_groups.iter().map(|g| g.id).collect();
+    let backup_group_ids: HashSet<Uuid> = backup.groups.iter().map(|g| g.id).collect();
+    preview.groups_added = backup_group_ids.difference(&current_group_ids).count();
+
+    if mode == RestoreMode::Replace {
+        let backup_note_ids: HashSet<Uuid> = backup.notes.iter().map(|note| note.id).collect();
+        preview.notes_removed = current.keys().filter(|id| !backup_note_ids.contains(id)).count();
+        preview.groups_removed = current_group_ids.difference(&backup_group_ids).count();
+        preview.persistent_text_changed = current_text != backup.persistent_text;
+    }
+    preview
+}
+
+/// Заметки совпадают по всем полям (у `Note` нет `PartialEq`)
+pub fn notes_equal(a: &Note, b: &Note) -> bool {
+    a.id == b.id
+        && a.title == b.title
+        && a.content == b.content
+        && a.created_at == b.created_at
+        && a.updated_at == b.updated_at
+        && a.pinned == b.pinned
+        && a.group_id == b.group_id
+        && a.tags == b.tags
+        && a.copy_count == b.copy_count
+        && a.last_used_at == b.last_used_at
+        && a.sort_index == b.sort_index
+}
+
+/// Путь ежедневной копии за указанный день
+pub fn daily_backup_path(backup_dir: &Path, date: NaiveDate) -> PathBuf {
+    backup_dir.join(format!("{}{}.{}", DAILY_BACKUP_PREFIX, date.format("%Y-%m-%d"), BACKUP_EXTENSION))
+}
+
+/// Ежедневные копии в папке, от старых к новым
+pub fn list_daily_backups(backup_dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+    if !backup_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups: Vec<PathBuf> = fs::read_dir(backup_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            name.starts_with(DAILY_BACKUP_PREFIX) && name.ends_with(&format!(".{}", BACKUP_EXTENSION))
+        })
+        .collect();
+    // Дата в имени в формате ГГГГ-ММ-ДД, поэтому сортировка по имени совпадает с сортировкой по дате
+    backups.sort();
+    Ok(backups)
+}
+
+/// Удаляет самые старые ежедневные копии, оставляя `keep` последних. Возвращает число удаленных
+pub fn rotate_daily_backups(backup_dir: &Path, keep: usize) -> Result<usize, AppError> {
+    let backups = list_daily_backups(backup_dir)?;
+    let excess = backups.len().saturating_sub(keep);
+    for path in &backups[..excess] {
+        if let Err(e) = fs::remove_file(path) {
+            log_error!("rotate", "backup", &e);
+        }
+    }
+    Ok(excess)
+}
+
+/// Создает копию за сегодня, если её ещё нет, и удаляет лишние старые.
+/// `keep == 0` отключает автоматические копии. Возвращает путь созданной копии.
+pub fn run_daily_backup(backup_dir: &Path, contents: &BackupContents, keep: u32, today: NaiveDate) -> Result<Option<PathBuf>, AppError> {
+    if keep == 0 {
+        return Ok(None);
+    }
+    let path = daily_backup_path(backup_dir, today);
+    let created = if path.exists() {
+        None
+    } else {
+        write_backup(&path, contents)?;
+        Some(path)
+    };
+    rotate_daily_backups(backup_dir, keep as usize)?;
+    Ok(created)
+}
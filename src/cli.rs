@@ -0,0 +1,752 @@
+/// Консольный интерфейс: команды над теми же данными, что и главное окно.
+/// `fast-bind <команда> [аргументы]`; без команды запускается окно приложения.
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::PathBuf;
+use clipboard::{ClipboardContext, ClipboardProvider};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use thiserror::Error;
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::import::ImportSource;
+use crate::instance::{self, Instance, InstanceLock, IpcReply, IpcRequest};
+use crate::notes::{Note, NoteGroup, NoteMetadata};
+use crate::settings::SettingsManager;
+use crate::snippet::{self, BuiltinValues};
+use crate::state::{AppState, LoadMode};
+use crate::storage;
+use crate::validation::ValidationRules;
+
+/// Команда выполнена
+pub const EXIT_OK: i32 = 0;
+/// Неизвестная команда или неверные аргументы
+pub const EXIT_USAGE: i32 = 2;
+/// Заметка или группа не найдена
+pub const EXIT_NOT_FOUND: i32 = 3;
+/// Данные не прошли проверку
+pub const EXIT_VALIDATION: i32 = 4;
+/// Ошибка чтения или записи данных
+pub const EXIT_IO: i32 = 5;
+/// Данные заняты запущенным приложением, а команду нельзя ему передать
+pub const EXIT_BUSY: i32 = 6;
+
+/// Значение `--content`, означающее чтение содержимого из stdin
+pub const STDIN_MARKER: &str = "-";
+
+/// Команды консольного режима
+pub const COMMANDS: [&str; 12] = [
+    "list", "show", "add", "edit", "rm", "copy", "search", "groups", "export", "import", "open", "help",
+];
+
+/// Минимальная длина префикса id, по которому ищется заметка
+const MIN_ID_PREFIX_LEN: usize = 4;
+
+/// На X11 содержимое буфера обмена отдает процесс, который его записал, и после выхода команды оно пропадает.
+/// Там `copy` выполняет только запущенное окно приложения
+const CLIPBOARD_OUTLIVES_PROCESS: bool = !cfg!(all(unix, not(target_os = "macos")));
+
+/// Длина id в текстовом выводе
+const SHORT_ID_LEN: usize = 8;
+
+const USAGE: &str = "\
+Использование: fast-bind [--data-dir <папка>] [--json] <команда> [аргументы]
+
+Команды:
+  list [--group <группа>] [--tag <тег>]      список заметок
+  show <заметка>                            содержимое заметки
+  add --title <заголовок> [--group <группа>] [--tag <тег>]... [--content <текст>|-]
+                                            новая заметка (без --content текст читается из stdin)
+  edit <заметка> [--title <заголовок>] [--content <текст>|-]
+                                            изменить заметку
+  rm <заметка>                              переместить заметку в корзину
+  copy <заметка> [--set <поле>=<значение>]...
+                                            скопировать заметку в буфер обмена
+                                            (в Linux - только при открытом окне приложения)
+  search <запрос>                           поиск по заголовкам и содержимому
+  groups                                    дерево групп
+  export <папка>                            экспорт в Markdown
+  import <путь> [--from markdown|obsidian|joplin|enex]
+                                            импорт заметок
+  open [<заметка>]                          показать окно запущенного приложения и выбрать заметку
+
+<заметка> - id, его начало (от 4 символов) или точный заголовок; <группа> - id или название.
+Пока приложение открыто, add, copy и open выполняет оно; edit, rm и import недоступны.
+
+Коды завершения: 0 - успех, 2 - неверные аргументы, 3 - не найдено,
+4 - ошибка проверки данных, 5 - ошибка чтения или записи, 6 - данные заняты приложением.
+";
+
+/// Команда консольного режима
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Список заметок, при необходимости только из группы или с тегом
+    List { group: Option<String>, tag: Option<String> },
+    Show { note: String },
+    /// `content: None` - пустая заметка, `STDIN_MARKER` - содержимое из stdin
+    Add { title: String, group: Option<String>, tags: Vec<String>, content: Option<String> },
+    Edit { note: String, title: Option<String>, content: Option<String> },
+    Rm { note: String },
+    /// `fields` - значения полей `{{name:Поле}}` из `--set Поле=значение`
+    Copy { note: String, fields: Vec<(String, String)> },
+    Search { query: String },
+    Groups,
+    Export { dir: PathBuf },
+    Import { path: PathBuf, source: ImportSource },
+    /// Показать окно запущенного приложения, при необходимости выбрав заметку
+    Open { note: Option<String> },
+    Help,
+}
+
+impl Command {
+    /// Команда только читает данные и может работать рядом с запущенным приложением
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Command::List { .. } | Command::Show { .. } | Command::Search { .. }
+                | Command::Groups | Command::Export { .. } | Command::Help
+        )
+    }
+}
+
+/// Разобранная командная строка
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliArgs {
+    pub command: Command,
+    /// Вывод в формате JSON
+    pub json: bool,
+    /// Папка данных вместо `~/.fast-bind`
+    pub data_dir: Option<PathBuf>,
+}
+
+/// Ошибка консольной команды; каждый вид завершается своим кодом.
+/// Запущенное приложение возвращает ее в ответ на переданную команду
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CliError {
+    #[error("{0}")]
+    Usage(String),
+
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("{0}")]
+    Io(String),
+
+    #[error("{0}")]
+    Busy(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => EXIT_USAGE,
+            CliError::NotFound(_) => EXIT_NOT_FOUND,
+            CliError::Validation(_) => EXIT_VALIDATION,
+            CliError::Io(_) => EXIT_IO,
+            CliError::Busy(_) => EXIT_BUSY,
+        }
+    }
+}
+
+/// Код завершения определяется видом ошибки; ошибки хранилища считаются ошибками ввода-вывода
+impl From<AppError> for CliError {
+    fn from(error: AppError) -> Self {
+        match error {
+            AppError::Validation(errors) => CliError::Validation(
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", ")
+            ),
+            AppError::GroupCycle(_) => CliError::Validation(error.to_string()),
+            AppError::NoteNotFound | AppError::GroupNotFound(_) | AppError::RevisionNotFound(_) => CliError::NotFound(error.to_string()),
+            AppError::LockHeld(_) => CliError::Busy(error.to_string()),
+            error => CliError::Io(error.to_string()),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        CliError::Io(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    fn from(error: serde_json::Error) -> Self {
+        CliError::Io(error.to_string())
+    }
+}
+
+/// Запущено ли приложение с консольной командой (глобальные флаги могут стоять перед ней).
+/// Любой аргумент, не похожий на флаг, считается командой, чтобы опечатка не открывала окно
+pub fn is_cli_invocation(args: &[String]) -> bool {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => {}
+            "--data-dir" => {
+                iter.next();
+            }
+            "--help" | "-h" => return true,
+            other => return !other.starts_with('-'),
+        }
+    }
+    false
+}
+
+/// Параметры вида `--name value` в порядке появления
+struct Options(Vec<(String, String)>);
+
+impl Options {
+    /// Значение параметра (последнее, если он указан несколько раз)
+    fn take(&mut self, name: &str) -> Option<String> {
+        self.take_all(name).pop()
+    }
+
+    fn take_all(&mut self, name: &str) -> Vec<String> {
+        let (taken, rest) = std::mem::take(&mut self.0).into_iter().partition(|(flag, _)| flag == name);
+        self.0 = rest;
+        taken.into_iter().map(|(_, value)| value).collect()
+    }
+}
+
+/// Разбирает аргументы командной строки (без имени программы)
+pub fn parse_args(args: &[String]) -> Result<CliArgs, CliError> {
+    let mut json = false;
+    let mut help = false;
+    let mut data_dir = None;
+    let mut positional = Vec::new();
+    let mut options = Options(Vec::new());
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--help" | "-h" => help = true,
+            "--" => {
+                positional.extend(iter.by_ref().cloned());
+            }
+            flag if flag.starts_with("--") => {
+                let value = iter.next()
+                    .ok_or_else(|| CliError::Usage(format!("не указано значение для {}", flag)))?;
+                if flag == "--data-dir" {
+                    data_dir = Some(PathBuf::from(value));
+                } else {
+                    options.0.push((flag.to_string(), value.clone()));
+                }
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    if help {
+        return Ok(CliArgs { command: Command::Help, json, data_dir });
+    }
+
+    let mut positional = positional.into_iter();
+    let name = positional.next().ok_or_else(|| CliError::Usage("не указана команда".to_string()))?;
+    let mut next_arg = |what: &str| positional.next()
+        .ok_or_else(|| CliError::Usage(format!("{}: не указан аргумент <{}>", name, what)));
+
+    let command = match name.as_str() {
+        "list" => Command::List { group: options.take("--group"), tag: options.take("--tag") },
+        "show" => Command::Show { note: next_arg("заметка")? },
+        "add" => Command::Add {
+            title: options.take("--title")
+                .ok_or_else(|| CliError::Usage("add: не указан --title".to_string()))?,
+            group: options.take("--group"),
+            tags: options.take_all("--tag"),
+            content: options.take("--content"),
+        },
+        "edit" => {
+            let note = next_arg("заметка")?;
+            let title = options.take("--title");
+            let content = options.take("--content");
+            if title.is_none() && content.is_none() {
+                return Err(CliError::Usage("edit: укажите --title или --content".to_string()));
+            }
+            Command::Edit { note, title, content }
+        }
+        "rm" => Command::Rm { note: next_arg("заметка")? },
+        "copy" => {
+            let note = next_arg("заметка")?;
+            let fields = options.take_all("--set").into_iter()
+                .map(|pair| match pair.split_once('=') {
+                    Some((field, value)) => Ok((field.trim().to_string(), value.to_string())),
+                    None => Err(CliError::Usage(format!("--set: ожидается поле=значение, получено '{}'", pair))),
+                })
+                .collect::<Result<_, _>>()?;
+            Command::Copy { note, fields }
+        }
+        "search" => {
+            let query = positional.by_ref().collect::<Vec<_>>().join(" ");
+            if query.trim().is_empty() {
+                return Err(CliError::Usage("search: не указан запрос".to_string()));
+            }
+            Command::Search { query }
+        }
+        "groups" => Command::Groups,
+        "export" => Command::Export { dir: PathBuf::from(next_arg("папка")?) },
+        "import" => Command::Import {
+            path: PathBuf::from(next_arg("путь")?),
+            source: options.take("--from").map(|name| parse_import_source(&name)).transpose()?.unwrap_or_default(),
+        },
+        "open" => Command::Open { note: positional.next() },
+        "help" => Command::Help,
+        other => return Err(CliError::Usage(format!(
+            "неизвестная команда '{}' (доступны: {})", other, COMMANDS.join(", ")
+        ))),
+    };
+
+    if let Some((flag, _)) = options.0.first() {
+        return Err(CliError::Usage(format!("{}: неизвестный параметр {}", name, flag)));
+    }
+    if let Some(extra) = positional.next() {
+        return Err(CliError::Usage(format!("{}: лишний аргумент '{}'", name, extra)));
+    }
+
+    Ok(CliArgs { command, json, data_dir })
+}
+
+fn parse_import_source(name: &str) -> Result<ImportSource, CliError> {
+    match name {
+        "markdown" => Ok(ImportSource::MarkdownFolder),
+        "obsidian" => Ok(ImportSource::Obsidian),
+        "joplin" => Ok(ImportSource::JoplinRaw),
+        "enex" => Ok(ImportSource::Enex),
+        other => Err(CliError::Usage(format!("--from: неизвестный источник '{}'", other))),
+    }
+}
+
+/// Выполняет команду из аргументов и возвращает код завершения
+pub fn run(args: &[String]) -> i32 {
+    let cli = match parse_args(args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("Ошибка: {}\n\n{}", e, USAGE);
+            return e.exit_code();
+        }
+    };
+
+    let mut command = cli.command;
+    if command == Command::Help {
+        print!("{}", USAGE);
+        return EXIT_OK;
+    }
+    // Без --content новая заметка берет текст из перенаправленного stdin
+    if let Command::Add { content: content @ None, .. } = &mut command
+        && !io::stdin().is_terminal()
+    {
+        *content = Some(STDIN_MARKER.to_string());
+    }
+
+    let base_dir = cli.data_dir.unwrap_or_else(storage::default_base_dir);
+    let mut stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+    let result = claim_data_dir(&base_dir, &mut command, &mut stdin).and_then(|executor| match executor {
+        Executor::Running(reply) => write_reply(&command, reply, cli.json, &mut stdout),
+        Executor::Local(lock) => {
+            // Без блокировки папки данных настройки и хранилище открываются только для чтения
+            let settings_manager = SettingsManager::new(&base_dir);
+            let (settings, store) = if lock.is_some() {
+                let settings = settings_manager.load();
+                let store = storage::open_store(&base_dir, settings.storage_backend);
+                (settings, store)
+            } else {
+                let settings = settings_manager.load_read_only();
+                let store = storage::open_store_read_only(&base_dir, settings.storage_backend);
+                (settings, store)
+            };
+            let mut app_state = AppState::with_store(store, LoadMode::Eager);
+            app_state.sort_mode = settings.note_sort_mode;
+            execute(&mut app_state, &command, cli.json, &mut stdin, &mut stdout)
+        }
+    });
+
+    match result {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("Ошибка: {}", e);
+            e.exit_code()
+        }
+    }
+}
+
+/// Кто выполняет команду
+enum Executor {
+    /// Этот процесс; блокировка папки данных держится до конца команды (для чтения не нужна)
+    Local(Option<InstanceLock>),
+    /// Запущенное приложение, уже вернувшее ответ
+    Running(IpcReply),
+}
+
+/// Пока открыто окно приложения, изменения выполняет оно: два процесса с собственными копиями
+/// заметок и групп перезаписывали бы файлы друг друга
+fn claim_data_dir(base_dir: &std::path::Path, command: &mut Command, input: &mut dyn Read) -> Result<Executor, CliError> {
+    if command.is_read_only() {
+        return Ok(Executor::Local(None));
+    }
+    let Some(request) = forwarded_request(command, input)? else {
+        return match InstanceLock::acquire(base_dir)? {
+            Some(lock) => Ok(Executor::Local(Some(lock))),
+            None => Err(CliError::Busy(
+                "приложение fast-bind запущено: выполните действие в его окне или закройте его".to_string()
+            )),
+        };
+    };
+    match instance::acquire_or_forward(base_dir, &request) {
+        Ok(Instance::Primary(lock)) => Ok(Executor::Local(Some(lock))),
+        Ok(Instance::Forwarded(reply)) => Ok(Executor::Running(reply)),
+        Err(AppError::LockHeld(_)) => Err(CliError::Busy("приложение fast-bind запущено, но не отвечает".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Запрос, которым команду можно передать запущенному приложению.
+/// Содержимое из stdin читается здесь и подставляется в команду, чтобы его не пришлось читать повторно
+pub fn forwarded_request(command: &mut Command, input: &mut dyn Read) -> Result<Option<IpcRequest>, CliError> {
+    Ok(match command {
+        Command::Add { title, group, tags, content } => {
+            *content = read_content(content.as_deref(), input)?;
+            Some(IpcRequest::CreateNote {
+                title: title.clone(),
+                group: group.clone(),
+                tags: tags.clone(),
+                content: content.clone().unwrap_or_default(),
+            })
+        }
+        Command::Copy { note, fields } => Some(IpcRequest::CopyNote { note: note.clone(), fields: fields.clone() }),
+        Command::Open { note: Some(note) } => Some(IpcRequest::OpenNote { note: note.clone() }),
+        Command::Open { note: None } => Some(IpcRequest::ShowWindow),
+        _ => None,
+    })
+}
+
+/// Выводит ответ запущенного приложения так же, как при выполнении команды на месте
+fn write_reply(command: &Command, reply: IpcReply, json: bool, out: &mut dyn Write) -> Result<(), CliError> {
+    match reply? {
+        Some(note_id) if json || matches!(command, Command::Add { .. }) => write_id(note_id, json, out),
+        _ => Ok(()),
+    }
+}
+
+/// Выполняет команду над `app_state`. `input` читается только для `--content -`
+pub fn execute(
+    app_state: &mut AppState,
+    command: &Command,
+    json: bool,
+    input: &mut dyn Read,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    match command {
+        Command::List { group, tag } => {
+            let group_id = group.as_deref().map(|key| resolve_group(app_state, key)).transpose()?;
+            let tag = tag.as_deref().map(ValidationRules::normalize_tag);
+            let notes: Vec<&Note> = app_state.notes.iter()
+                .filter(|note| group_id.is_none() || note.group_id == group_id)
+                .filter(|note| tag.as_ref().is_none_or(|tag| note.tags.contains(tag)))
+                .collect();
+            if json {
+                let items: Vec<_> = notes.iter().map(|note| note_summary(app_state, note)).collect();
+                writeln!(out, "{}", serde_json::to_string_pretty(&items)?)?;
+            } else {
+                for note in notes {
+                    write_note_line(app_state, note, out)?;
+                }
+            }
+        }
+        Command::Show { note } => {
+            let note = find_note(app_state, note)?;
+            if json {
+                writeln!(out, "{}", serde_json::to_string_pretty(note)?)?;
+            } else {
+                write!(out, "{}", note.content)?;
+                if !note.content.ends_with('\n') {
+                    writeln!(out)?;
+                }
+            }
+        }
+        Command::Add { title, group, tags, content } => {
+            let content = read_content(content.as_deref(), input)?.unwrap_or_default();
+            let note_id = add_note(app_state, title, group.as_deref(), tags, content)?;
+            write_id(note_id, json, out)?;
+        }
+        Command::Edit { note, title, content } => {
+            let current = find_note(app_state, note)?;
+            let note_id = current.id;
+            let content = read_content(content.as_deref(), input)?;
+            check_note(
+                title.as_deref().unwrap_or(&current.title),
+                content.as_deref().unwrap_or(&current.content),
+            )?;
+            app_state.update_note(note_id, title.clone(), content)?;
+            if json {
+                write_id(note_id, json, out)?;
+            }
+        }
+        Command::Rm { note } => {
+            let note_id = find_note(app_state, note)?.id;
+            app_state.delete_note(note_id)?;
+            if json {
+                write_id(note_id, json, out)?;
+            }
+        }
+        Command::Copy { note, fields } => {
+            let note = find_note(app_state, note)?;
+            let note_id = note.id;
+            let fields: HashMap<String, String> = fields.iter().cloned().collect();
+            check_fields(&note.content, &fields)?;
+            if !CLIPBOARD_OUTLIVES_PROCESS {
+                return Err(CliError::Io(
+                    "буфер обмена очищается при выходе команды: запустите окно fast-bind, и copy выполнит оно".to_string()
+                ));
+            }
+
+            let mut clipboard = ClipboardContext::new()
+                .map_err(|e| CliError::Io(format!("буфер обмена недоступен: {}", e)))?;
+            let builtins = BuiltinValues::new(clipboard.get_contents().unwrap_or_default());
+            let text = snippet::expand(&note.content, &builtins, &fields);
+            clipboard.set_contents(text.clone())
+                .map_err(|e| CliError::Io(format!("не удалось записать в буфер обмена: {}", e)))?;
+            app_state.record_note_use(note_id)?;
+            if json {
+                writeln!(out, "{}", serde_json::to_string_pretty(&json!({ "id": note_id, "text": text }))?)?;
+            }
+        }
+        Command::Search { query } => {
+            let results = app_state.search_notes(query);
+            if json {
+                let items: Vec<_> = results.iter()
+                    .map(|result| json!({
+                        "id": result.note_id,
+                        "title": result.title,
+                        "pinned": result.pinned,
+                        "score": result.score,
+                        "snippet": result.snippet,
+                    }))
+                    .collect();
+                writeln!(out, "{}", serde_json::to_string_pretty(&items)?)?;
+            } else {
+                for result in results {
+                    writeln!(out, "{}  {}", short_id(result.note_id), result.title)?;
+                    if !result.snippet.is_empty() {
+                        writeln!(out, "          {}", result.snippet.replace('\n', " "))?;
+                    }
+                }
+            }
+        }
+        Command::Groups => {
+            let groups = groups_in_tree_order(&app_state.groups);
+            let notes_in = |group: &NoteGroup| app_state.notes.iter().filter(|n| n.group_id == Some(group.id)).count();
+            if json {
+                let items: Vec<_> = groups.iter()
+                    .map(|group| json!({
+                        "id": group.id,
+                        "name": group.name,
+                        "parent_id": group.parent_id,
+                        "level": group.level,
+                        "notes": notes_in(group),
+                    }))
+                    .collect();
+                writeln!(out, "{}", serde_json::to_string_pretty(&items)?)?;
+            } else {
+                for group in groups {
+                    let indent = "  ".repeat(group.level as usize);
+                    writeln!(out, "{}  {}{} ({})", short_id(group.id), indent, group.name, notes_in(group))?;
+                }
+            }
+        }
+        Command::Export { dir } => {
+            let report = app_state.export_markdown(dir)?;
+            if json {
+                writeln!(out, "{}", serde_json::to_string_pretty(&json!({
+                    "target_dir": report.target_dir,
+                    "notes_exported": report.notes_exported,
+                    "groups_exported": report.groups_exported,
+                }))?)?;
+            } else {
+                writeln!(
+                    out, "Экспортировано заметок: {}, групп: {} в {}",
+                    report.notes_exported, report.groups_exported, report.target_dir.display()
+                )?;
+            }
+        }
+        Command::Import { path, source } => {
+            if !path.exists() {
+                return Err(CliError::NotFound(format!("путь {} не существует", path.display())));
+            }
+            let report = app_state.import_notes(*source, path)?;
+            if json {
+                let skipped: Vec<_> = report.skipped.iter()
+                    .map(|file| json!({ "path": file.path, "reason": file.reason.to_string() }))
+                    .collect();
+                writeln!(out, "{}", serde_json::to_string_pretty(&json!({
+                    "notes_imported": report.notes_imported,
+                    "groups_created": report.groups_created,
+                    "skipped": skipped,
+                }))?)?;
+            } else {
+                writeln!(out, "Импортировано заметок: {}, создано групп: {}", report.notes_imported, report.groups_created)?;
+                for file in &report.skipped {
+                    writeln!(out, "Пропущен {}: {}", file.path.display(), file.reason)?;
+                }
+            }
+        }
+        Command::Open { .. } => {
+            return Err(CliError::Io("окно приложения fast-bind не запущено".to_string()));
+        }
+        Command::Help => write!(out, "{}", USAGE)?,
+    }
+    Ok(())
+}
+
+/// Создает заметку с группой и тегами, проверив все данные до записи
+pub fn add_note(
+    app_state: &mut AppState,
+    title: &str,
+    group: Option<&str>,
+    tags: &[String],
+    content: String,
+) -> Result<Uuid, CliError> {
+    let group_id = group.map(|key| resolve_group(app_state, key)).transpose()?;
+    Ok(app_state.create_note_with_tags(title.to_string(), content, group_id, tags.to_vec())?)
+}
+
+/// Проверяет, что для всех полей `{{name:...}}` заданы значения: спросить их в консоли негде
+pub fn check_fields(content: &str, fields: &HashMap<String, String>) -> Result<(), CliError> {
+    let missing: Vec<String> = snippet::field_names(content).into_iter()
+        .filter(|name| !fields.contains_key(name))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(CliError::Validation(format!(
+        "не заданы поля {} (используйте --set поле=значение)", missing.join(", ")
+    )))
+}
+
+/// Находит id заметки по id, началу id или точному заголовку (без учета регистра).
+/// В ленивом режиме ищет и среди незагруженных заметок
+pub fn resolve_note(app_state: &AppState, key: &str) -> Result<Uuid, CliError> {
+    let notes = app_state.quick_picker_candidates();
+    if let Ok(id) = Uuid::parse_str(key) {
+        return notes.iter().find(|note| note.id == id).map(|note| note.id)
+            .ok_or_else(|| CliError::NotFound(format!("заметка {} не найдена", key)));
+    }
+
+    let by_title: Vec<&NoteMetadata> = notes.iter()
+        .filter(|note| note.title.to_lowercase() == key.to_lowercase())
+        .collect();
+    let matches = if by_title.is_empty() && key.len() >= MIN_ID_PREFIX_LEN {
+        notes.iter()
+            .filter(|note| note.id.to_string().starts_with(&key.to_lowercase()))
+            .collect()
+    } else {
+        by_title
+    };
+
+    match matches.as_slice() {
+        [] => Err(CliError::NotFound(format!("заметка '{}' не найдена", key))),
+        [note] => Ok(note.id),
+        _ => Err(CliError::Validation(format!(
+            "под '{}' подходит несколько заметок, укажите id: {}",
+            key,
+            matches.iter().map(|note| short_id(note.id)).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
+/// Заметка по ключу `resolve_note`, подгруженная в `app_state`
+fn find_note<'a>(app_state: &'a mut AppState, key: &str) -> Result<&'a Note, CliError> {
+    let note_id = resolve_note(app_state, key)?;
+    app_state.ensure_note_loaded(note_id);
+    app_state.note(note_id).ok_or_else(|| CliError::NotFound(format!("заметка '{}' не найдена", key)))
+}
+
+/// Находит группу по id или названию (без учета регистра)
+fn resolve_group(app_state: &AppState, key: &str) -> Result<Uuid, CliError> {
+    let matches: Vec<&NoteGroup> = match Uuid::parse_str(key) {
+        Ok(id) => app_state.groups.iter().filter(|group| group.id == id).collect(),
+        Err(_) => app_state.groups.iter()
+            .filter(|group| group.name.to_lowercase() == key.to_lowercase())
+            .collect(),
+    };
+    match matches.as_slice() {
+        [] => Err(CliError::NotFound(format!("группа '{}' не найдена", key))),
+        [group] => Ok(group.id),
+        _ => Err(CliError::Validation(format!("групп с названием '{}' несколько, укажите id", key))),
+    }
+}
+
+/// Текст из `--content`: `STDIN_MARKER` читает stdin целиком
+fn read_content(content: Option<&str>, input: &mut dyn Read) -> Result<Option<String>, CliError> {
+    match content {
+        Some(STDIN_MARKER) => {
+            let mut text = String::new();
+            input.read_to_string(&mut text)?;
+            Ok(Some(text))
+        }
+        other => Ok(other.map(str::to_string)),
+    }
+}
+
+/// Проверяет заголовок и содержимое до записи, чтобы ошибка дала код `EXIT_VALIDATION`
+fn check_note(title: &str, content: &str) -> Result<(), CliError> {
+    Ok(ValidationRules::validate_note_creation(title, content).into_result()?)
+}
+
+/// Группы в порядке обхода дерева: каждая сразу после родителя
+fn groups_in_tree_order(groups: &[NoteGroup]) -> Vec<&NoteGroup> {
+    fn visit<'a>(groups: &'a [NoteGroup], parent_id: Option<Uuid>, out: &mut Vec<&'a NoteGroup>) {
+        for group in groups.iter().filter(|group| group.parent_id == parent_id) {
+            out.push(group);
+            visit(groups, Some(group.id), out);
+        }
+    }
+    let mut ordered = Vec::with_capacity(groups.len());
+    visit(groups, None, &mut ordered);
+    ordered
+}
+
+fn group_name(app_state: &AppState, group_id: Option<Uuid>) -> Option<&str> {
+    let group_id = group_id?;
+    app_state.groups.iter().find(|group| group.id == group_id).map(|group| group.name.as_str())
+}
+
+fn note_summary(app_state: &AppState, note: &Note) -> serde_json::Value {
+    json!({
+        "id": note.id,
+        "title": note.title,
+        "group_id": note.group_id,
+        "group": group_name(app_state, note.group_id),
+        "tags": note.tags,
+        "pinned": note.pinned,
+        "created_at": note.created_at,
+        "updated_at": note.updated_at,
+    })
+}
+
+fn write_note_line(app_state: &AppState, note: &Note, out: &mut dyn Write) -> io::Result<()> {
+    write!(out, "{}  {}{}", short_id(note.id), if note.pinned { "📌 " } else { "" }, note.title)?;
+    if let Some(group) = group_name(app_state, note.group_id) {
+        write!(out, "  [{}]", group)?;
+    }
+    for tag in &note.tags {
+        write!(out, "  #{}", tag)?;
+    }
+    writeln!(out)
+}
+
+fn write_id(note_id: Uuid, json: bool, out: &mut dyn Write) -> Result<(), CliError> {
+    if json {
+        writeln!(out, "{}", serde_json::to_string_pretty(&json!({ "id": note_id }))?)?;
+    } else {
+        writeln!(out, "{}", note_id)?;
+    }
+    Ok(())
+}
+
+fn short_id(id: Uuid) -> String {
+    id.to_string()[..SHORT_ID_LEN].to_string()
+}
@@ -1,4 +1,7 @@
+use std::path::PathBuf;
 use thiserror::Error;
+use uuid::Uuid;
+use crate::validation::ValidationError;
 
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -8,6 +11,117 @@ pub enum AppError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
     
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    
     #[error("Note not found")]
     NoteNotFound,
-} 
\ No newline at end of file
+    
+    #[error("Group not found: {0}")]
+    GroupNotFound(Uuid),
+    
+    #[error("Revision not found: {0}")]
+    RevisionNotFound(Uuid),
+    
+    /// Группу нельзя вложить в саму себя или в свою подгруппу
+    #[error("Group cycle: {0}")]
+    GroupCycle(Uuid),
+    
+    /// Данные не прошли проверку; каждая ошибка показывается пользователю отдельно
+    #[error("Validation error: {}", join_errors(.0))]
+    Validation(Vec<ValidationError>),
+    
+    /// Файл хранилища есть, но прочитать его содержимое нельзя
+    #[error("Corrupted data in {}: {reason}", .path.display())]
+    Corrupted { path: PathBuf, reason: String },
+    
+    /// Папку данных держит другой процесс fast-bind
+    #[error("Data directory is locked by another instance: {}", .0.display())]
+    LockHeld(PathBuf),
+    
+    #[error("Backup error: {0}")]
+    Backup(String),
+    
+    #[error("Hotkey error: {0}")]
+    Hotkey(String),
+    
+    #[error("Watch error: {0}")]
+    Watch(String),
+}
+
+impl AppError {
+    /// Стабильный код ошибки для командной строки, журналов и внешних инструментов.
+    /// Коды не меняются между версиями; у новых видов ошибок - новые коды
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Io(_) => "io",
+            AppError::Json(_) => "json",
+            AppError::Database(_) => "database",
+            AppError::NoteNotFound => "note_not_found",
+            AppError::GroupNotFound(_) => "group_not_found",
+            AppError::RevisionNotFound(_) => "revision_not_found",
+            AppError::GroupCycle(_) => "group_cycle",
+            AppError::Validation(_) => "validation",
+            AppError::Corrupted { .. } => "corrupted",
+            AppError::LockHeld(_) => "lock_held",
+            AppError::Backup(_) => "backup",
+            AppError::Hotkey(_) => "hotkey",
+            AppError::Watch(_) => "watch",
+        }
+    }
+
+    /// Сбой чтения или записи данных: заметки могут не сохраниться, пока причину не устранят
+    pub fn is_storage_failure(&self) -> bool {
+        matches!(self, AppError::Io(_) | AppError::Json(_) | AppError::Database(_) | AppError::Corrupted { .. })
+    }
+
+    /// Ошибки, которые можно показать у полей формы (пусто, если ошибка не относится к вводу)
+    pub fn validation_errors(&self) -> Vec<ValidationError> {
+        match self {
+            AppError::Validation(errors) => errors.clone(),
+            AppError::GroupNotFound(id) => vec![ValidationError::GroupNotFound(*id)],
+            AppError::GroupCycle(id) => vec![ValidationError::CircularGroupDependency(*id)],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Ошибка по результатам проверки. Ссылка на несуществующую группу и цикл в иерархии -
+    /// не ошибки ввода, у них собственные виды
+    pub fn from_validation(errors: Vec<ValidationError>) -> Self {
+        for error in &errors {
+            match *error {
+                ValidationError::GroupNotFound(id) => return AppError::GroupNotFound(id),
+                ValidationError::CircularGroupDependency(id) => return AppError::GroupCycle(id),
+                _ => {}
+            }
+        }
+        AppError::Validation(errors)
+    }
+
+    /// Содержимое файла не разбирается как JSON
+    pub fn corrupted(path: impl Into<PathBuf>, error: serde_json::Error) -> Self {
+        AppError::Corrupted { path: path.into(), reason: error.to_string() }
+    }
+}
+
+fn join_errors(errors: &[ValidationError]) -> String {
+    errors.iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl From<ValidationError> for AppError {
+    fn from(error: ValidationError) -> Self {
+        AppError::from_validation(vec![error])
+    }
+}
+
+impl From<zip::result::ZipError> for AppError {
+    fn from(error: zip::result::ZipError) -> Self {
+        match error {
+            zip::result::ZipError::Io(e) => AppError::Io(e),
+            e => AppError::Backup(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+/// Экспорт заметок и групп в дерево папок с Markdown-файлами
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::fs_utils;
+use crate::markdown::{self, UniqueNames, MARKDOWN_EXTENSION};
+use crate::notes::{Note, NoteGroup};
+use crate::{log_info, log_success};
+
+/// Итог экспорта
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportReport {
+    pub target_dir: PathBuf,
+    pub notes_exported: usize,
+    pub groups_exported: usize,
+}
+
+/// Свободные имена в каждой папке экспорта; уже лежащие там файлы не перезаписываются
+#[derive(Default)]
+struct DirectoryNames {
+    dirs: HashMap<PathBuf, UniqueNames>,
+}
+
+impl DirectoryNames {
+    fn claim(&mut self, dir: &Path, stem: &str, extension: &str) -> Result<PathBuf, AppError> {
+        if !self.dirs.contains_key(dir) {
+            let mut names = UniqueNames::new();
+            for entry in fs::read_dir(dir)? {
+                names.reserve(&entry?.file_name().to_string_lossy());
+            }
+            self.dirs.insert(dir.to_path_buf(), names);
+        }
+        let names = self.dirs.get_mut(dir).expect("папка добавлена выше");
+        Ok(dir.join(names.claim(&markdown::sanitize_file_name(stem), extension)))
+    }
+}
+
+/// Записывает каждую группу папкой (с учетом вложенности `parent_id`),
+/// а каждую заметку - `.md` файлом с front matter в папке своей группы.
+/// Заметки без группы или из несуществующей группы попадают в корень `target_dir`.
+pub fn export_markdown(notes: &[Note], groups: &[NoteGroup], target_dir: &Path) -> Result<ExportReport, AppError> {
+    log_info!("export", "markdown", &format!("Экспорт {} заметок и {} групп в {}", notes.len(), groups.len(), target_dir.display()));
+    fs::create_dir_all(target_dir)?;
+
+    let mut names = DirectoryNames::default();
+    let group_dirs = create_group_dirs(groups, target_dir, &mut names)?;
+
+    for note in notes {
+        let dir = note.group_id
+            .and_then(|group_id| group_dirs.get(&group_id))
+            .map(PathBuf::as_path)
+            .unwrap_or(target_dir);
+        let path = names.claim(dir, &note.title, MARKDOWN_EXTENSION)?;
+        fs_utils::atomic_write(&path, markdown::render_note(note))?;
+    }
+
+    log_success!("export", "markdown");
+    Ok(ExportReport {
+        target_dir: target_dir.to_path_buf(),
+        notes_exported: notes.len(),
+        groups_exported: group_dirs.len(),
+    })
+}
+
+/// Создает папки групп: сначала родителей, затем детей.
+/// Группы с потерянным родителем или в цикле `parent_id` создаются в корне.
+fn create_group_dirs(groups: &[NoteGroup], target_dir: &Path, names: &mut DirectoryNames) -> Result<HashMap<Uuid, PathBuf>, AppError> {
+    let mut group_dirs: HashMap<Uuid, PathBuf> = HashMap::new();
+    let mut pending: Vec<&NoteGroup> = groups.iter().collect();
+
+    while !pending.is_empty() {
+        let mut progressed = false;
+        let mut still_pending = Vec::new();
+
+        for group in pending {
+            let parent_dir = match group.parent_id {
+                None => Some(target_dir.to_path_buf()),
+                Some(parent_id) if !groups.iter().any(|g| g.id == parent_id) => Some(target_dir.to_path_buf()),
+                Some(parent_id) => group_dirs.get(&parent_id).cloned(),
+            };
+            match parent_dir {
+                Some(parent_dir) => {
+                    let dir = names.claim(&parent_dir, &group.name, "")?;
+                    fs::create_dir_all(&dir)?;
+                    group_dirs.insert(group.id, dir);
+                    progressed = true;
+                }
+                None => still_pending.push(group),
+            }
+        }
+
+        if !progressed {
+            // Остались только группы, ссылающиеся друг на друга по кругу: разрываем цикл в корне
+            let group = still_pending.remove(0);
+            let dir = names.claim(target_dir, &group.name, "")?;
+            fs::create_dir_all(&dir)?;
+            group_dirs.insert(group.id, dir);
+        }
+        pending = still_pending;
+    }
+
+    Ok(group_dirs)
+}
@@ -0,0 +1,63 @@
+/// Вспомогательные функции для надёжной работы с файлами
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Расширение временных файлов, создаваемых при атомарной записи
+pub const TEMP_EXTENSION: &str = "tmp";
+
+/// Путь временного файла для атомарной записи (`note.json` -> `note.json.tmp`)
+pub fn temp_path_for(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(TEMP_EXTENSION);
+    path.with_file_name(file_name)
+}
+
+/// Атомарно записывает файл: данные пишутся во временный файл, сбрасываются на диск
+/// и только затем переименовываются поверх старой версии.
+/// При сбое в любой момент на диске остаётся либо старая, либо новая версия целиком.
+pub fn atomic_write(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let temp_path = temp_path_for(path);
+
+    let result = write_and_sync(&temp_path, contents.as_ref())
+        .and_then(|_| fs::rename(&temp_path, path));
+    if result.is_err() {
+        // Недописанный временный файл не нужен, исходный файл не тронут
+        let _ = fs::remove_file(&temp_path);
+        return result;
+    }
+
+    sync_parent_dir(path);
+    Ok(())
+}
+
+fn write_and_sync(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+/// Сбрасывает на диск запись о переименовании в директории (на Windows не поддерживается)
+fn sync_parent_dir(path: &Path) {
+    #[cfg(unix)]
+    if let Some(dir) = path.parent().and_then(|parent| File::open(parent).ok()) {
+        let _ = dir.sync_all();
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+/// Удаляет временные файлы, оставшиеся после прерванной записи.
+/// Возвращает количество удалённых файлов.
+pub fn remove_stale_temp_files(dir: &Path) -> io::Result<usize> {
+    let mut removed = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == TEMP_EXTENSION) {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
@@ -0,0 +1,88 @@
+/// История версий заметок и построчное сравнение версий
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::notes::Note;
+
+/// Максимальное количество хранимых версий одной заметки (старые удаляются)
+pub const MAX_REVISIONS_PER_NOTE: usize = 50;
+
+/// Произведение количества строк, выше которого diff не считается построчно (защита от O(n*m) на огромных текстах)
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Сохраненная предыдущая версия заметки
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteRevision {
+    pub id: Uuid,
+    pub note_id: Uuid,
+    pub title: String,
+    pub content: String,
+    /// Когда эта версия была сохранена (время последнего изменения заметки на тот момент)
+    pub saved_at: DateTime<Utc>,
+}
+
+impl NoteRevision {
+    /// Снимок текущего состояния заметки
+    pub fn from_note(note: &Note) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            note_id: note.id,
+            title: note.title.clone(),
+            content: note.content.clone(),
+            saved_at: note.updated_at,
+        }
+    }
+}
+
+/// Строка результата сравнения двух версий
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Unchanged(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Построчное сравнение двух текстов (наибольшая общая подпоследовательность строк)
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let (n, m) = (old_lines.len(), new_lines.len());
+
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        return old_lines.iter().map(|l| DiffLine::Removed(l.to_string()))
+            .chain(new_lines.iter().map(|l| DiffLine::Added(l.to_string())))
+            .collect();
+    }
+
+    // lcs[i][j] - длина общей подпоследовательности для old[i..] и new[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_lines[i] == new_lines[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_lines[i] == new_lines[j] {
+            result.push(DiffLine::Unchanged(old_lines[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(DiffLine::Removed(old_lines[i].to_string()));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(new_lines[j].to_string()));
+            j += 1;
+        }
+    }
+    result.extend(old_lines[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    result.extend(new_lines[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+
+    result
+}
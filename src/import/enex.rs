@@ -0,0 +1,332 @@
+/// Импорт экспорта Evernote (`.enex`)
+///
+/// Один `.enex` файл - один блокнот. Текст заметки хранится в ENML (XHTML внутри CDATA)
+/// и переводится в Markdown; вложения не импортируются.
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use uuid::Uuid;
+use xml::reader::{EventReader, XmlEvent};
+use crate::error::AppError;
+use crate::notes::NoteGroup;
+use super::{ImportContext, ImportResult, NoteDraft, SkipReason};
+
+/// Расширение файлов экспорта Evernote
+const ENEX_EXTENSION: &str = "enex";
+
+/// Импортирует файл `.enex` или все `.enex` файлы папки.
+/// Каждый файл становится группой с именем файла, заметки - заметками с тегами и датами.
+pub fn import_enex(path: &Path, existing_groups: &[NoteGroup], existing_note_ids: &HashSet<Uuid>) -> Result<ImportResult, AppError> {
+    let files: Vec<PathBuf> = if path.is_dir() {
+        let mut files: Vec<PathBuf> = fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|file| file.extension().is_some_and(|ext| ext.eq_ignore_ascii_case(ENEX_EXTENSION)))
+            .collect();
+        files.sort();
+        files
+    } else {
+        // Открываем сразу, чтобы отсутствующий файл был ошибкой всего импорта
+        File::open(path)?;
+        vec![path.to_path_buf()]
+    };
+
+    let mut context = ImportContext::new("enex", path, existing_groups, existing_note_ids);
+    for file in files {
+        import_notebook(&mut context, &file);
+    }
+    Ok(context.finish("enex"))
+}
+
+/// Заметка в процессе разбора XML
+#[derive(Default)]
+struct EnexNote {
+    title: String,
+    content: String,
+    created: Option<DateTime<Utc>>,
+    updated: Option<DateTime<Utc>>,
+    tags: Vec<String>,
+}
+
+fn import_notebook(context: &mut ImportContext, file: &Path) {
+    let reader = match File::open(file) {
+        Ok(reader) => EventReader::new(BufReader::new(reader)),
+        Err(e) => {
+            context.skip(file, SkipReason::Unreadable(e.to_string()));
+            return;
+        }
+    };
+
+    let notebook = file.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let group_id = match context.group(&notebook, None) {
+        Ok(group_id) => group_id,
+        Err(reason) => {
+            context.skip(file, reason);
+            return;
+        }
+    };
+
+    let mut note: Option<EnexNote> = None;
+    let mut text = String::new();
+
+    for event in reader {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                // Уже прочитанные заметки остаются, остаток файла пропускается
+                context.skip(file, SkipReason::Invalid(format!("ошибка разбора XML: {}", e)));
+                return;
+            }
+        };
+
+        match event {
+            XmlEvent::StartElement { name, .. } => {
+                if name.local_name == "note" {
+                    note = Some(EnexNote::default());
+                }
+                text.clear();
+            }
+            XmlEvent::Characters(data) | XmlEvent::CData(data) => text.push_str(&data),
+            XmlEvent::EndElement { name } => {
+                match (name.local_name.as_str(), note.as_mut()) {
+                    ("note", Some(_)) => {
+                        let finished = note.take().unwrap_or_default();
+                        let draft = NoteDraft {
+                            id: None,
+                            title: Some(finished.title),
+                            fallback_title: String::new(),
+                            content: enml_to_markdown(&finished.content),
+                            created_at: finished.created,
+                            updated_at: finished.updated,
+                            pinned: false,
+                            group_id: Some(group_id),
+                            tags: finished.tags,
+                        };
+                        context.add_note(file, draft);
+                    }
+                    ("title", Some(note)) => note.title = text.trim().to_string(),
+                    ("content", Some(note)) => note.content = std::mem::take(&mut text),
+                    ("created", Some(note)) => note.created = parse_enex_time(text.trim()),
+                    ("updated", Some(note)) => note.updated = parse_enex_time(text.trim()),
+                    ("tag", Some(note)) => note.tags.push(text.trim().to_string()),
+                    _ => {}
+                }
+                // Текст вложений (base64) не накапливаем
+                text.clear();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Дата Evernote в формате `20240131T120000Z`
+fn parse_enex_time(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|time| time.and_utc())
+}
+
+/// Переводит ENML (XHTML Evernote) в Markdown: абзацы, переносы, заголовки, списки,
+/// чекбоксы, выделение, ссылки и код. Неизвестные теги отбрасываются, их текст остается.
+fn enml_to_markdown(enml: &str) -> String {
+    let mut out = String::new();
+    // Стек списков: `Some(n)` - нумерованный со следующим номером n, `None` - маркированный
+    let mut lists: Vec<Option<usize>> = Vec::new();
+    let mut link: Option<String> = None;
+    let mut in_pre = false;
+    let mut rest = enml;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+        push_text(&mut out, &rest[..start], in_pre);
+        rest = &rest[start..];
+
+        // Комментарии, объявления XML и DOCTYPE пропускаем целиком
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        let closing = tag.starts_with('/');
+        let tag_body = tag.trim_start_matches('/').trim_end_matches('/');
+        let name = tag_body.split_whitespace().next().unwrap_or_default().to_lowercase();
+
+        match (name.as_str(), closing) {
+            ("br", _) => out.push('\n'),
+            ("p" | "div" | "en-note" | "table" | "tr" | "blockquote", _) => ensure_line_break(&mut out),
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                ensure_line_break(&mut out);
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                out.push_str(&"#".repeat(level));
+                out.push(' ');
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => ensure_line_break(&mut out),
+            ("ul", false) => lists.push(None),
+            ("ol", false) => lists.push(Some(1)),
+            ("ul" | "ol", true) => {
+                lists.pop();
+                ensure_line_break(&mut out);
+            }
+            ("li", false) => {
+                ensure_line_break(&mut out);
+                out.push_str(&"  ".repeat(lists.len().saturating_sub(1)));
+                match lists.last_mut() {
+                    Some(Some(number)) => {
+                        out.push_str(&format!("{}. ", number));
+                        *number += 1;
+                    }
+                    _ => out.push_str("- "),
+                }
+            }
+            ("li", true) => ensure_line_break(&mut out),
+            ("td" | "th", true) => out.push(' '),
+            ("hr", _) => {
+                ensure_line_break(&mut out);
+                out.push_str("---\n");
+            }
+            ("en-todo", _) => {
+                let checked = attribute(tag_body, "checked").is_some_and(|value| value == "true");
+                out.push_str(if checked { "[x] " } else { "[ ] " });
+            }
+            ("b" | "strong", _) => out.push_str("**"),
+            ("i" | "em", _) => out.push('*'),
+            ("s" | "strike" | "del", _) => out.push_str("~~"),
+            ("code", _) if !in_pre => out.push('`'),
+            ("pre", false) => {
+                ensure_line_break(&mut out);
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                ensure_line_break(&mut out);
+                out.push_str("```\n");
+                in_pre = false;
+            }
+            ("a", false) => {
+                link = attribute(tag_body, "href");
+                out.push('[');
+            }
+            ("a", true) => match link.take() {
+                Some(href) => out.push_str(&format!("]({})", href)),
+                None => out.push(']'),
+            },
+            ("en-media", _) => out.push_str("[вложение]"),
+            _ => {}
+        }
+    }
+
+    // Не больше одной пустой строки подряд и без пробелов по краям
+    let mut result = String::new();
+    let mut blank_lines = 0;
+    for line in out.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+    result.trim().to_string()
+}
+
+/// Добавляет текст, раскрывая сущности; вне `<pre>` переводы строк и отступы HTML схлопываются
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let decoded = decode_entities(text);
+    if in_pre {
+        out.push_str(&decoded);
+        return;
+    }
+    let mut previous_space = out.is_empty() || out.ends_with(char::is_whitespace);
+    for c in decoded.chars() {
+        // Неразрывный пробел из &nbsp; сохраняется как обычный пробел, даже подряд
+        if c == '\u{a0}' {
+            out.push(' ');
+            previous_space = true;
+        } else if c.is_whitespace() {
+            if !previous_space {
+                out.push(' ');
+                previous_space = true;
+            }
+        } else {
+            out.push(c);
+            previous_space = false;
+        }
+    }
+}
+
+fn ensure_line_break(out: &mut String) {
+    let trimmed_len = out.trim_end_matches(' ').len();
+    out.truncate(trimmed_len);
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Значение атрибута тега (`href="..."` или `href='...'`)
+fn attribute(tag_body: &str, name: &str) -> Option<String> {
+    let position = tag_body.find(&format!("{}=", name))?;
+    let value = &tag_body[position + name.len() + 1..];
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &value[1..];
+    let end = value.find(quote)?;
+    Some(decode_entities(&value[..end]))
+}
+
+/// Именованные и числовые сущности HTML, встречающиеся в ENML
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,194 @@
+/// Импорт папки экспорта Joplin в формате RAW
+///
+/// Каждый элемент Joplin лежит в отдельном `<id>.md`: первая строка - название,
+/// затем текст, а в конце блок метаданных `ключ: значение` (id, parent_id, даты, type_).
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::notes::NoteGroup;
+use super::{read_text_file, ImportContext, ImportResult, NoteDraft, SkipReason};
+
+/// Типы элементов Joplin (поле `type_`)
+const TYPE_NOTE: &str = "1";
+const TYPE_FOLDER: &str = "2";
+const TYPE_TAG: &str = "5";
+const TYPE_NOTE_TAG: &str = "6";
+
+/// Элемент экспорта: название, текст и метаданные
+struct JoplinItem {
+    path: PathBuf,
+    title: String,
+    body: String,
+    metadata: HashMap<String, String>,
+}
+
+impl JoplinItem {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str).filter(|value| !value.is_empty())
+    }
+
+    /// Дата из `user_*` поля (её видит пользователь), иначе из служебного
+    fn time(&self, key: &str) -> Option<DateTime<Utc>> {
+        self.get(&format!("user_{}", key))
+            .or_else(|| self.get(key))
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|time| time.with_timezone(&Utc))
+    }
+}
+
+/// Блокноты Joplin становятся группами (с вложенностью), заметки - заметками с тегами и датами.
+/// Id Joplin (32 шестнадцатеричных символа) сохраняются, поэтому повторный импорт не создает дубликатов.
+pub fn import_joplin_raw(export_dir: &Path, existing_groups: &[NoteGroup], existing_note_ids: &HashSet<Uuid>) -> Result<ImportResult, AppError> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(export_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+
+    let mut context = ImportContext::new("joplin", export_dir, existing_groups, existing_note_ids);
+    let mut folders = Vec::new();
+    let mut notes = Vec::new();
+    let mut tag_titles: HashMap<String, String> = HashMap::new();
+    let mut note_tags: HashMap<String, Vec<String>> = HashMap::new();
+
+    for path in paths {
+        let item = match read_text_file(&path) {
+            Ok((text, _)) => parse_item(path, &text),
+            Err(reason) => {
+                context.skip(&path, reason);
+                continue;
+            }
+        };
+        if item.get("encryption_applied") == Some("1") {
+            context.skip(&item.path, SkipReason::Invalid("заметка зашифрована в Joplin".to_string()));
+            continue;
+        }
+
+        match item.get("type_") {
+            Some(TYPE_NOTE) => notes.push(item),
+            Some(TYPE_FOLDER) => folders.push(item),
+            Some(TYPE_TAG) => {
+                if let Some(id) = item.get("id") {
+                    tag_titles.insert(id.to_string(), item.title.clone());
+                }
+            }
+            Some(TYPE_NOTE_TAG) => {
+                if let (Some(note_id), Some(tag_id)) = (item.get("note_id"), item.get("tag_id")) {
+                    note_tags.entry(note_id.to_string()).or_default().push(tag_id.to_string());
+                }
+            }
+            // Вложения, ревизии и прочие служебные элементы не импортируются
+            _ => {}
+        }
+    }
+
+    let folder_groups = create_folder_groups(&mut context, &folders);
+
+    for item in notes {
+        let group_id = match item.get("parent_id") {
+            Some(parent_id) => match folder_groups.get(parent_id) {
+                Some(Ok(group_id)) => Some(*group_id),
+                Some(Err(reason)) => {
+                    context.skip(&item.path, reason.clone());
+                    continue;
+                }
+                None => None,
+            },
+            None => None,
+        };
+        let tags = item.get("id")
+            .and_then(|id| note_tags.get(id))
+            .map(|tag_ids| tag_ids.iter().filter_map(|tag_id| tag_titles.get(tag_id).cloned()).collect())
+            .unwrap_or_default();
+
+        let draft = NoteDraft {
+            id: item.get("id").and_then(|id| Uuid::parse_str(id).ok()),
+            title: Some(item.title.clone()),
+            fallback_title: String::new(),
+            content: item.body.clone(),
+            created_at: item.time("created_time"),
+            updated_at: item.time("updated_time"),
+            pinned: false,
+            group_id,
+            tags,
+        };
+        context.add_note(&item.path, draft);
+    }
+
+    Ok(context.finish("joplin"))
+}
+
+/// Создает группы для блокнотов, начиная с корневых. Блокноты с потерянным родителем
+/// попадают в корень, а блокноты глубже допустимого получают причину пропуска.
+fn create_folder_groups(context: &mut ImportContext, folders: &[JoplinItem]) -> HashMap<String, Result<Uuid, SkipReason>> {
+    let folder_ids: HashSet<&str> = folders.iter().filter_map(|f| f.get("id")).collect();
+    let mut groups: HashMap<String, Result<Uuid, SkipReason>> = HashMap::new();
+    let mut pending: Vec<&JoplinItem> = folders.iter().filter(|f| f.get("id").is_some()).collect();
+
+    while !pending.is_empty() {
+        let mut still_pending = Vec::new();
+        let count_before = pending.len();
+
+        for folder in pending {
+            let parent = match folder.get("parent_id") {
+                Some(parent_id) if folder_ids.contains(parent_id) => groups.get(parent_id).map(|result| result.clone().map(Some)),
+                _ => Some(Ok(None)),
+            };
+            let result = match parent {
+                Some(Ok(parent_id)) => context.group(&folder.title, parent_id),
+                Some(Err(reason)) => Err(reason),
+                None => {
+                    still_pending.push(folder);
+                    continue;
+                }
+            };
+            if let Err(ref reason) = result {
+                context.skip(&folder.path, reason.clone());
+            }
+            groups.insert(folder.get("id").unwrap_or_default().to_string(), result);
+        }
+
+        if still_pending.len() == count_before {
+            // Блокноты ссылаются друг на друга по кругу: разрываем цикл в корне
+            let folder = still_pending.remove(0);
+            let result = context.group(&folder.title, None);
+            if let Err(ref reason) = result {
+                context.skip(&folder.path, reason.clone());
+            }
+            groups.insert(folder.get("id").unwrap_or_default().to_string(), result);
+        }
+        pending = still_pending;
+    }
+
+    groups
+}
+
+/// Разбирает элемент: метаданные - непрерывный блок строк `ключ: значение` в конце файла
+fn parse_item(path: PathBuf, text: &str) -> JoplinItem {
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+
+    let mut metadata_start = lines.len();
+    while metadata_start > 0 && parse_metadata_line(lines[metadata_start - 1]).is_some() {
+        metadata_start -= 1;
+    }
+    let metadata = lines[metadata_start..].iter()
+        .filter_map(|line| parse_metadata_line(line))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+    let content_lines = &lines[..metadata_start];
+    let title = content_lines.first().map(|line| line.trim().to_string()).unwrap_or_default();
+    // После названия идет пустая строка, перед метаданными - тоже
+    let body = content_lines.get(2..).unwrap_or_default().join("\n").trim_end().to_string();
+
+    JoplinItem { path, title, body, metadata }
+}
+
+fn parse_metadata_line(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    let is_key = !key.is_empty() && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    is_key.then(|| (key, value.trim()))
+}
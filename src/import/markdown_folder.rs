@@ -0,0 +1,240 @@
+/// Импорт папки с Markdown и текстовыми файлами, в том числе хранилища Obsidian
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::markdown;
+use crate::notes::NoteGroup;
+use super::{read_text_file, ImportContext, ImportResult, NoteDraft, SkipReason};
+
+/// Расширения файлов, которые превращаются в заметки
+const IMPORT_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+/// Обходит папку: подпапки становятся группами (существующая группа с тем же именем и родителем
+/// используется повторно), `.md`/`.txt` файлы - заметками. Файлы из корня попадают в заметки без группы.
+/// Ошибка возвращается, только если не читается сама папка.
+pub fn import_markdown_folder(source_dir: &Path, existing_groups: &[NoteGroup], existing_note_ids: &HashSet<Uuid>) -> Result<ImportResult, AppError> {
+    FolderImporter::run(source_dir, existing_groups, existing_note_ids, false)
+}
+
+/// Импорт хранилища Obsidian: как папка Markdown, но теги `#tag` из текста добавляются к тегам заметки,
+/// а вики-ссылки `[[имя файла]]` переписываются на названия импортированных заметок
+pub fn import_obsidian_vault(vault_dir: &Path, existing_groups: &[NoteGroup], existing_note_ids: &HashSet<Uuid>) -> Result<ImportResult, AppError> {
+    FolderImporter::run(vault_dir, existing_groups, existing_note_ids, true)
+}
+
+struct FolderImporter {
+    context: ImportContext,
+    obsidian: bool,
+    /// Имя файла без расширения (в нижнем регистре) -> индекс заметки, для вики-ссылок
+    notes_by_stem: HashMap<String, usize>,
+}
+
+impl FolderImporter {
+    fn run(source_dir: &Path, existing_groups: &[NoteGroup], existing_note_ids: &HashSet<Uuid>, obsidian: bool) -> Result<ImportResult, AppError> {
+        let source = if obsidian { "obsidian" } else { "markdown" };
+        // Корень читаем отдельно: его ошибка - ошибка всего импорта
+        fs::read_dir(source_dir)?;
+
+        let mut importer = Self {
+            context: ImportContext::new(source, source_dir, existing_groups, existing_note_ids),
+            obsidian,
+            notes_by_stem: HashMap::new(),
+        };
+        importer.visit_dir(source_dir, None);
+        if obsidian {
+            importer.resolve_wikilinks();
+        }
+        Ok(importer.context.finish(source))
+    }
+
+    fn visit_dir(&mut self, dir: &Path, group_id: Option<Uuid>) {
+        let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect(),
+            Err(e) => {
+                self.context.skip(dir, SkipReason::Unreadable(e.to_string()));
+                return;
+            }
+        };
+        entries.sort();
+
+        for path in entries {
+            // Скрытые файлы и служебные папки других программ (.git, .obsidian) пропускаем молча
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if name.starts_with('.') {
+                continue;
+            }
+
+            let file_type = match fs::symlink_metadata(&path) {
+                Ok(metadata) => metadata.file_type(),
+                Err(e) => {
+                    self.context.skip(&path, SkipReason::Unreadable(e.to_string()));
+                    continue;
+                }
+            };
+
+            if file_type.is_symlink() {
+                self.context.skip(&path, SkipReason::Symlink);
+            } else if file_type.is_dir() {
+                match self.context.group(&name, group_id) {
+                    Ok(subgroup_id) => self.visit_dir(&path, Some(subgroup_id)),
+                    Err(reason) => self.context.skip(&path, reason),
+                }
+            } else {
+                self.import_file(&path, group_id);
+            }
+        }
+    }
+
+    fn import_file(&mut self, path: &Path, group_id: Option<Uuid>) {
+        let extension = path.extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !IMPORT_EXTENSIONS.contains(&extension.as_str()) {
+            self.context.skip(path, SkipReason::UnsupportedType);
+            return;
+        }
+
+        let (text, modified) = match read_text_file(path) {
+            Ok(file) => file,
+            Err(reason) => {
+                self.context.skip(path, reason);
+                return;
+            }
+        };
+
+        let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        let draft = self.draft_from_text(&text, &stem, group_id, modified);
+        if let Some(index) = self.context.add_note(path, draft) {
+            self.notes_by_stem.entry(stem.to_lowercase()).or_insert(index);
+        }
+    }
+
+    /// Заметка из текста файла: заголовок берется из front matter, первого заголовка `#` или имени файла
+    fn draft_from_text(&self, text: &str, file_stem: &str, group_id: Option<Uuid>, modified: Option<DateTime<Utc>>) -> NoteDraft {
+        let (front_matter, body) = markdown::split_front_matter(text);
+        // Экспорт дописывает перевод строки в конец файла
+        let content = body.strip_suffix('\n').unwrap_or(body);
+
+        let mut tags = front_matter.tags;
+        if self.obsidian {
+            tags.extend(inline_tags(content));
+        }
+
+        NoteDraft {
+            id: front_matter.id,
+            title: front_matter.title.or_else(|| markdown::first_heading(content)),
+            fallback_title: file_stem.to_string(),
+            content: content.to_string(),
+            created_at: front_matter.created.or(modified),
+            updated_at: front_matter.updated.or(modified),
+            pinned: front_matter.pinned.unwrap_or(false),
+            group_id,
+            tags,
+        }
+    }
+
+    /// Переписывает `[[файл]]` и `[[файл#раздел|текст]]` на `[[Название заметки]]`, сохраняя текст ссылки.
+    /// Ссылки на неимпортированные файлы и вложения (`![[...]]`) остаются как есть.
+    fn resolve_wikilinks(&mut self) {
+        let titles: HashMap<String, String> = self.notes_by_stem.iter()
+            .map(|(stem, &index)| (stem.clone(), self.context.result.notes[index].title.clone()))
+            .collect();
+
+        for note in &mut self.context.result.notes {
+            note.content = rewrite_wikilinks(&note.content, |target| {
+                // Ссылка может содержать путь: [[папка/файл]]
+                let stem = target.rsplit('/').next().unwrap_or(target);
+                let stem = stem.strip_suffix(".md").unwrap_or(stem);
+                titles.get(&stem.to_lowercase()).cloned()
+            });
+        }
+    }
+}
+
+/// Заменяет вики-ссылки с помощью `resolve` (по цели ссылки без раздела и текста)
+fn rewrite_wikilinks(text: &str, resolve: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("[[") {
+        let is_embed = rest[..start].ends_with('!');
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let link = &after[..end];
+        let (target_part, alias) = match link.split_once('|') {
+            Some((target, alias)) => (target, Some(alias)),
+            None => (link, None),
+        };
+        let target = target_part.split('#').next().unwrap_or_default().trim();
+
+        match resolve(target).filter(|_| !is_embed && !link.contains('\n')) {
+            Some(title) => {
+                out.push_str("[[");
+                out.push_str(&title);
+                if let Some(alias) = alias {
+                    out.push('|');
+                    out.push_str(alias);
+                }
+                out.push_str("]]");
+            }
+            None => {
+                out.push_str("[[");
+                out.push_str(link);
+                out.push_str("]]");
+            }
+        }
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Теги вида `#tag` и `#проект/задача` из текста, кроме блоков кода и заголовков
+fn inline_tags(text: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        // Внутри `кода` теги не ищем: берем только части строки вне обратных кавычек
+        for (index, segment) in line.split('`').enumerate() {
+            if index % 2 == 1 {
+                continue;
+            }
+            let mut previous = ' ';
+            let chars: Vec<char> = segment.chars().collect();
+            let mut i = 0;
+            while i < chars.len() {
+                if chars[i] == '#' && previous.is_whitespace() {
+                    let tag: String = chars[i + 1..].iter()
+                        .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '/'))
+                        .collect();
+                    // "#1" - не тег, а "# Заголовок" - заголовок
+                    if tag.chars().any(|c| !c.is_ascii_digit()) {
+                        i += tag.chars().count();
+                        tags.push(tag);
+                    }
+                }
+                previous = chars[i];
+                i += 1;
+            }
+        }
+    }
+    tags
+}
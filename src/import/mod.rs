@@ -0,0 +1,293 @@
+/// Импорт заметок из папок с Markdown и из других программ заметок
+mod enex;
+mod joplin;
+mod markdown_folder;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::notes::{Note, NoteGroup};
+use crate::validation::{ValidationError, ValidationRules};
+use crate::{log_info, log_success};
+
+pub use enex::import_enex;
+pub use joplin::import_joplin_raw;
+pub use markdown_folder::{import_markdown_folder, import_obsidian_vault};
+
+/// Запас на служебные данные (front matter, метаданные Joplin) сверх максимального размера заметки
+const METADATA_ALLOWANCE: u64 = 64 * 1024;
+
+/// Откуда импортировать заметки
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportSource {
+    /// Папка с `.md` и `.txt` файлами (в том числе результат экспорта)
+    #[default]
+    MarkdownFolder,
+    /// Хранилище Obsidian: папки, вики-ссылки `[[...]]` и теги `#tag`
+    Obsidian,
+    /// Папка экспорта Joplin в формате RAW
+    JoplinRaw,
+    /// Файл `.enex` из Evernote или папка с такими файлами
+    Enex,
+}
+
+impl ImportSource {
+    pub const ALL: [ImportSource; 4] = [
+        ImportSource::MarkdownFolder,
+        ImportSource::Obsidian,
+        ImportSource::JoplinRaw,
+        ImportSource::Enex,
+    ];
+
+    /// Название для интерфейса
+    pub fn label(self) -> &'static str {
+        match self {
+            ImportSource::MarkdownFolder => "📝 Папка Markdown",
+            ImportSource::Obsidian => "🟣 Obsidian",
+            ImportSource::JoplinRaw => "📓 Joplin (RAW)",
+            ImportSource::Enex => "🐘 Evernote (.enex)",
+        }
+    }
+}
+
+/// Почему файл, папка или заметка не были импортированы
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Файл не текстовый (не `.md`, `.markdown` или `.txt`)
+    UnsupportedType,
+    /// Файл или заметка больше допустимого размера
+    TooLarge(u64),
+    /// Файл не в кодировке UTF-8
+    NotUtf8,
+    /// Заметка с таким id уже есть
+    AlreadyExists,
+    /// Папка глубже `ValidationRules::MAX_GROUP_DEPTH`
+    TooDeep,
+    /// Символическая ссылка (не переходим, чтобы не зациклиться)
+    Symlink,
+    /// Ошибка чтения
+    Unreadable(String),
+    /// Данные не удалось разобрать или они не прошли проверку
+    Invalid(String),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::UnsupportedType => write!(f, "неподдерживаемый тип файла"),
+            SkipReason::TooLarge(bytes) => write!(f, "слишком большой файл ({} КБ)", bytes / 1024),
+            SkipReason::NotUtf8 => write!(f, "файл не в кодировке UTF-8"),
+            SkipReason::AlreadyExists => write!(f, "заметка с таким id уже существует"),
+            SkipReason::TooDeep => write!(f, "превышена глубина вложенности групп ({})", ValidationRules::MAX_GROUP_DEPTH),
+            SkipReason::Symlink => write!(f, "символическая ссылка"),
+            SkipReason::Unreadable(error) => write!(f, "ошибка чтения: {}", error),
+            SkipReason::Invalid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Пропущенный при импорте файл или папка
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: SkipReason,
+}
+
+/// Итог импорта
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub notes_imported: usize,
+    pub groups_created: usize,
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Прочитанные заметки и новые группы, еще не сохраненные в хранилище
+#[derive(Debug, Default)]
+pub struct ImportResult {
+    pub notes: Vec<Note>,
+    pub groups: Vec<NoteGroup>,
+    pub report: ImportReport,
+}
+
+/// Импортирует заметки из источника `source`, расположенного по пути `path`.
+/// Проблемные файлы и заметки попадают в отчет, а не прерывают импорт.
+pub fn import_notes(source: ImportSource, path: &Path, existing_groups: &[NoteGroup], existing_note_ids: &HashSet<Uuid>) -> Result<ImportResult, AppError> {
+    match source {
+        ImportSource::MarkdownFolder => import_markdown_folder(path, existing_groups, existing_note_ids),
+        ImportSource::Obsidian => import_obsidian_vault(path, existing_groups, existing_note_ids),
+        ImportSource::JoplinRaw => import_joplin_raw(path, existing_groups, existing_note_ids),
+        ImportSource::Enex => import_enex(path, existing_groups, existing_note_ids),
+    }
+}
+
+/// Заметка до проверки: необязательные поля заполняются при добавлении в `ImportContext`
+#[derive(Debug, Default)]
+struct NoteDraft {
+    id: Option<Uuid>,
+    title: Option<String>,
+    /// Заголовок на случай, если `title` пуст (обычно имя файла)
+    fallback_title: String,
+    content: String,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    pinned: bool,
+    group_id: Option<Uuid>,
+    tags: Vec<String>,
+}
+
+/// Общее состояние импорта: группы (существующие и новые), занятые id и отчет
+struct ImportContext {
+    all_groups: Vec<NoteGroup>,
+    existing_groups: usize,
+    used_ids: HashSet<Uuid>,
+    result: ImportResult,
+}
+
+impl ImportContext {
+    fn new(source: &str, path: &Path, existing_groups: &[NoteGroup], existing_note_ids: &HashSet<Uuid>) -> Self {
+        log_info!("import", source, &format!("Импорт из {}", path.display()));
+        Self {
+            all_groups: existing_groups.to_vec(),
+            existing_groups: existing_groups.len(),
+            used_ids: existing_note_ids.clone(),
+            result: ImportResult::default(),
+        }
+    }
+
+    fn skip(&mut self, path: &Path, reason: SkipReason) {
+        self.result.report.skipped.push(SkippedFile { path: path.to_path_buf(), reason });
+    }
+
+    /// Группа с именем `name` внутри `parent_id`: существующая с тем же именем или новая
+    fn group(&mut self, name: &str, parent_id: Option<Uuid>) -> Result<Uuid, SkipReason> {
+        let name = truncate_bytes(name.trim(), ValidationRules::MAX_GROUP_NAME_LENGTH);
+
+        if let Some(group) = self.all_groups.iter().find(|g| g.parent_id == parent_id && g.name == name) {
+            return Ok(group.id);
+        }
+
+        let validation = ValidationRules::validate_group_creation(&name, parent_id, &self.all_groups);
+        if let Some(error) = validation.errors.first() {
+            return Err(match error {
+                ValidationError::InvalidGroupHierarchy => SkipReason::TooDeep,
+                error => SkipReason::Invalid(error.to_string()),
+            });
+        }
+
+        let level = parent_id
+            .and_then(|id| self.all_groups.iter().find(|g| g.id == id))
+            .map_or(0, |parent| parent.level + 1);
+        let group = NoteGroup {
+            id: Uuid::new_v4(),
+            name,
+            collapsed: false,
+            parent_id,
+            level,
+            sort_mode: None,
+        };
+        let group_id = group.id;
+        self.all_groups.push(group);
+        Ok(group_id)
+    }
+
+    /// Проверяет черновик и добавляет заметку; при ошибке файл `path` попадает в отчет
+    fn add_note(&mut self, path: &Path, draft: NoteDraft) -> Option<usize> {
+        match self.note_from_draft(draft) {
+            Ok(note) => {
+                self.used_ids.insert(note.id);
+                self.result.notes.push(note);
+                Some(self.result.notes.len() - 1)
+            }
+            Err(reason) => {
+                self.skip(path, reason);
+                None
+            }
+        }
+    }
+
+    fn note_from_draft(&self, draft: NoteDraft) -> Result<Note, SkipReason> {
+        if draft.content.len() > ValidationRules::MAX_CONTENT_LENGTH {
+            return Err(SkipReason::TooLarge(draft.content.len() as u64));
+        }
+
+        let id = match draft.id {
+            Some(id) if self.used_ids.contains(&id) => return Err(SkipReason::AlreadyExists),
+            Some(id) => id,
+            None => Uuid::new_v4(),
+        };
+
+        let title = draft.title
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| draft.fallback_title.trim().to_string());
+        let title = if title.is_empty() { "Без названия".to_string() } else { title };
+
+        let created_at = draft.created_at.unwrap_or_else(Utc::now);
+        let updated_at = draft.updated_at.unwrap_or(created_at);
+
+        Ok(Note {
+            id,
+            title: truncate_bytes(&title, ValidationRules::MAX_TITLE_LENGTH),
+            content: draft.content,
+            created_at,
+            updated_at,
+            pinned: draft.pinned,
+            group_id: draft.group_id,
+            tags: normalize_tags(&draft.tags),
+            copy_count: 0,
+            last_used_at: None,
+            sort_index: 0,
+        })
+    }
+
+    fn finish(mut self, source: &str) -> ImportResult {
+        let mut result = self.result;
+        result.groups = self.all_groups.split_off(self.existing_groups);
+        result.report.notes_imported = result.notes.len();
+        result.report.groups_created = result.groups.len();
+        log_success!("import", source);
+        result
+    }
+}
+
+/// Читает текстовый файл, проверяя размер до чтения и кодировку после
+fn read_text_file(path: &Path) -> Result<(String, Option<DateTime<Utc>>), SkipReason> {
+    let metadata = fs::metadata(path).map_err(|e| SkipReason::Unreadable(e.to_string()))?;
+    // Огромные файлы даже не читаем в память
+    if metadata.len() > ValidationRules::MAX_CONTENT_LENGTH as u64 + METADATA_ALLOWANCE {
+        return Err(SkipReason::TooLarge(metadata.len()));
+    }
+    let bytes = fs::read(path).map_err(|e| SkipReason::Unreadable(e.to_string()))?;
+    let text = String::from_utf8(bytes).map_err(|_| SkipReason::NotUtf8)?;
+    Ok((text, metadata.modified().ok().map(DateTime::from)))
+}
+
+/// Нормализует теги, отбрасывая невалидные и лишние сверх лимита.
+/// Пробелы внутри тега (в Joplin и Evernote они допустимы) заменяются на дефис.
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = ValidationRules::normalize_tag(tag).split_whitespace().collect::<Vec<_>>().join("-");
+        if ValidationRules::validate_tag(&tag).is_valid && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized.truncate(ValidationRules::MAX_TAGS_PER_NOTE);
+    normalized
+}
+
+/// Обрезает строку до `max_bytes` байт по границе символа
+fn truncate_bytes(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text[..end].to_string()
+}
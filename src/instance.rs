@@ -0,0 +1,230 @@
+/// Один экземпляр приложения на папку данных: файл блокировки и локальный сокет,
+/// через который повторный запуск и командная строка передают команды открытому окну
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::cli::CliError;
+use crate::error::AppError;
+use crate::validation::ValidationRules;
+use crate::{log_error, log_info};
+
+/// Файл блокировки в папке данных; внутри - PID владельца (только для диагностики)
+pub const LOCK_FILE_NAME: &str = "fast-bind.lock";
+
+/// Сокет запущенного приложения в папке данных
+pub const SOCKET_FILE_NAME: &str = "fast-bind.sock";
+
+/// Сколько ждать ответа открытого окна
+const REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Сколько повторный запуск ждет, пока освободится блокировка или появится сокет
+/// (блокировку может ненадолго держать консольная команда)
+const LOCK_WAIT: Duration = Duration::from_secs(2);
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Запрос больше максимальной заметки с запасом на служебные поля не принимается
+const MAX_MESSAGE_BYTES: u64 = ValidationRules::MAX_CONTENT_LENGTH as u64 * 2 + 64 * 1024;
+
+/// Команда запущенному приложению (одна строка JSON)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum IpcRequest {
+    /// Показать и активировать главное окно
+    ShowWindow,
+    /// Открыть быстрый выбор (повторный запуск с `--quick-picker`)
+    QuickPicker,
+    /// Выбрать заметку и показать окно; `note` - id, начало id или заголовок
+    OpenNote { note: String },
+    CreateNote { title: String, group: Option<String>, tags: Vec<String>, content: String },
+    /// Скопировать заметку в буфер обмена; `fields` - значения полей `{{name:Поле}}`
+    CopyNote { note: String, fields: Vec<(String, String)> },
+}
+
+/// Ответ: id затронутой заметки или ошибка с тем же кодом завершения, что и в командной строке
+pub type IpcReply = Result<Option<Uuid>, CliError>;
+
+/// Блокировка папки данных; снимается, когда процесс закрывает файл (в том числе при падении)
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// Захватывает блокировку. `Ok(None)` - ее держит другой процесс
+    pub fn acquire(base_dir: &Path) -> Result<Option<Self>, AppError> {
+        fs::create_dir_all(base_dir)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(base_dir.join(LOCK_FILE_NAME))?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok(None),
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+        }
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+        Ok(Some(Self { _file: file }))
+    }
+}
+
+/// Итог попытки стать единственным экземпляром
+pub enum Instance {
+    /// Блокировка захвачена: этот процесс работает с данными сам
+    Primary(InstanceLock),
+    /// Запрос выполнило уже запущенное приложение
+    Forwarded(IpcReply),
+}
+
+/// Захватывает блокировку или передает запрос приложению, которое ее держит.
+/// Пока сокета нет (блокировку держит консольная команда или окно еще запускается), попытки повторяются;
+/// если за `LOCK_WAIT` владелец так и не ответил - `AppError::LockHeld`
+pub fn acquire_or_forward(base_dir: &Path, request: &IpcRequest) -> Result<Instance, AppError> {
+    let deadline = Instant::now() + LOCK_WAIT;
+    loop {
+        if let Some(lock) = InstanceLock::acquire(base_dir)? {
+            return Ok(Instance::Primary(lock));
+        }
+        match send_request(base_dir, request) {
+            Ok(reply) => return Ok(Instance::Forwarded(reply)),
+            Err(e) if Instant::now() >= deadline => {
+                log_error!("forward", "ipc", &e);
+                return Err(AppError::LockHeld(base_dir.to_path_buf()));
+            }
+            Err(_) => thread::sleep(RETRY_INTERVAL),
+        }
+    }
+}
+
+/// Запрос, ожидающий ответа главного потока
+pub struct IpcCall {
+    pub request: IpcRequest,
+    reply: mpsc::Sender<IpcReply>,
+}
+
+impl IpcCall {
+    pub fn reply(self, reply: IpcReply) {
+        // Клиент мог не дождаться ответа - это не ошибка приложения
+        let _ = self.reply.send(reply);
+    }
+}
+
+/// Прием команд на сокете папки данных. Соединения обслуживает фоновый поток,
+/// а сами команды выполняет главный поток через `try_recv`; поток будит цикл отрисовки
+pub struct IpcServer {
+    calls: mpsc::Receiver<IpcCall>,
+    socket_path: PathBuf,
+}
+
+impl IpcServer {
+    /// Запускать только под `InstanceLock`: оставшийся после падения сокет удаляется
+    #[cfg(unix)]
+    pub fn start(base_dir: &Path, ctx: &egui::Context) -> Result<Self, AppError> {
+        use std::os::unix::net::UnixListener;
+
+        let socket_path = base_dir.join(SOCKET_FILE_NAME);
+        if socket_path.exists() {
+            fs::remove_file(&socket_path)?;
+        }
+        let listener = UnixListener::bind(&socket_path)?;
+        let (sender, calls) = mpsc::channel();
+        let ctx = ctx.clone();
+        thread::Builder::new().name("ipc".to_string()).spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream.and_then(|stream| {
+                    stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+                    serve_connection(&stream, &mut &stream, &sender, &ctx)
+                });
+                if let Err(e) = result {
+                    log_error!("serve", "ipc", &e);
+                }
+            }
+        })?;
+
+        log_info!("start", "ipc", &format!("Прием команд на {}", socket_path.display()));
+        Ok(Self { calls, socket_path })
+    }
+
+    #[cfg(not(unix))]
+    pub fn start(_base_dir: &Path, _ctx: &egui::Context) -> Result<Self, AppError> {
+        Err(unsupported())
+    }
+
+    /// Следующий пришедший запрос, если он есть
+    pub fn try_recv(&self) -> Option<IpcCall> {
+        self.calls.try_recv().ok()
+    }
+}
+
+impl Drop for IpcServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.socket_path);
+    }
+}
+
+/// Читает один запрос, ждет ответа главного потока и отправляет его клиенту
+fn serve_connection(
+    input: impl Read,
+    output: &mut impl Write,
+    calls: &mpsc::Sender<IpcCall>,
+    ctx: &egui::Context,
+) -> io::Result<()> {
+    let reply = match read_message::<IpcRequest>(input) {
+        Ok(request) => {
+            let (reply_sender, reply) = mpsc::channel();
+            if calls.send(IpcCall { request, reply: reply_sender }).is_err() {
+                Err(CliError::Io("приложение закрывается".to_string()))
+            } else {
+                ctx.request_repaint();
+                reply.recv_timeout(REPLY_TIMEOUT)
+                    .unwrap_or_else(|_| Err(CliError::Io("приложение не ответило вовремя".to_string())))
+            }
+        }
+        Err(e) => Err(CliError::Usage(format!("неверный запрос: {}", e))),
+    };
+    write_message(output, &reply)
+}
+
+/// Отправляет запрос запущенному приложению и ждет ответа
+#[cfg(unix)]
+pub fn send_request(base_dir: &Path, request: &IpcRequest) -> Result<IpcReply, AppError> {
+    use std::os::unix::net::UnixStream;
+
+    let stream = UnixStream::connect(base_dir.join(SOCKET_FILE_NAME))?;
+    stream.set_read_timeout(Some(REPLY_TIMEOUT + Duration::from_secs(1)))?;
+    write_message(&mut &stream, request)?;
+    Ok(read_message(&stream)?)
+}
+
+#[cfg(not(unix))]
+pub fn send_request(_base_dir: &Path, _request: &IpcRequest) -> Result<IpcReply, AppError> {
+    Err(unsupported())
+}
+
+#[cfg(not(unix))]
+fn unsupported() -> AppError {
+    AppError::Io(io::Error::new(io::ErrorKind::Unsupported, "передача команд запущенному приложению доступна только в Unix"))
+}
+
+fn write_message(output: &mut impl Write, message: &impl Serialize) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    output.write_all(&line)?;
+    output.flush()
+}
+
+fn read_message<T: for<'de> Deserialize<'de>>(input: impl Read) -> io::Result<T> {
+    let mut line = String::new();
+    BufReader::new(input.take(MAX_MESSAGE_BYTES)).read_line(&mut line)?;
+    if line.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "соединение закрыто без ответа"));
+    }
+    Ok(serde_json::from_str(&line)?)
+}
@@ -1,7 +1,25 @@
 pub mod notes;
 pub mod state;
+pub mod backup;
+pub mod cli;
 pub mod error;
+pub mod fs_utils;
+pub mod history;
+pub mod import;
+pub mod instance;
+pub mod export;
 pub mod logging;
+pub mod markdown;
+pub mod notifications;
 pub mod validation;
 pub mod performance;
+pub mod quick_picker;
+pub mod search;
+pub mod settings;
+pub mod snippet;
+pub mod sqlite_store;
+pub mod storage;
+pub mod trash;
+pub mod undo;
+pub mod watcher;
 pub mod ui; 
\ No newline at end of file
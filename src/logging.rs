@@ -1,5 +1,6 @@
 /// Модуль для улучшенной обработки ошибок и логирования
 use std::fmt;
+use crate::error::AppError;
 
 /// Уровни логирования для приложения
 #[derive(Debug, Clone, Copy)]
@@ -55,8 +56,12 @@ pub struct AppLogger;
 
 impl AppLogger {
     /// Логирование ошибки с контекстом
-    pub fn log_error(context: &OperationContext, error: &dyn std::error::Error) {
-        log::error!("[{}] Ошибка: {}", context, error);
+    pub fn log_error(context: &OperationContext, error: &(dyn std::error::Error + 'static)) {
+        // Ошибки приложения помечаются стабильным кодом, чтобы их можно было искать в журнале
+        match error.downcast_ref::<AppError>() {
+            Some(app_error) => log::error!("[{}] Ошибка [{}]: {}", context, app_error.code(), error),
+            None => log::error!("[{}] Ошибка: {}", context, error),
+        }
         
         // Логируем цепочку ошибок если есть
         let mut source = error.source();
@@ -88,19 +93,19 @@ macro_rules! operation_context {
     };
 }
 
-/// Макрос для логирования ошибок с автоматическим созданием контекста
+/// Макрос для логирования ошибок с автоматическим созданием контекста (ошибка передается по ссылке)
 #[macro_export]
 macro_rules! log_error {
     ($op:expr, $entity:expr, $error:expr) => {
         $crate::logging::AppLogger::log_error(
             &$crate::operation_context!($op, $entity),
-            &$error
+            $error
         )
     };
     ($op:expr, $entity:expr, $id:expr, $error:expr) => {
         $crate::logging::AppLogger::log_error(
             &$crate::operation_context!($op, $entity, $id),
-            &$error
+            $error
         )
     };
 }
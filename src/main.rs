@@ -2,25 +2,65 @@
 
 mod notes;
 mod error;
+mod fs_utils;
+mod history;
+mod import;
+mod instance;
 mod app;
 mod state;
+mod backup;
+mod cli;
 mod ui;
+mod export;
 mod logging;
+mod markdown;
+mod notifications;
 mod validation;
 mod performance;
+mod quick_picker;
+mod search;
+mod settings;
+mod snippet;
+mod sqlite_store;
+mod storage;
+mod trash;
+mod undo;
+mod watcher;
 
 use eframe::egui;
 use app::App;
 use error::AppError;
+use instance::{Instance, IpcRequest};
 
 fn main() -> Result<(), AppError> {
-    // Инициализируем систему логирования
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cli_mode = cli::is_cli_invocation(&args);
+    
+    // Инициализируем систему логирования (в консольном режиме - только предупреждения и ошибки)
     env_logger::Builder::from_default_env()
-        .filter_level(log::LevelFilter::Info)
+        .filter_level(if cli_mode { log::LevelFilter::Warn } else { log::LevelFilter::Info })
         .format_timestamp_secs()
         .init();
     
+    if cli_mode {
+        std::process::exit(cli::run(&args));
+    }
+    
     log::info!("Запуск приложения fast-bind");
+    let open_quick_picker = args.iter().any(|arg| arg == quick_picker::QUICK_PICKER_FLAG);
+    
+    // Второй экземпляр не запускается: окно уже открытого приложения показывается само
+    let request = if open_quick_picker { IpcRequest::QuickPicker } else { IpcRequest::ShowWindow };
+    let _instance_lock = match instance::acquire_or_forward(&storage::default_base_dir(), &request)? {
+        Instance::Primary(lock) => lock,
+        Instance::Forwarded(reply) => {
+            log::info!("Приложение уже запущено, команда передана открытому окну");
+            if let Err(e) = reply {
+                log::error!("Запущенное приложение не выполнило команду: {}", e);
+            }
+            return Ok(());
+        }
+    };
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1024.0, 768.0])
@@ -50,7 +90,7 @@ fn main() -> Result<(), AppError> {
             }
             
             // Создаём приложение без принудительной установки темы
-            Ok(Box::new(App::new(cc)))
+            Ok(Box::new(App::new(cc, open_quick_picker)))
         })
     ).map_err(|e| AppError::Io(std::io::Error::new(std::io::ErrorKind::Other, format!("Failed to start application: {}", e))))
 }
\ No newline at end of file
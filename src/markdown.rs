@@ -0,0 +1,261 @@
+/// Формат Markdown-файлов заметок: YAML front matter и безопасные имена файлов
+use std::collections::HashSet;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+use crate::notes::Note;
+
+/// Расширение файлов заметок при экспорте
+pub const MARKDOWN_EXTENSION: &str = "md";
+
+/// Максимальная длина имени файла или папки без расширения (в символах)
+const MAX_FILE_STEM_CHARS: usize = 100;
+
+/// Имя для заметок и групп с пустым названием
+const UNTITLED: &str = "Без названия";
+
+/// Имена устройств Windows, которые нельзя использовать как имя файла
+const RESERVED_WINDOWS_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Строка в двойных кавычках по правилам YAML (совпадают с экранированием JSON)
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+fn yaml_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Markdown-документ заметки: front matter с метаданными и текст заметки
+pub fn render_note(note: &Note) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("id: {}\n", note.id));
+    out.push_str(&format!("title: {}\n", yaml_string(&note.title)));
+    out.push_str(&format!("created: {}\n", yaml_time(&note.created_at)));
+    out.push_str(&format!("updated: {}\n", yaml_time(&note.updated_at)));
+    out.push_str(&format!("pinned: {}\n", note.pinned));
+    if note.tags.is_empty() {
+        out.push_str("tags: []\n");
+    } else {
+        out.push_str("tags:\n");
+        for tag in &note.tags {
+            out.push_str(&format!("  - {}\n", yaml_string(tag)));
+        }
+    }
+    out.push_str("---\n\n");
+    out.push_str(&note.content);
+    if !note.content.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Поля заметки, прочитанные из front matter (все необязательные)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontMatter {
+    pub id: Option<Uuid>,
+    pub title: Option<String>,
+    pub created: Option<DateTime<Utc>>,
+    pub updated: Option<DateTime<Utc>>,
+    pub pinned: Option<bool>,
+    pub tags: Vec<String>,
+}
+
+/// Отделяет front matter от текста документа.
+/// Если документ не начинается с `---` или блок не закрыт, front matter считается пустым.
+/// Неизвестные ключи и вложенные структуры пропускаются.
+pub fn split_front_matter(text: &str) -> (FrontMatter, &str) {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let Some(rest) = text.strip_prefix("---\n").or_else(|| text.strip_prefix("---\r\n")) else {
+        return (FrontMatter::default(), text);
+    };
+
+    // Ищем закрывающую строку `---` (или `...`)
+    let mut offset = 0;
+    let mut block_end = None;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            block_end = Some((offset, offset + line.len()));
+            break;
+        }
+        offset += line.len();
+    }
+    let Some((block_len, body_start)) = block_end else {
+        return (FrontMatter::default(), text);
+    };
+
+    let front_matter = parse_front_matter_block(&rest[..block_len]);
+    let body = &rest[body_start..];
+    // Пустая строка после front matter добавляется при экспорте и к тексту не относится
+    let body = body.strip_prefix("\r\n").or_else(|| body.strip_prefix('\n')).unwrap_or(body);
+    (front_matter, body)
+}
+
+fn parse_front_matter_block(block: &str) -> FrontMatter {
+    let mut front_matter = FrontMatter::default();
+    let mut list_key: Option<String> = None;
+
+    for line in block.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        // Элемент блочного списка "  - value" относится к последнему ключу без значения
+        if let Some(item) = line.trim_start().strip_prefix("- ") {
+            if list_key.as_deref().is_some_and(is_tags_key) {
+                front_matter.tags.push(unquote(item));
+            }
+            continue;
+        }
+        if line.starts_with(' ') || line.starts_with('\t') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+        list_key = value.is_empty().then(|| key.clone());
+
+        match key.as_str() {
+            "id" => front_matter.id = Uuid::parse_str(&unquote(value)).ok(),
+            "title" => front_matter.title = Some(unquote(value)).filter(|t| !t.trim().is_empty()),
+            "created" | "created_at" | "date" => front_matter.created = parse_time(&unquote(value)),
+            "updated" | "updated_at" | "modified" => front_matter.updated = parse_time(&unquote(value)),
+            "pinned" => front_matter.pinned = match unquote(value).to_lowercase().as_str() {
+                "true" | "yes" => Some(true),
+                "false" | "no" => Some(false),
+                _ => None,
+            },
+            key if is_tags_key(key) && !value.is_empty() => front_matter.tags.extend(parse_inline_list(value)),
+            _ => {}
+        }
+    }
+
+    front_matter.tags.retain(|tag| !tag.trim().is_empty());
+    front_matter
+}
+
+fn is_tags_key(key: &str) -> bool {
+    key == "tags" || key == "tag"
+}
+
+/// Значение списка в одну строку: `[a, "b"]`, `a, b` или `#a #b`
+fn parse_inline_list(value: &str) -> Vec<String> {
+    let inner = value.strip_prefix('[').and_then(|v| v.strip_suffix(']'));
+    let separator_is_comma = inner.is_some() || value.contains(',');
+    let items = inner.unwrap_or(value);
+
+    let parts: Vec<&str> = if separator_is_comma {
+        items.split(',').collect()
+    } else {
+        items.split_whitespace().collect()
+    };
+    parts.into_iter()
+        .map(|item| unquote(item.trim()).trim_start_matches('#').to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Снимает YAML-кавычки со скалярного значения
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        return serde_json::from_str(value).unwrap_or_else(|_| value[1..value.len() - 1].to_string());
+    }
+    if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        return value[1..value.len() - 1].replace("''", "'");
+    }
+    value.to_string()
+}
+
+/// Дата в RFC 3339 или без часового пояса (считается UTC): `2024-01-31`, `2024-01-31 12:00:00`
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(time.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc())
+}
+
+/// Заголовок первого уровня (`# Заголовок`) в начале текста
+pub fn first_heading(body: &str) -> Option<String> {
+    let line = body.lines().find(|line| !line.trim().is_empty())?;
+    let heading = line.trim().strip_prefix("# ")?.trim();
+    (!heading.is_empty()).then(|| heading.to_string())
+}
+
+/// Превращает название в имя файла, допустимое на Windows, macOS и Linux
+pub fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(MAX_FILE_STEM_CHARS)
+        .collect();
+
+    // Windows не допускает точки и пробелы в конце, а точка в начале делает файл скрытым
+    let trimmed = cleaned.trim().trim_matches('.').trim();
+    if trimmed.is_empty() {
+        return UNTITLED.to_string();
+    }
+
+    let upper = trimmed.to_uppercase();
+    let base = upper.split('.').next().unwrap_or_default();
+    if RESERVED_WINDOWS_NAMES.contains(&base) {
+        return format!("_{}", trimmed);
+    }
+    trimmed.to_string()
+}
+
+/// Выдает уникальные имена внутри одной папки: "Имя", "Имя (2)", "Имя (3)"...
+/// Сравнение без учета регистра, т.к. файловые системы Windows и macOS его не различают.
+#[derive(Debug, Default)]
+pub struct UniqueNames {
+    used: HashSet<String>,
+}
+
+impl UniqueNames {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Помечает имя занятым (например, уже существующим на диске файлом)
+    pub fn reserve(&mut self, name: &str) {
+        self.used.insert(name.to_lowercase());
+    }
+
+    /// Свободное имя на основе `stem` с расширением `extension` (пустое - без расширения)
+    pub fn claim(&mut self, stem: &str, extension: &str) -> String {
+        let with_extension = |stem: &str| {
+            if extension.is_empty() {
+                stem.to_string()
+            } else {
+                format!("{}.{}", stem, extension)
+            }
+        };
+
+        let mut candidate = with_extension(stem);
+        let mut counter = 2;
+        while self.used.contains(&candidate.to_lowercase()) {
+            candidate = with_extension(&format!("{} ({})", stem, counter));
+            counter += 1;
+        }
+        self.reserve(&candidate);
+        candidate
+    }
+}
@@ -1,12 +1,18 @@
-use std::path::PathBuf;
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use crate::error::AppError;
+use crate::fs_utils;
+use crate::history::NoteRevision;
+use crate::storage::NoteStore;
+use crate::trash::Trash;
+use crate::{log_error, log_info};
 
 // Структура, представляющая группу заметок
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NoteGroup {
     pub id: Uuid,
     pub name: String,
@@ -16,6 +22,66 @@ pub struct NoteGroup {
     pub parent_id: Option<Uuid>, // ID родительской группы для вложенности
     #[serde(default)]
     pub level: u32, // Уровень вложенности (0 = корневая группа)
+    /// Порядок заметок в группе (None - как в настройках)
+    #[serde(default)]
+    pub sort_mode: Option<NoteSortMode>,
+}
+
+/// Порядок заметок в списке. Закрепленные заметки всегда выше остальных.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NoteSortMode {
+    /// Сначала новые (порядок по умолчанию)
+    #[default]
+    Created,
+    /// Сначала недавно скопированные
+    RecentlyUsed,
+    /// Сначала чаще всего копируемые
+    MostUsed,
+    Alphabetical,
+    /// Сначала недавно измененные
+    Updated,
+    /// Ручной порядок (`sort_index`), задается перетаскиванием
+    Manual,
+}
+
+impl NoteSortMode {
+    pub const ALL: [NoteSortMode; 6] = [
+        NoteSortMode::Created,
+        NoteSortMode::RecentlyUsed,
+        NoteSortMode::MostUsed,
+        NoteSortMode::Alphabetical,
+        NoteSortMode::Updated,
+        NoteSortMode::Manual,
+    ];
+
+    /// Название для интерфейса
+    pub fn label(self) -> &'static str {
+        match self {
+            NoteSortMode::Created => "По дате создания",
+            NoteSortMode::RecentlyUsed => "Недавно использованные",
+            NoteSortMode::MostUsed => "Часто используемые",
+            NoteSortMode::Alphabetical => "По алфавиту",
+            NoteSortMode::Updated => "По дате изменения",
+            NoteSortMode::Manual => "Вручную",
+        }
+    }
+
+    /// Сравнение заметок для сортировки по возрастанию позиции в списке
+    pub fn compare(self, a: &Note, b: &Note) -> Ordering {
+        let by_mode = match self {
+            NoteSortMode::Created => Ordering::Equal,
+            NoteSortMode::RecentlyUsed => b.last_used_at.cmp(&a.last_used_at),
+            NoteSortMode::MostUsed => b.copy_count.cmp(&a.copy_count)
+                .then(b.last_used_at.cmp(&a.last_used_at)),
+            NoteSortMode::Alphabetical => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            NoteSortMode::Updated => b.updated_at.cmp(&a.updated_at),
+            NoteSortMode::Manual => a.sort_index.cmp(&b.sort_index)
+                .then(a.created_at.cmp(&b.created_at)),
+        };
+        b.pinned.cmp(&a.pinned)
+            .then(by_mode)
+            .then(b.created_at.cmp(&a.created_at))
+    }
 }
 
 // Структура, представляющая заметку
@@ -30,8 +96,28 @@ pub struct Note {
     pub pinned: bool,
     #[serde(default)]
     pub group_id: Option<Uuid>,
+    #[serde(default)]
+    pub tags: Vec<String>,           // Теги (заметка может относиться к нескольким контекстам)
+    /// Сколько раз заметку копировали в буфер обмена или постоянный текст
+    #[serde(default)]
+    pub copy_count: u32,
+    /// Когда заметку копировали в последний раз
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Позиция в ручном порядке внутри группы
+    #[serde(default)]
+    pub sort_index: u32,
 }
 
+/// Поддиректория с историей версий заметок
+const HISTORY_DIR_NAME: &str = "history";
+
+/// Поддиректория корзины
+const TRASH_DIR_NAME: &str = "trash";
+
+/// Файл со списком групп в папке заметок
+pub const GROUPS_FILE_NAME: &str = "groups.json";
+
 // Структура для управления заметками
 pub struct NotesManager {
     notes_dir: PathBuf,  // Директория, где хранятся заметки
@@ -45,6 +131,26 @@ impl NotesManager {
             fs::create_dir_all(&notes_dir).expect("Failed to create notes directory");
         }
         
+        // Убираем остатки записи, прерванной сбоем или отключением питания
+        let history_dir = notes_dir.join(HISTORY_DIR_NAME);
+        let trash_dir = notes_dir.join(TRASH_DIR_NAME);
+        for dir in [&notes_dir, &history_dir, &trash_dir] {
+            if !dir.exists() {
+                continue;
+            }
+            match fs_utils::remove_stale_temp_files(dir) {
+                Ok(0) => {}
+                Ok(removed) => log_info!("cleanup", "temp_files", &format!("Удалено недописанных временных файлов: {}", removed)),
+                Err(e) => log_error!("cleanup", "temp_files", &e),
+            }
+        }
+        
+        Self { notes_dir }
+    }
+
+    /// Менеджер только для чтения: не создает папку и не убирает временные файлы,
+    /// поэтому его можно открыть, не захватывая папку данных
+    pub fn read_only(notes_dir: PathBuf) -> Self {
         Self { notes_dir }
     }
 
@@ -53,16 +159,44 @@ impl NotesManager {
         self.notes_dir.join(format!("{}.json", id))
     }
 
-    // Сохраняем заметку в файл
-    pub fn save_note(&self, note: &Note) -> Result<(), AppError> {
+    /// Получает путь к директории для дополнительных файлов (родительская директория notes)
+    pub fn get_base_dir(&self) -> &Path {
+        self.notes_dir.parent().unwrap_or(&self.notes_dir)
+    }
+
+    // Путь к файлу истории версий заметки (в поддиректории, чтобы не смешивать с заметками)
+    fn get_history_path(&self, id: Uuid) -> PathBuf {
+        self.notes_dir.join(HISTORY_DIR_NAME).join(format!("{}.json", id))
+    }
+
+    // Путь к файлу корзины
+    fn trash_path(&self) -> PathBuf {
+        self.notes_dir.join(TRASH_DIR_NAME).join("trash.json")
+    }
+
+    // Путь к файлу постоянного текста
+    fn persistent_text_path(&self) -> PathBuf {
+        self.get_base_dir().join("persistent_text.txt")
+    }
+}
+
+/// Читает JSON-файл хранилища; неразборчивое содержимое - `AppError::Corrupted` с путем к файлу
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, AppError> {
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| AppError::corrupted(path, e))
+}
+
+impl NoteStore for NotesManager {
+    // Сохраняем заметку в файл (атомарно, чтобы сбой не оставил обрезанный JSON)
+    fn save_note(&self, note: &Note) -> Result<(), AppError> {
         let file_path = self.get_note_path(note.id);
         let content = serde_json::to_string_pretty(note)?;
-        fs::write(file_path, content)?;
+        fs_utils::atomic_write(&file_path, content)?;
         Ok(())
     }
 
     // Получаем список всех заметок
-    pub fn get_all_notes(&self) -> Result<Vec<Note>, AppError> {
+    fn get_all_notes(&self) -> Result<Vec<Note>, AppError> {
         let mut notes = Vec::new();
         
         for entry in fs::read_dir(&self.notes_dir)? {
@@ -87,7 +221,7 @@ impl NotesManager {
     }
 
     // Удаляем заметку
-    pub fn delete_note(&self, id: Uuid) -> Result<(), AppError> {
+    fn delete_note(&self, id: Uuid) -> Result<(), AppError> {
         let file_path = self.notes_dir.join(format!("{}.json", id));
         if file_path.exists() {
             fs::remove_file(file_path)?;
@@ -97,30 +231,23 @@ impl NotesManager {
         }
     }
 
-    pub fn save_groups(&self, groups: &Vec<NoteGroup>) -> Result<(), AppError> {
-        let file_path = self.notes_dir.join("groups.json");
+    fn save_groups(&self, groups: &[NoteGroup]) -> Result<(), AppError> {
+        let file_path = self.notes_dir.join(GROUPS_FILE_NAME);
         let content = serde_json::to_string_pretty(groups)?;
-        fs::write(file_path, content)?;
+        fs_utils::atomic_write(&file_path, content)?;
         Ok(())
     }
 
-    pub fn load_groups(&self) -> Result<Vec<NoteGroup>, AppError> {
-        let file_path = self.notes_dir.join("groups.json");
+    fn load_groups(&self) -> Result<Vec<NoteGroup>, AppError> {
+        let file_path = self.notes_dir.join(GROUPS_FILE_NAME);
         if !file_path.exists() {
             return Ok(vec![]);
         }
-        let content = fs::read_to_string(file_path)?;
-        let groups: Vec<NoteGroup> = serde_json::from_str(&content)?;
-        Ok(groups)
-    }
-
-    /// Получает путь к директории для дополнительных файлов (родительская директория notes)
-    pub fn get_base_dir(&self) -> &std::path::Path {
-        self.notes_dir.parent().unwrap_or(&self.notes_dir)
+        read_json(&file_path)
     }
 
     /// Получает список ID всех заметок (быстрая операция для ленивой загрузки)
-    pub fn get_note_ids(&self) -> Result<Vec<Uuid>, AppError> {
+    fn get_note_ids(&self) -> Result<Vec<Uuid>, AppError> {
         let mut note_ids = Vec::new();
         
         for entry in fs::read_dir(&self.notes_dir)? {
@@ -142,20 +269,18 @@ impl NotesManager {
     }
 
     /// Загружает заметку по ID (оптимизировано для ленивой загрузки)
-    pub fn load_note_by_id(&self, id: Uuid) -> Result<Option<Note>, AppError> {
+    fn load_note_by_id(&self, id: Uuid) -> Result<Option<Note>, AppError> {
         let file_path = self.get_note_path(id);
         
         if !file_path.exists() {
             return Ok(None);
         }
         
-        let content = fs::read_to_string(&file_path)?;
-        let note: Note = serde_json::from_str(&content)?;
-        Ok(Some(note))
+        Ok(Some(read_json(&file_path)?))
     }
 
     /// Получает метаданные заметок без загрузки содержимого (для быстрого отображения списков)
-    pub fn get_notes_metadata(&self) -> Result<Vec<NoteMetadata>, AppError> {
+    fn get_notes_metadata(&self) -> Result<Vec<NoteMetadata>, AppError> {
         let mut metadata = Vec::new();
         
         for entry in fs::read_dir(&self.notes_dir)? {
@@ -165,17 +290,7 @@ impl NotesManager {
             if path.is_file() && path.extension().map_or(false, |ext| ext == "json") {
                 match fs::read_to_string(&path) {
                     Ok(content) => match serde_json::from_str::<Note>(&content) {
-                        Ok(note) => {
-                            metadata.push(NoteMetadata {
-                                id: note.id,
-                                title: note.title,
-                                created_at: note.created_at,
-                                updated_at: note.updated_at,
-                                pinned: note.pinned,
-                                group_id: note.group_id,
-                                content_length: note.content.len(),
-                            });
-                        },
+                        Ok(note) => metadata.push(NoteMetadata::from_note(&note)),
                         Err(e) => eprintln!("Ошибка десериализации заметки {:?}: {}", path, e),
                     },
                     Err(e) => eprintln!("Ошибка чтения файла заметки {:?}: {}", path, e),
@@ -189,21 +304,8 @@ impl NotesManager {
         Ok(metadata)
     }
 
-    /// Пагинированная загрузка заметок (для ленивой загрузки)
-    pub fn get_notes_page(&self, page: usize, page_size: usize) -> Result<Vec<Note>, AppError> {
-        let all_notes = self.get_all_notes()?;
-        let start_idx = page * page_size;
-        let end_idx = (start_idx + page_size).min(all_notes.len());
-        
-        if start_idx >= all_notes.len() {
-            return Ok(Vec::new());
-        }
-        
-        Ok(all_notes[start_idx..end_idx].to_vec())
-    }
-
     /// Получает количество заметок без их загрузки
-    pub fn get_notes_count(&self) -> Result<usize, AppError> {
+    fn get_notes_count(&self) -> Result<usize, AppError> {
         let mut count = 0;
         
         for entry in fs::read_dir(&self.notes_dir)? {
@@ -217,6 +319,69 @@ impl NotesManager {
         
         Ok(count)
     }
+
+    /// Загружает постоянный текст из файла рядом с директорией заметок
+    fn load_persistent_text(&self) -> Result<String, AppError> {
+        let persistent_file = self.persistent_text_path();
+        if !persistent_file.exists() {
+            return Ok(String::new());
+        }
+        Ok(fs::read_to_string(persistent_file)?)
+    }
+
+    /// Сохраняет постоянный текст
+    fn save_persistent_text(&self, text: &str) -> Result<(), AppError> {
+        fs_utils::atomic_write(&self.persistent_text_path(), text)?;
+        Ok(())
+    }
+
+    fn load_revisions(&self, note_id: Uuid) -> Result<Vec<NoteRevision>, AppError> {
+        let file_path = self.get_history_path(note_id);
+        if !file_path.exists() {
+            return Ok(Vec::new());
+        }
+        read_json(&file_path)
+    }
+
+    fn save_revisions(&self, note_id: Uuid, revisions: &[NoteRevision]) -> Result<(), AppError> {
+        let file_path = self.get_history_path(note_id);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(revisions)?;
+        fs_utils::atomic_write(&file_path, content)?;
+        Ok(())
+    }
+
+    fn delete_revisions(&self, note_id: Uuid) -> Result<(), AppError> {
+        let file_path = self.get_history_path(note_id);
+        if file_path.exists() {
+            fs::remove_file(file_path)?;
+        }
+        Ok(())
+    }
+
+    fn load_trash(&self) -> Result<Trash, AppError> {
+        let file_path = self.trash_path();
+        if !file_path.exists() {
+            return Ok(Trash::default());
+        }
+        read_json(&file_path)
+    }
+
+    fn save_trash(&self, trash: &Trash) -> Result<(), AppError> {
+        let file_path = self.trash_path();
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(trash)?;
+        fs_utils::atomic_write(&file_path, content)?;
+        Ok(())
+    }
+
+    fn watched_dir(&self) -> Option<&Path> {
+        Some(&self.notes_dir)
+    }
 }
 
 /// Метаданные заметки для быстрого отображения в списках
@@ -228,7 +393,29 @@ pub struct NoteMetadata {
     pub updated_at: DateTime<Utc>,
     pub pinned: bool,
     pub group_id: Option<Uuid>,
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub content_length: usize, // Длина содержимого для оценки размера
+    #[serde(default)]
+    pub copy_count: u32,
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
 }
 
- 
\ No newline at end of file
+impl NoteMetadata {
+    /// Метаданные заметки без содержимого
+    pub fn from_note(note: &Note) -> Self {
+        Self {
+            id: note.id,
+            title: note.title.clone(),
+            created_at: note.created_at,
+            updated_at: note.updated_at,
+            pinned: note.pinned,
+            group_id: note.group_id,
+            tags: note.tags.clone(),
+            content_length: note.content.len(),
+            copy_count: note.copy_count,
+            last_used_at: note.last_used_at,
+        }
+    }
+}
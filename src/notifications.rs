@@ -0,0 +1,163 @@
+/// Центр уведомлений: в оконной сборке нет консоли, поэтому итоги операций и ошибки показываются в самом приложении
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local};
+use crate::error::AppError;
+
+/// Сколько всплывающее уведомление остается на экране
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Сколько уведомлений хранится в истории
+pub const HISTORY_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Success,
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: u64,
+    pub level: NotificationLevel,
+    pub message: String,
+    /// Код ошибки `AppError::code`
+    pub code: Option<&'static str>,
+    pub time: DateTime<Local>,
+    /// Сколько раз подряд пришло такое же уведомление
+    pub repeats: u32,
+    shown_at: Instant,
+}
+
+/// История уведомлений, всплывающие уведомления и закрепленные ошибки хранилища.
+/// Всплывающие исчезают сами через `TOAST_DURATION`, закрепленные - только когда их закроют
+#[derive(Debug, Default)]
+pub struct NotificationCenter {
+    /// Новые уведомления - в начале
+    history: VecDeque<Notification>,
+    toasts: Vec<u64>,
+    banners: Vec<u64>,
+    next_id: u64,
+    unread: usize,
+}
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Success, message.into(), None, false);
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Info, message.into(), None, false);
+    }
+
+    /// Ошибка операции: пишется в журнал и показывается пользователю.
+    /// Сбой хранилища закрепляется вверху окна, остальные ошибки всплывают на время
+    pub fn error(&mut self, context: &str, error: &AppError) {
+        log::error!("{} [{}]: {}", context, error.code(), error);
+        let message = format!("{}: {}", context, error);
+        self.push(NotificationLevel::Error, message, Some(error.code()), error.is_storage_failure());
+    }
+
+    fn push(&mut self, level: NotificationLevel, message: String, code: Option<&'static str>, pinned: bool) {
+        // Повтор последнего уведомления (например, ошибка при каждой прокрутке) не копится в истории
+        if let Some(last) = self.history.front_mut()
+            && last.level == level
+            && last.message == message
+        {
+            last.repeats += 1;
+            last.time = Local::now();
+            last.shown_at = Instant::now();
+            let id = last.id;
+            let shown = if pinned { &mut self.banners } else { &mut self.toasts };
+            if !shown.contains(&id) {
+                shown.push(id);
+            }
+            return;
+        }
+
+        // Непрочитанными считаются записи истории, а не повторы
+        self.unread = (self.unread + 1).min(HISTORY_LIMIT);
+        self.next_id += 1;
+        self.history.push_front(Notification {
+            id: self.next_id,
+            level,
+            message,
+            code,
+            time: Local::now(),
+            repeats: 1,
+            shown_at: Instant::now(),
+        });
+        self.history.truncate(HISTORY_LIMIT);
+        if pinned {
+            self.banners.push(self.next_id);
+        } else {
+            self.toasts.push(self.next_id);
+        }
+
+        // Закрепленные ошибки, вытесненные из истории, показывать уже нечем
+        let history = &self.history;
+        self.banners.retain(|id| history.iter().any(|n| n.id == *id));
+        self.toasts.retain(|id| history.iter().any(|n| n.id == *id));
+    }
+
+    /// Убирает всплывающие уведомления, показанные дольше `TOAST_DURATION` к моменту `now`
+    pub fn expire_toasts(&mut self, now: Instant) {
+        let history = &self.history;
+        self.toasts.retain(|id| {
+            history.iter()
+                .find(|n| n.id == *id)
+                .is_some_and(|n| now.saturating_duration_since(n.shown_at) < TOAST_DURATION)
+        });
+    }
+
+    /// Через сколько исчезнет ближайшее всплывающее уведомление
+    pub fn next_expiry(&self, now: Instant) -> Option<Duration> {
+        self.toasts().map(|n| TOAST_DURATION.saturating_sub(now.saturating_duration_since(n.shown_at))).min()
+    }
+
+    /// Всплывающие уведомления в порядке появления
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        self.shown(&self.toasts)
+    }
+
+    /// Закрепленные ошибки хранилища в порядке появления
+    pub fn banners(&self) -> impl Iterator<Item = &Notification> {
+        self.shown(&self.banners)
+    }
+
+    fn shown<'a>(&'a self, ids: &'a [u64]) -> impl Iterator<Item = &'a Notification> {
+        ids.iter().filter_map(|id| self.history.iter().find(|n| n.id == *id))
+    }
+
+    /// Закрывает уведомление на экране; в истории оно остается
+    pub fn dismiss(&mut self, id: u64) {
+        self.toasts.retain(|&shown| shown != id);
+        self.banners.retain(|&shown| shown != id);
+    }
+
+    /// История уведомлений, новые - первыми
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter()
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.toasts.clear();
+        self.banners.clear();
+        self.unread = 0;
+    }
+
+    /// Записи истории, появившиеся после последнего просмотра
+    pub fn unread_count(&self) -> usize {
+        self.unread
+    }
+
+    pub fn mark_read(&mut self) {
+        self.unread = 0;
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::{VecDeque, HashMap};
 use uuid::Uuid;
-use crate::notes::{Note, NotesManager, NoteMetadata};
+use crate::notes::{Note, NoteMetadata};
+use crate::storage::NoteStore;
 use crate::error::AppError;
 
 /// Структура для ленивой загрузки заметок с оптимизацией производительности
@@ -41,10 +42,10 @@ impl LazyNoteLoader {
         }
     }
 
-    /// Инициализирует загрузчик с менеджером заметок
-    pub fn initialize_with_manager(&mut self, notes_manager: &NotesManager) -> Result<(), AppError> {
+    /// Инициализирует загрузчик по метаданным из хранилища
+    pub fn initialize_with_store(&mut self, store: &dyn NoteStore) -> Result<(), AppError> {
         // Загружаем метаданные заметок для быстрого отображения
-        let metadata = notes_manager.get_notes_metadata()?;
+        let metadata = store.get_notes_metadata()?;
         self.note_ids = metadata.iter().map(|m| m.id).collect();
         self.total_notes = self.note_ids.len();
         
@@ -71,29 +72,29 @@ impl LazyNoteLoader {
     }
 
     /// Загружает следующую страницу заметок
-    pub fn load_next_page(&mut self, notes_manager: &NotesManager) -> Result<Vec<Note>, AppError> {
+    pub fn load_next_page(&mut self, store: &dyn NoteStore) -> Result<Vec<Note>, AppError> {
         if self.current_page * self.notes_per_page >= self.total_notes {
             return Ok(Vec::new()); // Больше нет данных
         }
 
-        let page_notes = self.load_page(self.current_page, notes_manager)?;
+        let page_notes = self.load_page(self.current_page, store)?;
         self.current_page += 1;
         
         // Предзагружаем следующие страницы в фоне
-        self.prefetch_next_pages(notes_manager)?;
+        self.prefetch_next_pages(store)?;
         
         Ok(page_notes)
     }
 
     /// Загружает конкретную страницу заметок
-    pub fn load_page(&mut self, page: usize, notes_manager: &NotesManager) -> Result<Vec<Note>, AppError> {
+    pub fn load_page(&mut self, page: usize, store: &dyn NoteStore) -> Result<Vec<Note>, AppError> {
         let start_idx = page * self.notes_per_page;
         let end_idx = (start_idx + self.notes_per_page).min(self.total_notes);
         
         let mut page_notes = Vec::new();
         
         for i in start_idx..end_idx {
-            if let Some(note) = self.get_note_by_index(i, notes_manager)? {
+            if let Some(note) = self.get_note_by_index(i, store)? {
                 page_notes.push(note.clone());
             }
         }
@@ -102,7 +103,7 @@ impl LazyNoteLoader {
     }
 
     /// Получает заметку по индексу с кэшированием
-    pub fn get_note_by_index(&mut self, index: usize, notes_manager: &NotesManager) -> Result<Option<Note>, AppError> {
+    pub fn get_note_by_index(&mut self, index: usize, store: &dyn NoteStore) -> Result<Option<Note>, AppError> {
         if index >= self.total_notes {
             return Ok(None);
         }
@@ -114,7 +115,7 @@ impl LazyNoteLoader {
 
         // Загружаем заметку из файла
         let note_id = self.note_ids[index];
-        if let Some(note) = notes_manager.load_note_by_id(note_id)? {
+        if let Some(note) = store.load_note_by_id(note_id)? {
             self.cache_note(index, note.clone());
             Ok(Some(note))
         } else {
@@ -142,11 +143,11 @@ impl LazyNoteLoader {
     }
 
     /// Получает заметки для отображения в заданном диапазоне
-    pub fn get_visible_notes(&mut self, start: usize, count: usize, notes_manager: &NotesManager) -> Result<Vec<Option<Note>>, AppError> {
+    pub fn get_visible_notes(&mut self, start: usize, count: usize, store: &dyn NoteStore) -> Result<Vec<Option<Note>>, AppError> {
         let mut notes = Vec::with_capacity(count);
         
         for i in start..(start + count).min(self.total_notes) {
-            notes.push(self.get_note_by_index(i, notes_manager)?);
+            notes.push(self.get_note_by_index(i, store)?);
         }
         
         // Дополняем None'ами до нужного размера
@@ -158,11 +159,11 @@ impl LazyNoteLoader {
     }
 
     /// Предзагружает следующие страницы в фоне
-    fn prefetch_next_pages(&mut self, notes_manager: &NotesManager) -> Result<(), AppError> {
+    fn prefetch_next_pages(&mut self, store: &dyn NoteStore) -> Result<(), AppError> {
         for i in 1..=self.prefetch_pages {
             let page_to_prefetch = self.current_page + i;
             if page_to_prefetch * self.notes_per_page < self.total_notes {
-                let _ = self.load_page(page_to_prefetch, notes_manager); // Игнорируем ошибки предзагрузки
+                let _ = self.load_page(page_to_prefetch, store); // Игнорируем ошибки предзагрузки
             }
         }
         Ok(())
@@ -181,6 +182,34 @@ impl LazyNoteLoader {
         self.loaded_indices.push_back(index);
     }
 
+    /// Метаданные всех заметок (включая незагруженные)
+    pub fn all_metadata(&self) -> impl Iterator<Item = &NoteMetadata> {
+        self.metadata_cache.values()
+    }
+
+    /// Известна ли загрузчику заметка (загружена она или нет)
+    pub fn contains(&self, note_id: Uuid) -> bool {
+        self.metadata_cache.contains_key(&note_id)
+    }
+
+    /// Обновляет кэшированные метаданные и копию заметки после её изменения
+    pub fn update_note(&mut self, note: &Note) {
+        if let Some(meta) = self.metadata_cache.get_mut(&note.id) {
+            meta.title = note.title.clone();
+            meta.updated_at = note.updated_at;
+            meta.pinned = note.pinned;
+            meta.group_id = note.group_id;
+            meta.tags = note.tags.clone();
+            meta.content_length = note.content.len();
+            meta.copy_count = note.copy_count;
+            meta.last_used_at = note.last_used_at;
+        }
+
+        if let Some(cached) = self.loaded_notes.values_mut().find(|n| n.id == note.id) {
+            *cached = note.clone();
+        }
+    }
+
     /// Очищает кэш загруженных заметок
     pub fn clear_cache(&mut self) {
         self.loaded_notes.clear();
@@ -0,0 +1,157 @@
+/// Быстрый выбор заметки: нечеткий поиск по названиям и глобальная горячая клавиша
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use eframe::egui;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use global_hotkey::hotkey::HotKey;
+use crate::error::AppError;
+use crate::notes::NoteMetadata;
+use crate::{log_error, log_info};
+
+/// Флаг командной строки, открывающий быстрый выбор при запуске.
+/// Там, где глобальные клавиши недоступны (Wayland), его можно назначить на сочетание в настройках системы.
+pub const QUICK_PICKER_FLAG: &str = "--quick-picker";
+
+/// Глобальное сочетание по умолчанию
+pub const DEFAULT_QUICK_PICKER_HOTKEY: &str = "Ctrl+Alt+Space";
+
+/// Сколько заметок показывать в списке
+pub const QUICK_PICKER_LIMIT: usize = 50;
+
+/// Заметка, подходящая под запрос
+#[derive(Debug, Clone)]
+pub struct PickerMatch {
+    pub note: NoteMetadata,
+    pub score: i32,
+}
+
+/// Нечеткое совпадение: все символы запроса встречаются в тексте по порядку (без учета регистра).
+/// Больше очков за символы подряд и в начале слов. `None`, если совпадения нет.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut matched = 0;
+    let mut previous: Option<usize> = None;
+
+    for (i, &c) in text.iter().enumerate() {
+        if matched == query.len() {
+            break;
+        }
+        if c != query[matched] {
+            continue;
+        }
+
+        score += 1;
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match previous {
+            Some(p) if p + 1 == i => score += 5,
+            Some(p) => score -= (i - p - 1).min(3) as i32,
+            None => {}
+        }
+        previous = Some(i);
+        matched += 1;
+    }
+
+    (matched == query.len()).then_some(score)
+}
+
+/// Заметки под запрос: сначала закрепленные, затем по качеству совпадения, частоте копирования и дате изменения.
+/// Запрос ищется в названии, а при неудаче - в тегах (с меньшим весом).
+pub fn rank_notes(notes: impl IntoIterator<Item = NoteMetadata>, query: &str, limit: usize) -> Vec<PickerMatch> {
+    let mut matches: Vec<PickerMatch> = notes.into_iter()
+        .filter_map(|note| {
+            let score = fuzzy_score(query, &note.title).or_else(|| {
+                note.tags.iter()
+                    .filter_map(|tag| fuzzy_score(query.trim_start_matches('#'), tag))
+                    .max()
+                    .map(|score| score / 2)
+            })?;
+            Some(PickerMatch { note, score })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.note.pinned.cmp(&a.note.pinned)
+            .then(b.score.cmp(&a.score))
+            .then(b.note.copy_count.cmp(&a.note.copy_count))
+            .then(b.note.updated_at.cmp(&a.note.updated_at))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// Разбирает сочетание вида `Ctrl+Alt+Space`. Пустая строка - горячая клавиша отключена.
+pub fn parse_hotkey(spec: &str) -> Result<Option<HotKey>, AppError> {
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return Ok(None);
+    }
+    spec.parse::<HotKey>()
+        .map(Some)
+        .map_err(|e| AppError::Hotkey(e.to_string()))
+}
+
+/// Глобальная горячая клавиша быстрого выбора (X11, Windows, macOS).
+/// Нажатие запоминается в флаге и будит цикл отрисовки, даже если окно свернуто.
+pub struct GlobalHotkey {
+    manager: GlobalHotKeyManager,
+    registered: Option<HotKey>,
+    hotkey_id: Arc<AtomicU32>,
+    triggered: Arc<AtomicBool>,
+}
+
+impl GlobalHotkey {
+    pub fn new(ctx: &egui::Context) -> Result<Self, AppError> {
+        let manager = GlobalHotKeyManager::new().map_err(|e| AppError::Hotkey(e.to_string()))?;
+        let hotkey_id = Arc::new(AtomicU32::new(0));
+        let triggered = Arc::new(AtomicBool::new(false));
+
+        // Обработчик событий задается один раз на процесс, поэтому id текущего сочетания читается из общего значения
+        let (handler_id, handler_flag, ctx) = (hotkey_id.clone(), triggered.clone(), ctx.clone());
+        GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+            if event.state == HotKeyState::Pressed && event.id == handler_id.load(Ordering::Relaxed) {
+                handler_flag.store(true, Ordering::Relaxed);
+                ctx.request_repaint();
+            }
+        }));
+
+        Ok(Self { manager, registered: None, hotkey_id, triggered })
+    }
+
+    /// Заменяет сочетание; при ошибке прежнее сочетание остается в силе
+    pub fn register(&mut self, spec: &str) -> Result<(), AppError> {
+        let hotkey = parse_hotkey(spec)?;
+        if hotkey == self.registered {
+            return Ok(());
+        }
+
+        if let Some(new_hotkey) = hotkey {
+            self.manager.register(new_hotkey).map_err(|e| AppError::Hotkey(e.to_string()))?;
+        }
+        if let Some(old_hotkey) = self.registered.take()
+            && let Err(e) = self.manager.unregister(old_hotkey)
+        {
+            log_error!("unregister", "hotkey", &e);
+        }
+
+        self.hotkey_id.store(hotkey.map_or(0, |h| h.id()), Ordering::Relaxed);
+        self.registered = hotkey;
+        log_info!("register", "hotkey", &format!("Быстрый выбор: {}", if spec.trim().is_empty() { "отключен" } else { spec.trim() }));
+        Ok(())
+    }
+
+    /// Было ли нажато сочетание с прошлой проверки
+    pub fn take_triggered(&self) -> bool {
+        self.triggered.swap(false, Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,210 @@
+/// Модуль полнотекстового поиска по заголовкам и содержимому заметок
+use std::ops::Range;
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::notes::Note;
+use crate::storage::NoteStore;
+use crate::log_error;
+
+/// Количество символов контекста до первого совпадения во фрагменте
+const SNIPPET_CONTEXT_BEFORE: usize = 30;
+/// Максимальная длина фрагмента содержимого в символах
+const SNIPPET_MAX_CHARS: usize = 120;
+
+/// Результат поиска по одной заметке
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub note_id: Uuid,
+    pub title: String,
+    pub pinned: bool,
+    /// Чем больше, тем выше результат в списке
+    pub score: u32,
+    /// Байтовые диапазоны совпадений в заголовке
+    pub title_matches: Vec<Range<usize>>,
+    /// Фрагмент содержимого вокруг первого совпадения
+    pub snippet: String,
+    /// Байтовые диапазоны совпадений во фрагменте
+    pub snippet_matches: Vec<Range<usize>>,
+}
+
+/// Поисковый движок по заметкам
+pub struct NoteSearch {
+    terms: Vec<Vec<char>>,
+}
+
+impl NoteSearch {
+    /// Разбирает запрос на слова; пустой запрос не находит ничего
+    pub fn new(query: &str) -> Self {
+        let terms = query.split_whitespace()
+            .map(|term| term.chars().flat_map(Self::fold_char).collect::<Vec<char>>())
+            .filter(|term| !term.is_empty())
+            .collect();
+        Self { terms }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Приводит символ к виду для сравнения: нижний регистр, "ё" считается равной "е"
+    fn fold_char(c: char) -> impl Iterator<Item = char> {
+        c.to_lowercase().map(|lc| if lc == 'ё' { 'е' } else { lc })
+    }
+
+    /// Ищет все вхождения слова в тексте, возвращая байтовые диапазоны исходной строки
+    fn find_term(text: &str, term: &[char]) -> Vec<Range<usize>> {
+        // Нормализованные символы вместе с байтовыми границами исходного символа
+        let folded: Vec<(char, usize, usize)> = text.char_indices()
+            .flat_map(|(start, c)| {
+                let end = start + c.len_utf8();
+                Self::fold_char(c).map(move |fc| (fc, start, end))
+            })
+            .collect();
+
+        let mut matches = Vec::new();
+        if term.is_empty() || folded.len() < term.len() {
+            return matches;
+        }
+
+        let mut i = 0;
+        while i + term.len() <= folded.len() {
+            if folded[i..i + term.len()].iter().map(|(c, _, _)| c).eq(term.iter()) {
+                matches.push(folded[i].1..folded[i + term.len() - 1].2);
+                i += term.len();
+            } else {
+                i += 1;
+            }
+        }
+
+        matches
+    }
+
+    /// Объединяет пересекающиеся диапазоны и сортирует их
+    fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+        ranges.sort_by_key(|r| r.start);
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
+    /// Проверяет заметку; возвращает None, если хотя бы одно слово запроса не найдено
+    pub fn match_note(&self, note: &Note) -> Option<SearchResult> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut score = 0u32;
+        let mut title_matches = Vec::new();
+        let mut content_matches = Vec::new();
+
+        for term in &self.terms {
+            let in_title = Self::find_term(&note.title, term);
+            let in_content = Self::find_term(&note.content, term);
+
+            if in_title.is_empty() && in_content.is_empty() {
+                return None;
+            }
+
+            if !in_title.is_empty() {
+                score += 100;
+                if in_title[0].start == 0 {
+                    score += 50; // Заголовок начинается с искомого слова
+                }
+            }
+            score += (in_content.len() as u32).min(20) * 5;
+
+            title_matches.extend(in_title);
+            content_matches.extend(in_content);
+        }
+
+        if note.pinned {
+            score += 10;
+        }
+
+        let content_matches = Self::merge_ranges(content_matches);
+        let (snippet, snippet_matches) = Self::build_snippet(&note.content, &content_matches);
+
+        Some(SearchResult {
+            note_id: note.id,
+            title: note.title.clone(),
+            pinned: note.pinned,
+            score,
+            title_matches: Self::merge_ranges(title_matches),
+            snippet,
+            snippet_matches,
+        })
+    }
+
+    /// Вырезает фрагмент содержимого вокруг первого совпадения
+    fn build_snippet(content: &str, matches: &[Range<usize>]) -> (String, Vec<Range<usize>>) {
+        let first_match = matches.first().map(|r| r.start).unwrap_or(0);
+
+        // Начало фрагмента отстоит на SNIPPET_CONTEXT_BEFORE символов от совпадения
+        let start = content[..first_match].char_indices()
+            .rev()
+            .nth(SNIPPET_CONTEXT_BEFORE.saturating_sub(1))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        let end = content[start..].char_indices()
+            .nth(SNIPPET_MAX_CHARS)
+            .map(|(i, _)| start + i)
+            .unwrap_or(content.len());
+
+        let mut snippet = String::new();
+        let mut offset = 0;
+        if start > 0 {
+            snippet.push('…');
+            offset = '…'.len_utf8();
+        }
+        // Переводы строк заменяем пробелами, чтобы фрагмент помещался в одну строку
+        snippet.extend(content[start..end].chars().map(|c| if c == '\n' || c == '\r' { ' ' } else { c }));
+        if end < content.len() {
+            snippet.push('…');
+        }
+
+        let snippet_matches = matches.iter()
+            .filter(|r| r.start >= start && r.end <= end)
+            .map(|r| (r.start - start + offset)..(r.end - start + offset))
+            .collect();
+
+        (snippet, snippet_matches)
+    }
+
+    /// Сортирует результаты: сначала по релевантности, затем по заголовку
+    fn rank(results: &mut [SearchResult]) {
+        results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+    }
+
+    /// Поиск по заметкам, уже загруженным в память
+    pub fn search_notes(&self, notes: &[Note]) -> Vec<SearchResult> {
+        let mut results: Vec<SearchResult> = notes.iter()
+            .filter_map(|note| self.match_note(note))
+            .collect();
+        Self::rank(&mut results);
+        results
+    }
+
+    /// Поиск по всем заметкам хранилища (для ленивого режима, где в памяти только часть заметок).
+    /// Заметки читаются по одной, чтобы не держать всю коллекцию в памяти.
+    pub fn search_store(&self, store: &dyn NoteStore) -> Result<Vec<SearchResult>, AppError> {
+        let mut results = Vec::new();
+        for note_id in store.get_note_ids()? {
+            match store.load_note_by_id(note_id) {
+                Ok(Some(note)) => {
+                    if let Some(result) = self.match_note(&note) {
+                        results.push(result);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => log_error!("search", "note", &note_id.to_string(), &e),
+            }
+        }
+        Self::rank(&mut results);
+        Ok(results)
+    }
+}
@@ -0,0 +1,134 @@
+/// Модуль для хранения пользовательских настроек между запусками
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use crate::backup::DEFAULT_DAILY_BACKUPS_TO_KEEP;
+use crate::error::AppError;
+use crate::fs_utils;
+use crate::notes::NoteSortMode;
+use crate::quick_picker::DEFAULT_QUICK_PICKER_HOTKEY;
+use crate::state::LoadMode;
+use crate::storage::StorageBackend;
+use crate::trash::DEFAULT_TRASH_RETENTION_DAYS;
+use crate::ui::keymap::Keymap;
+use crate::ui::theme::ThemeMode;
+use crate::{log_error, log_info};
+
+/// Текущая версия формата файла настроек
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Имя файла настроек в базовой директории приложения
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// Пользовательские настройки приложения
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Версия формата файла (для миграций)
+    pub version: u32,
+    pub theme_mode: ThemeMode,
+    pub load_mode: LoadMode,
+    pub show_performance_stats: bool,
+    /// Формат хранения заметок (применяется при следующем запуске)
+    pub storage_backend: StorageBackend,
+    /// Сколько дней хранить удаленное в корзине (0 - бессрочно)
+    pub trash_retention_days: u32,
+    /// Сколько ежедневных резервных копий хранить (0 - не создавать)
+    pub daily_backups_to_keep: u32,
+    /// Привязки горячих клавиш
+    pub keymap: Keymap,
+    /// Глобальное сочетание быстрого выбора (пустая строка - отключено)
+    pub quick_picker_hotkey: String,
+    /// Порядок заметок в группах без собственной сортировки
+    pub note_sort_mode: NoteSortMode,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            theme_mode: ThemeMode::Auto,
+            load_mode: LoadMode::Auto,
+            show_performance_stats: false,
+            storage_backend: StorageBackend::Json,
+            trash_retention_days: DEFAULT_TRASH_RETENTION_DAYS,
+            daily_backups_to_keep: DEFAULT_DAILY_BACKUPS_TO_KEEP,
+            keymap: Keymap::default(),
+            quick_picker_hotkey: DEFAULT_QUICK_PICKER_HOTKEY.to_string(),
+            note_sort_mode: NoteSortMode::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Приводит настройки старых версий к текущему формату
+    pub(crate) fn migrate(mut self) -> Self {
+        if self.version < SETTINGS_VERSION {
+            log_info!("migrate", "settings", &format!("Обновление настроек с версии {} до {}", self.version, SETTINGS_VERSION));
+            self.version = SETTINGS_VERSION;
+        }
+        self
+    }
+}
+
+/// Структура для загрузки и сохранения настроек
+pub struct SettingsManager {
+    settings_path: PathBuf,
+}
+
+impl SettingsManager {
+    /// Создает менеджер настроек для указанной базовой директории
+    pub fn new(base_dir: &Path) -> Self {
+        Self {
+            settings_path: base_dir.join(SETTINGS_FILE_NAME),
+        }
+    }
+
+    /// Загружает настройки, возвращая значения по умолчанию при отсутствии или повреждении файла
+    pub fn load(&self) -> AppSettings {
+        if !self.settings_path.exists() {
+            return AppSettings::default();
+        }
+
+        match self.try_load() {
+            Ok(settings) => settings.migrate(),
+            Err(e) => {
+                log_error!("load", "settings", &e);
+                // Сохраняем повреждённый файл рядом, чтобы не потерять его при следующей записи
+                let backup_path = self.settings_path.with_extension("json.bak");
+                if let Err(e) = fs::rename(&self.settings_path, &backup_path) {
+                    log_error!("backup", "settings", &e);
+                }
+                AppSettings::default()
+            }
+        }
+    }
+
+    /// Загружает настройки, не изменяя файл: поврежденный файл не откладывается в `.bak`.
+    /// Для команд, которые читают данные без блокировки папки
+    pub fn load_read_only(&self) -> AppSettings {
+        if !self.settings_path.exists() {
+            return AppSettings::default();
+        }
+        self.try_load().map(AppSettings::migrate).unwrap_or_else(|e| {
+            log_error!("load", "settings", &e);
+            AppSettings::default()
+        })
+    }
+
+    fn try_load(&self) -> Result<AppSettings, AppError> {
+        let content = fs::read_to_string(&self.settings_path)?;
+        let settings: AppSettings = serde_json::from_str(&content)?;
+        Ok(settings)
+    }
+
+    /// Сохраняет настройки в файл
+    pub fn save(&self, settings: &AppSettings) -> Result<(), AppError> {
+        if let Some(parent) = self.settings_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(settings)?;
+        fs_utils::atomic_write(&self.settings_path, content)?;
+        Ok(())
+    }
+}
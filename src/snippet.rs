@@ -0,0 +1,172 @@
+/// Подстановки в тексте заметок: `{{date}}`, `{{time}}`, `{{clipboard}}`, `{{uuid}}` и поля `{{name:Клиент}}`
+use std::collections::HashMap;
+use chrono::{DateTime, Local};
+use uuid::Uuid;
+
+/// Формат `{{date}}`
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Формат `{{time}}`
+pub const TIME_FORMAT: &str = "%H:%M";
+
+/// Подстановка в тексте заметки
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Placeholder {
+    Date,
+    Time,
+    Clipboard,
+    Uuid,
+    /// Поле, значение которого вводит пользователь
+    Field(String),
+}
+
+impl Placeholder {
+    /// Разбирает содержимое между `{{` и `}}`; неизвестные имена не считаются подстановками
+    fn parse(inner: &str) -> Option<Self> {
+        let inner = inner.trim();
+        if let Some(name) = inner.strip_prefix("name:") {
+            let name = name.trim();
+            return (!name.is_empty()).then(|| Placeholder::Field(name.to_string()));
+        }
+        match inner.to_lowercase().as_str() {
+            "date" => Some(Placeholder::Date),
+            "time" => Some(Placeholder::Time),
+            "clipboard" => Some(Placeholder::Clipboard),
+            "uuid" => Some(Placeholder::Uuid),
+            _ => None,
+        }
+    }
+}
+
+/// Часть текста: обычный текст или подстановка
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+/// Разбивает текст на обычные части и подстановки. `{{...}}` с неизвестным именем остается текстом.
+pub fn parse(text: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let placeholder = after.find("}}")
+            .filter(|&end| !after[..end].contains('\n'))
+            .and_then(|end| Placeholder::parse(&after[..end]).map(|p| (p, end)));
+
+        match placeholder {
+            Some((placeholder, end)) => {
+                literal.push_str(&rest[..start]);
+                if !literal.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Placeholder(placeholder));
+                rest = &after[end + 2..];
+            }
+            None => {
+                literal.push_str(&rest[..start + 2]);
+                rest = after;
+            }
+        }
+    }
+
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        segments.push(Segment::Text(literal));
+    }
+    segments
+}
+
+/// Есть ли в тексте хотя бы одна подстановка
+pub fn has_placeholders(text: &str) -> bool {
+    parse(text).iter().any(|segment| matches!(segment, Segment::Placeholder(_)))
+}
+
+/// Имена полей `{{name:...}}` в порядке первого появления, без повторов
+pub fn field_names(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for segment in parse(text) {
+        if let Segment::Placeholder(Placeholder::Field(name)) = segment
+            && !names.contains(&name)
+        {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Значения встроенных подстановок на момент копирования
+#[derive(Debug, Clone)]
+pub struct BuiltinValues {
+    pub now: DateTime<Local>,
+    /// Текущее содержимое буфера обмена (пустая строка, если прочитать не удалось)
+    pub clipboard: String,
+}
+
+impl BuiltinValues {
+    pub fn new(clipboard: String) -> Self {
+        Self { now: Local::now(), clipboard }
+    }
+}
+
+/// Раскрывает подстановки. Каждый `{{uuid}}` получает новый идентификатор;
+/// поле без введенного значения заменяется пустой строкой.
+pub fn expand(text: &str, builtins: &BuiltinValues, fields: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    for segment in parse(text) {
+        match segment {
+            Segment::Text(text) => out.push_str(&text),
+            Segment::Placeholder(Placeholder::Date) => out.push_str(&builtins.now.format(DATE_FORMAT).to_string()),
+            Segment::Placeholder(Placeholder::Time) => out.push_str(&builtins.now.format(TIME_FORMAT).to_string()),
+            Segment::Placeholder(Placeholder::Clipboard) => out.push_str(&builtins.clipboard),
+            Segment::Placeholder(Placeholder::Uuid) => out.push_str(&Uuid::new_v4().to_string()),
+            Segment::Placeholder(Placeholder::Field(name)) => {
+                out.push_str(fields.get(&name).map(String::as_str).unwrap_or_default());
+            }
+        }
+    }
+    out
+}
+
+/// Куда поместить раскрытый текст
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTarget {
+    Clipboard,
+    PersistentText,
+}
+
+/// Форма ввода значений полей перед копированием заметки
+#[derive(Debug, Clone)]
+pub struct SnippetForm {
+    pub note_id: Uuid,
+    pub note_title: String,
+    /// Текст заметки на момент копирования
+    pub content: String,
+    pub target: CopyTarget,
+    /// Имя поля и введенное значение
+    pub fields: Vec<(String, String)>,
+}
+
+impl SnippetForm {
+    /// Форма для текста с полями `{{name:...}}`; `None`, если полей нет и спрашивать нечего
+    pub fn new(note_id: Uuid, note_title: &str, content: &str, target: CopyTarget) -> Option<Self> {
+        let fields: Vec<(String, String)> = field_names(content)
+            .into_iter()
+            .map(|name| (name, String::new()))
+            .collect();
+        (!fields.is_empty()).then(|| Self {
+            note_id,
+            note_title: note_title.to_string(),
+            content: content.to_string(),
+            target,
+            fields,
+        })
+    }
+
+    pub fn values(&self) -> HashMap<String, String> {
+        self.fields.iter().cloned().collect()
+    }
+}
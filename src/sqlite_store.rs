@@ -0,0 +1,495 @@
+/// Хранилище заметок во встроенной базе SQLite (для больших коллекций)
+use std::path::Path;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Row};
+use rusqlite::types::Type;
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::history::NoteRevision;
+use crate::notes::{Note, NoteGroup, NoteMetadata};
+use crate::storage::NoteStore;
+use crate::trash::{Trash, TrashedGroup, TrashedNote};
+use crate::{log_info, log_success};
+
+/// Версия схемы базы (хранится в `PRAGMA user_version`)
+const SCHEMA_VERSION: i32 = 5;
+
+/// Ключ таблицы `meta` для постоянного текста
+const META_PERSISTENT_TEXT: &str = "persistent_text";
+/// Ключ таблицы `meta`, отмечающий выполненную миграцию из JSON-файлов
+const META_JSON_MIGRATED: &str = "json_migrated";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS notes (
+        id             TEXT PRIMARY KEY,
+        title          TEXT NOT NULL,
+        content        TEXT NOT NULL,
+        created_at     TEXT NOT NULL,
+        updated_at     TEXT NOT NULL,
+        pinned         INTEGER NOT NULL DEFAULT 0,
+        group_id       TEXT,
+        tags           TEXT NOT NULL DEFAULT '[]',
+        content_length INTEGER NOT NULL DEFAULT 0,
+        copy_count     INTEGER NOT NULL DEFAULT 0,
+        last_used_at   TEXT,
+        sort_index     INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE INDEX IF NOT EXISTS idx_notes_created_at ON notes(created_at DESC);
+    CREATE INDEX IF NOT EXISTS idx_notes_group_id ON notes(group_id);
+
+    CREATE TABLE IF NOT EXISTS groups (
+        id        TEXT PRIMARY KEY,
+        name      TEXT NOT NULL,
+        collapsed INTEGER NOT NULL DEFAULT 0,
+        parent_id TEXT,
+        level     INTEGER NOT NULL DEFAULT 0,
+        position  INTEGER NOT NULL,
+        sort_mode TEXT
+    );
+
+    CREATE TABLE IF NOT EXISTS revisions (
+        id       TEXT PRIMARY KEY,
+        note_id  TEXT NOT NULL,
+        title    TEXT NOT NULL,
+        content  TEXT NOT NULL,
+        saved_at TEXT NOT NULL,
+        position INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_revisions_note_id ON revisions(note_id, position);
+
+    CREATE TABLE IF NOT EXISTS trash (
+        id         TEXT PRIMARY KEY,
+        kind       TEXT NOT NULL,
+        deleted_at TEXT NOT NULL,
+        payload    TEXT NOT NULL,
+        position   INTEGER NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS meta (
+        key   TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+";
+
+/// Вид элемента корзины в таблице `trash`
+const TRASH_KIND_NOTE: &str = "note";
+const TRASH_KIND_GROUP: &str = "group";
+
+/// Колонки метаданных (всё, кроме содержимого)
+const METADATA_COLUMNS: &str = "id, title, created_at, updated_at, pinned, group_id, tags, content_length, copy_count, last_used_at";
+/// Колонки полной заметки
+const NOTE_COLUMNS: &str = "id, title, content, created_at, updated_at, pinned, group_id, tags, copy_count, last_used_at, sort_index";
+
+/// Колонки, добавленные после первой версии схемы: (таблица, колонка, определение)
+const ADDED_COLUMNS: [(&str, &str, &str); 4] = [
+    ("notes", "copy_count", "INTEGER NOT NULL DEFAULT 0"),
+    ("notes", "last_used_at", "TEXT"),
+    ("notes", "sort_index", "INTEGER NOT NULL DEFAULT 0"),
+    ("groups", "sort_mode", "TEXT"),
+];
+
+/// Хранилище заметок, групп и черновика в одном файле SQLite
+pub struct SqliteNoteStore {
+    conn: Connection,
+}
+
+impl SqliteNoteStore {
+    /// Открывает (или создает) базу по указанному пути
+    pub fn open(path: &Path) -> Result<Self, AppError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Открывает существующую базу только для чтения: схема не обновляется, миграция не выполняется.
+    /// `None`, если базы нет или заметки в нее еще не перенесены - тогда актуальны JSON-файлы
+    pub fn open_read_only(path: &Path) -> Result<Option<Self>, AppError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let store = Self { conn: Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)? };
+        Ok(store.meta(META_JSON_MIGRATED)?.map(|_| store))
+    }
+
+    /// Создает базу в памяти (для тестов)
+    pub fn open_in_memory() -> Result<Self, AppError> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, AppError> {
+        // WAL переживает сбои и не блокирует чтение во время записи
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.execute_batch(SCHEMA)?;
+        Self::add_missing_columns(&conn)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        Ok(Self { conn })
+    }
+
+    /// Добавляет в таблицы из старых версий базы колонки, которых там еще нет
+    fn add_missing_columns(conn: &Connection) -> Result<(), AppError> {
+        for (table, column, definition) in ADDED_COLUMNS {
+            let exists: bool = conn.query_row(
+                &format!("SELECT COUNT(*) > 0 FROM pragma_table_info('{}') WHERE name = ?1", table),
+                [column],
+                |row| row.get(0),
+            )?;
+            if !exists {
+                conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Однократно переносит заметки, группы и черновик из другого хранилища (JSON-файлов).
+    /// Возвращает количество перенесенных заметок или `None`, если миграция уже выполнялась.
+    /// Исходные файлы не удаляются.
+    pub fn migrate_from(&self, source: &dyn NoteStore) -> Result<Option<usize>, AppError> {
+        if self.meta(META_JSON_MIGRATED)?.is_some() {
+            return Ok(None);
+        }
+
+        let notes = source.get_all_notes()?;
+        let groups = source.load_groups()?;
+        let persistent_text = source.load_persistent_text()?;
+        let trash = source.load_trash()?;
+        log_info!("migrate", "sqlite", &format!("Перенос {} заметок и {} групп в SQLite", notes.len(), groups.len()));
+
+        let tx = self.conn.unchecked_transaction()?;
+        for note in &notes {
+            // Заметки, уже созданные в базе, не перезаписываем
+            Self::write_note(&tx, "INSERT OR IGNORE", note)?;
+            let revisions = source.load_revisions(note.id)?;
+            if !revisions.is_empty() {
+                Self::replace_revisions(&tx, note.id, &revisions)?;
+            }
+        }
+        if !groups.is_empty() {
+            Self::replace_groups(&tx, &groups)?;
+        }
+        if !persistent_text.is_empty() {
+            Self::set_meta(&tx, META_PERSISTENT_TEXT, &persistent_text)?;
+        }
+        if !trash.is_empty() {
+            Self::replace_trash(&tx, &trash)?;
+        }
+        Self::set_meta(&tx, META_JSON_MIGRATED, &Utc::now().to_rfc3339())?;
+        tx.commit()?;
+
+        log_success!("migrate", "sqlite");
+        Ok(Some(notes.len()))
+    }
+
+    /// Перезаписывает корзину; сами элементы хранятся как JSON
+    fn replace_trash(conn: &Connection, trash: &Trash) -> Result<(), AppError> {
+        conn.execute("DELETE FROM trash", [])?;
+        let mut stmt = conn.prepare(
+            "INSERT INTO trash (id, kind, deleted_at, payload, position) VALUES (?1, ?2, ?3, ?4, ?5)"
+        )?;
+        let mut position: i64 = 0;
+        for item in &trash.notes {
+            let payload = serde_json::to_string(item)?;
+            stmt.execute(params![item.note.id.to_string(), TRASH_KIND_NOTE, Self::format_time(&item.deleted_at), payload, position])?;
+            position += 1;
+        }
+        for item in &trash.groups {
+            let payload = serde_json::to_string(item)?;
+            stmt.execute(params![item.group.id.to_string(), TRASH_KIND_GROUP, Self::format_time(&item.deleted_at), payload, position])?;
+            position += 1;
+        }
+        Ok(())
+    }
+
+    fn meta(&self, key: &str) -> Result<Option<String>, AppError> {
+        Ok(self.conn
+            .query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| row.get(0))
+            .optional()?)
+    }
+
+    fn set_meta(conn: &Connection, key: &str, value: &str) -> Result<(), AppError> {
+        conn.execute(
+            "INSERT INTO meta (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    fn replace_groups(conn: &Connection, groups: &[NoteGroup]) -> Result<(), AppError> {
+        conn.execute("DELETE FROM groups", [])?;
+        for (position, group) in groups.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO groups (id, name, collapsed, parent_id, level, position, sort_mode) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    group.id.to_string(),
+                    group.name,
+                    group.collapsed,
+                    group.parent_id.map(|id| id.to_string()),
+                    group.level,
+                    position as i64,
+                    group.sort_mode.map(|mode| serde_json::to_string(&mode)).transpose()?,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn replace_revisions(conn: &Connection, note_id: Uuid, revisions: &[NoteRevision]) -> Result<(), AppError> {
+        conn.execute("DELETE FROM revisions WHERE note_id = ?1", [note_id.to_string()])?;
+        for (position, revision) in revisions.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO revisions (id, note_id, title, content, saved_at, position) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    revision.id.to_string(),
+                    note_id.to_string(),
+                    revision.title,
+                    revision.content,
+                    Self::format_time(&revision.saved_at),
+                    position as i64,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Время хранится в RFC 3339 с фиксированной точностью, чтобы строки сортировались хронологически
+    fn format_time(time: &DateTime<Utc>) -> String {
+        time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+    }
+
+    /// Записывает заметку; `verb` - "INSERT OR REPLACE" или "INSERT OR IGNORE"
+    fn write_note(conn: &Connection, verb: &str, note: &Note) -> Result<(), AppError> {
+        let sql = format!(
+            "{} INTO notes (id, title, content, created_at, updated_at, pinned, group_id, tags, content_length, copy_count, last_used_at, sort_index)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            verb
+        );
+        conn.execute(&sql, params![
+            note.id.to_string(),
+            note.title,
+            note.content,
+            Self::format_time(&note.created_at),
+            Self::format_time(&note.updated_at),
+            note.pinned,
+            note.group_id.map(|id| id.to_string()),
+            serde_json::to_string(&note.tags)?,
+            note.content.len() as i64,
+            note.copy_count,
+            note.last_used_at.as_ref().map(Self::format_time),
+            note.sort_index,
+        ])?;
+        Ok(())
+    }
+
+    fn conversion_error(idx: usize, error: impl std::error::Error + Send + Sync + 'static) -> rusqlite::Error {
+        rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(error))
+    }
+
+    fn get_uuid(row: &Row, idx: usize) -> rusqlite::Result<Uuid> {
+        let value: String = row.get(idx)?;
+        Uuid::parse_str(&value).map_err(|e| Self::conversion_error(idx, e))
+    }
+
+    fn get_optional_uuid(row: &Row, idx: usize) -> rusqlite::Result<Option<Uuid>> {
+        let value: Option<String> = row.get(idx)?;
+        value.map(|v| Uuid::parse_str(&v).map_err(|e| Self::conversion_error(idx, e)))
+            .transpose()
+    }
+
+    fn get_time(row: &Row, idx: usize) -> rusqlite::Result<DateTime<Utc>> {
+        let value: String = row.get(idx)?;
+        DateTime::parse_from_rfc3339(&value)
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|e| Self::conversion_error(idx, e))
+    }
+
+    fn get_optional_time(row: &Row, idx: usize) -> rusqlite::Result<Option<DateTime<Utc>>> {
+        let value: Option<String> = row.get(idx)?;
+        value.map(|v| DateTime::parse_from_rfc3339(&v)
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|e| Self::conversion_error(idx, e)))
+            .transpose()
+    }
+
+    fn get_tags(row: &Row, idx: usize) -> rusqlite::Result<Vec<String>> {
+        let value: String = row.get(idx)?;
+        serde_json::from_str(&value).map_err(|e| Self::conversion_error(idx, e))
+    }
+
+    /// Строка запроса с колонками `NOTE_COLUMNS` -> заметка
+    fn note_from_row(row: &Row) -> rusqlite::Result<Note> {
+        Ok(Note {
+            id: Self::get_uuid(row, 0)?,
+            title: row.get(1)?,
+            content: row.get(2)?,
+            created_at: Self::get_time(row, 3)?,
+            updated_at: Self::get_time(row, 4)?,
+            pinned: row.get(5)?,
+            group_id: Self::get_optional_uuid(row, 6)?,
+            tags: Self::get_tags(row, 7)?,
+            copy_count: row.get(8)?,
+            last_used_at: Self::get_optional_time(row, 9)?,
+            sort_index: row.get(10)?,
+        })
+    }
+
+    /// Строка запроса с колонками `METADATA_COLUMNS` -> метаданные
+    fn metadata_from_row(row: &Row) -> rusqlite::Result<NoteMetadata> {
+        Ok(NoteMetadata {
+            id: Self::get_uuid(row, 0)?,
+            title: row.get(1)?,
+            created_at: Self::get_time(row, 2)?,
+            updated_at: Self::get_time(row, 3)?,
+            pinned: row.get(4)?,
+            group_id: Self::get_optional_uuid(row, 5)?,
+            tags: Self::get_tags(row, 6)?,
+            content_length: row.get::<_, i64>(7)? as usize,
+            copy_count: row.get(8)?,
+            last_used_at: Self::get_optional_time(row, 9)?,
+        })
+    }
+}
+
+impl NoteStore for SqliteNoteStore {
+    fn save_note(&self, note: &Note) -> Result<(), AppError> {
+        Self::write_note(&self.conn, "INSERT OR REPLACE", note)
+    }
+
+    fn load_note_by_id(&self, id: Uuid) -> Result<Option<Note>, AppError> {
+        let sql = format!("SELECT {} FROM notes WHERE id = ?1", NOTE_COLUMNS);
+        Ok(self.conn
+            .query_row(&sql, [id.to_string()], Self::note_from_row)
+            .optional()?)
+    }
+
+    fn delete_note(&self, id: Uuid) -> Result<(), AppError> {
+        let deleted = self.conn.execute("DELETE FROM notes WHERE id = ?1", [id.to_string()])?;
+        if deleted == 0 {
+            return Err(AppError::NoteNotFound);
+        }
+        Ok(())
+    }
+
+    fn get_all_notes(&self) -> Result<Vec<Note>, AppError> {
+        let sql = format!("SELECT {} FROM notes ORDER BY created_at DESC, id", NOTE_COLUMNS);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let notes = stmt.query_map([], Self::note_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(notes)
+    }
+
+    fn get_note_ids(&self) -> Result<Vec<Uuid>, AppError> {
+        let mut stmt = self.conn.prepare("SELECT id FROM notes ORDER BY created_at DESC, id")?;
+        let ids = stmt.query_map([], |row| Self::get_uuid(row, 0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+
+    fn get_notes_metadata(&self) -> Result<Vec<NoteMetadata>, AppError> {
+        let sql = format!("SELECT {} FROM notes ORDER BY created_at DESC, id", METADATA_COLUMNS);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let metadata = stmt.query_map([], Self::metadata_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(metadata)
+    }
+
+    fn get_notes_page(&self, page: usize, page_size: usize) -> Result<Vec<Note>, AppError> {
+        let sql = format!("SELECT {} FROM notes ORDER BY created_at DESC, id LIMIT ?1 OFFSET ?2", NOTE_COLUMNS);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let notes = stmt.query_map(params![page_size as i64, (page * page_size) as i64], Self::note_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(notes)
+    }
+
+    fn get_notes_count(&self) -> Result<usize, AppError> {
+        let count: i64 = self.conn.query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    fn save_groups(&self, groups: &[NoteGroup]) -> Result<(), AppError> {
+        let tx = self.conn.unchecked_transaction()?;
+        Self::replace_groups(&tx, groups)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn load_groups(&self) -> Result<Vec<NoteGroup>, AppError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, collapsed, parent_id, level, sort_mode FROM groups ORDER BY position"
+        )?;
+        let groups = stmt.query_map([], |row| {
+            Ok(NoteGroup {
+                id: Self::get_uuid(row, 0)?,
+                name: row.get(1)?,
+                collapsed: row.get(2)?,
+                parent_id: Self::get_optional_uuid(row, 3)?,
+                level: row.get(4)?,
+                sort_mode: row.get::<_, Option<String>>(5)?
+                    .map(|mode| serde_json::from_str(&mode).map_err(|e| Self::conversion_error(5, e)))
+                    .transpose()?,
+            })
+        })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(groups)
+    }
+
+    fn load_persistent_text(&self) -> Result<String, AppError> {
+        Ok(self.meta(META_PERSISTENT_TEXT)?.unwrap_or_default())
+    }
+
+    fn save_persistent_text(&self, text: &str) -> Result<(), AppError> {
+        Self::set_meta(&self.conn, META_PERSISTENT_TEXT, text)
+    }
+
+    fn load_revisions(&self, note_id: Uuid) -> Result<Vec<NoteRevision>, AppError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, note_id, title, content, saved_at FROM revisions WHERE note_id = ?1 ORDER BY position"
+        )?;
+        let revisions = stmt.query_map([note_id.to_string()], |row| {
+            Ok(NoteRevision {
+                id: Self::get_uuid(row, 0)?,
+                note_id: Self::get_uuid(row, 1)?,
+                title: row.get(2)?,
+                content: row.get(3)?,
+                saved_at: Self::get_time(row, 4)?,
+            })
+        })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(revisions)
+    }
+
+    fn save_revisions(&self, note_id: Uuid, revisions: &[NoteRevision]) -> Result<(), AppError> {
+        let tx = self.conn.unchecked_transaction()?;
+        Self::replace_revisions(&tx, note_id, revisions)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn delete_revisions(&self, note_id: Uuid) -> Result<(), AppError> {
+        self.conn.execute("DELETE FROM revisions WHERE note_id = ?1", [note_id.to_string()])?;
+        Ok(())
+    }
+
+    fn load_trash(&self) -> Result<Trash, AppError> {
+        let mut stmt = self.conn.prepare("SELECT kind, payload FROM trash ORDER BY position")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut trash = Trash::default();
+        for (kind, payload) in rows {
+            match kind.as_str() {
+                TRASH_KIND_NOTE => trash.notes.push(serde_json::from_str::<TrashedNote>(&payload)?),
+                TRASH_KIND_GROUP => trash.groups.push(serde_json::from_str::<TrashedGroup>(&payload)?),
+                _ => {}
+            }
+        }
+        Ok(trash)
+    }
+
+    fn save_trash(&self, trash: &Trash) -> Result<(), AppError> {
+        let tx = self.conn.unchecked_transaction()?;
+        Self::replace_trash(&tx, trash)?;
+        tx.commit()?;
+        Ok(())
+    }
+}
@@ -1,12 +1,28 @@
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
-use crate::notes::{Note, NotesManager, NoteGroup, NoteMetadata};
+use serde::{Serialize, Deserialize};
+use crate::notes::{Note, NoteGroup, NoteMetadata, NoteSortMode};
+use crate::storage::{NoteStore, StorageBackend};
 use crate::ui::theme::ThemeMode;
-use crate::validation::ValidationRules;
+use crate::ui::keymap::ShortcutAction;
+use crate::error::AppError;
+use crate::export::{self, ExportReport};
+use crate::backup::{self, BackupContents, BackupManifest, PendingRestore, RestoreMode, RestorePreview};
+use crate::import::{self, ImportReport, ImportSource};
+use crate::history::{NoteRevision, MAX_REVISIONS_PER_NOTE};
+use crate::validation::{ValidationError, ValidationRules};
+use crate::notifications::NotificationCenter;
 use crate::performance::{LazyNoteLoader, LoaderStats};
+use crate::settings::AppSettings;
+use crate::snippet::SnippetForm;
+use crate::search::{NoteSearch, SearchResult};
+use crate::trash::{Trash, TrashedGroup, TrashedNote};
+use crate::undo::{Change, Command, UndoStack};
+use crate::watcher::{ExternalChanges, SyncReport};
 use crate::{log_error, log_info, log_success};
 
 /// Режим загрузки заметок
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LoadMode {
     /// Автоматический выбор режима в зависимости от количества заметок
     Auto,
@@ -20,7 +36,8 @@ pub enum LoadMode {
 pub struct AppState {
     pub notes: Vec<Note>,
     pub groups: Vec<NoteGroup>,
-    pub notes_manager: NotesManager,
+    /// Хранилище заметок, групп и черновика
+    pub store: Box<dyn NoteStore>,
     pub persistent_text: String,
     /// Система ленивой загрузки заметок
     pub lazy_loader: Option<LazyNoteLoader>,
@@ -28,18 +45,31 @@ pub struct AppState {
     pub load_mode: LoadMode,
     /// Пороговое количество заметок для переключения на ленивую загрузку
     pub lazy_threshold: usize,
+    /// Удаленные заметки и группы, которые еще можно восстановить
+    pub trash: Trash,
+    /// Отмена и повтор операций (Ctrl+Z / Ctrl+Shift+Z)
+    pub undo_stack: UndoStack,
+    /// Порядок заметок в группах без собственной сортировки
+    pub sort_mode: NoteSortMode,
+    /// Позиции заметок в `notes` по id (пересчитываются после каждого изменения списка)
+    note_positions: HashMap<Uuid, usize>,
 }
 
 impl AppState {
-    pub fn new(notes_manager: NotesManager) -> Self {
-        Self::with_load_mode(notes_manager, LoadMode::Auto)
+    pub fn new(store: impl NoteStore + 'static) -> Self {
+        Self::with_load_mode(store, LoadMode::Auto)
     }
 
     /// Создает новое состояние с выбранным режимом загрузки
-    pub fn with_load_mode(notes_manager: NotesManager, load_mode: LoadMode) -> Self {
+    pub fn with_load_mode(store: impl NoteStore + 'static, load_mode: LoadMode) -> Self {
+        Self::with_store(Box::new(store), load_mode)
+    }
+
+    /// Создает состояние поверх хранилища, выбранного во время выполнения
+    pub fn with_store(store: Box<dyn NoteStore>, load_mode: LoadMode) -> Self {
         // Определяем режим загрузки
         let lazy_threshold = 100; // Переходим на ленивую загрузку при >100 заметках
-        let notes_count = notes_manager.get_notes_count().unwrap_or(0);
+        let notes_count = store.get_notes_count().unwrap_or(0);
         
         let actual_load_mode = match load_mode {
             LoadMode::Auto => {
@@ -54,12 +84,16 @@ impl AppState {
 
         let mut state = Self {
             notes: Vec::new(),
-            groups: notes_manager.load_groups().unwrap_or_default(),
-            notes_manager,
+            groups: store.load_groups().unwrap_or_default(),
+            store,
             persistent_text: String::new(),
             lazy_loader: None,
             load_mode: actual_load_mode,
             lazy_threshold,
+            trash: Trash::default(),
+            undo_stack: UndoStack::new(),
+            sort_mode: NoteSortMode::default(),
+            note_positions: HashMap::new(),
         };
 
         // Инициализируем загрузку данных
@@ -73,17 +107,17 @@ impl AppState {
         match self.load_mode {
             LoadMode::Auto | LoadMode::Eager => {
                 // Обычная загрузка всех заметок
-                self.notes = Self::load_and_sort_notes(&self.notes_manager);
+                self.notes = Self::load_and_sort_notes(self.store.as_ref());
                 log_info!("init", "notes", "eager", &format!("Загружено {} заметок в режиме Eager", self.notes.len()));
             }
             LoadMode::Lazy => {
                 // Инициализируем ленивую загрузку
                 let mut lazy_loader = LazyNoteLoader::new(20, 0); // 20 заметок на страницу
-                if let Err(e) = lazy_loader.initialize_with_manager(&self.notes_manager) {
+                if let Err(e) = lazy_loader.initialize_with_store(self.store.as_ref()) {
                     log_error!("init", "lazy_loader", &e);
                     // Fallback на обычную загрузку
                     self.load_mode = LoadMode::Eager;
-                    self.notes = Self::load_and_sort_notes(&self.notes_manager);
+                    self.notes = Self::load_and_sort_notes(self.store.as_ref());
                 } else {
                     let total_notes = lazy_loader.total_count();
                     self.lazy_loader = Some(lazy_loader);
@@ -95,16 +129,23 @@ impl AppState {
         }
 
         // Загружаем постоянный текст
-        self.persistent_text = Self::load_persistent_text(&self.notes_manager).unwrap_or_default();
+        self.persistent_text = self.store.load_persistent_text().unwrap_or_default();
+
+        self.trash = self.store.load_trash().unwrap_or_else(|e| {
+            log_error!("load", "trash", &e);
+            Trash::default()
+        });
+        self.reindex_notes();
     }
 
     /// Загружает первую страницу заметок при ленивой загрузке
     fn load_initial_page(&mut self) {
         if let Some(ref mut lazy_loader) = self.lazy_loader {
-            match lazy_loader.load_next_page(&self.notes_manager) {
+            match lazy_loader.load_next_page(self.store.as_ref()) {
                 Ok(initial_notes) => {
                     self.notes = initial_notes;
                     Self::sort_notes(&mut self.notes);
+                    Self::index_notes(&self.notes, &mut self.note_positions);
                     log_info!("load", "page", "0", &format!("Загружена первая страница: {} заметок", self.notes.len()));
                 }
                 Err(e) => {
@@ -115,18 +156,19 @@ impl AppState {
     }
 
     /// Загружает больше заметок при необходимости (для ленивой загрузки)
-    pub fn load_more_if_needed(&mut self, visible_range: (usize, usize)) -> Result<bool, Box<dyn std::error::Error>> {
+    pub fn load_more_if_needed(&mut self, visible_range: (usize, usize)) -> Result<bool, AppError> {
         if self.load_mode != LoadMode::Lazy {
             return Ok(false);
         }
 
         if let Some(ref mut lazy_loader) = self.lazy_loader {
             if lazy_loader.should_load_more(visible_range) {
-                match lazy_loader.load_next_page(&self.notes_manager) {
+                match lazy_loader.load_next_page(self.store.as_ref()) {
                     Ok(new_notes) => {
                         if !new_notes.is_empty() {
                             self.notes.extend(new_notes);
                             Self::sort_notes(&mut self.notes);
+                            Self::index_notes(&self.notes, &mut self.note_positions);
                             log_info!("load", "page", &lazy_loader.loaded_pages().to_string(), 
                                      &format!("Дозагружено {} заметок", lazy_loader.loaded_pages() * 20));
                             return Ok(true);
@@ -134,7 +176,7 @@ impl AppState {
                     }
                     Err(e) => {
                         log_error!("load", "page", &lazy_loader.loaded_pages().to_string(), &e);
-                        return Err(Box::new(e));
+                        return Err(e);
                     }
                 }
             }
@@ -149,7 +191,7 @@ impl AppState {
     }
 
     /// Переключает режим загрузки
-    pub fn switch_load_mode(&mut self, new_mode: LoadMode) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn switch_load_mode(&mut self, new_mode: LoadMode) -> Result<(), AppError> {
         if self.load_mode == new_mode {
             return Ok(());
         }
@@ -193,9 +235,191 @@ impl AppState {
         }
     }
 
+    /// Метаданные всех заметок для быстрого выбора и поиска по id или заголовку (в ленивом режиме - и незагруженных)
+    pub fn quick_picker_candidates(&self) -> Vec<NoteMetadata> {
+        match self.lazy_loader {
+            Some(ref lazy_loader) => lazy_loader.all_metadata().cloned().collect(),
+            None => self.notes.iter().map(NoteMetadata::from_note).collect(),
+        }
+    }
+    
+    /// Ищет заметки по заголовку и содержимому.
+    /// В ленивом режиме поиск идёт по всем заметкам на диске, а не только по загруженным страницам.
+    pub fn search_notes(&self, query: &str) -> Vec<SearchResult> {
+        let search = NoteSearch::new(query);
+        if search.is_empty() {
+            return Vec::new();
+        }
+        
+        if self.load_mode == LoadMode::Lazy {
+            match search.search_store(self.store.as_ref()) {
+                Ok(results) => return results,
+                Err(e) => log_error!("search", "notes", &e),
+            }
+        }
+        
+        search.search_notes(&self.notes)
+    }
+    
+    /// Подгружает заметку с диска, если ее еще нет в `notes`. Возвращает `false`, если заметки нет
+    pub fn ensure_note_loaded(&mut self, note_id: Uuid) -> bool {
+        if self.note_index(note_id).is_some() {
+            return true;
+        }
+        
+        match self.store.load_note_by_id(note_id) {
+            Ok(Some(note)) => {
+                self.notes.push(note);
+                self.resort_notes();
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                log_error!("load", "note", &note_id.to_string(), &e);
+                false
+            }
+        }
+    }
+    
+    /// Позиция заметки в `notes` (для отрисовки списков)
+    pub fn note_index(&self, note_id: Uuid) -> Option<usize> {
+        match self.note_positions.get(&note_id) {
+            Some(&idx) if self.notes.get(idx).is_some_and(|n| n.id == note_id) => Some(idx),
+            // Список изменили в обход `AppState` - ищем перебором
+            _ => self.notes.iter().position(|n| n.id == note_id),
+        }
+    }
+    
+    /// Загруженная заметка по id
+    pub fn note(&self, note_id: Uuid) -> Option<&Note> {
+        self.note_index(note_id).map(|idx| &self.notes[idx])
+    }
+    
+    /// Заметка по id: загруженная или прочитанная из хранилища (в ленивом режиме загружены не все)
+    fn note_from_store(&self, note_id: Uuid) -> Result<Note, AppError> {
+        match self.note(note_id) {
+            Some(note) => Ok(note.clone()),
+            None => self.store.load_note_by_id(note_id)?.ok_or(AppError::NoteNotFound),
+        }
+    }
+    
+    /// Переносит записанную в хранилище заметку в память: в список, если она загружена, и в ленивый загрузчик
+    fn sync_saved_note(&mut self, note: &Note) {
+        if let Some(index) = self.note_index(note.id) {
+            self.notes[index] = note.clone();
+        }
+        if let Some(ref mut lazy_loader) = self.lazy_loader {
+            lazy_loader.update_note(note);
+        }
+    }
+    
+    /// Переносит в `notes` изменения, сделанные в хранилище другими программами.
+    /// Файл сверяется с заметкой в памяти, поэтому собственные записи приложения ничего не меняют
+    pub fn apply_external_changes(&mut self, changes: &ExternalChanges) -> SyncReport {
+        let mut note_ids: HashSet<Uuid> = changes.note_ids.clone();
+        if changes.rescan {
+            match self.store.get_note_ids() {
+                Ok(ids) => note_ids.extend(ids),
+                Err(e) => log_error!("rescan", "notes", &e),
+            }
+            note_ids.extend(self.notes.iter().map(|n| n.id));
+            if let Some(ref lazy_loader) = self.lazy_loader {
+                note_ids.extend(lazy_loader.all_metadata().map(|m| m.id));
+            }
+        }
+
+        let mut report = SyncReport::default();
+        for note_id in note_ids {
+            let on_disk = match self.store.load_note_by_id(note_id) {
+                Ok(note) => note,
+                Err(e) => {
+                    // Файл могли еще не дописать - после записи придет новое событие
+                    log_error!("reload", "note", &note_id.to_string(), &e);
+                    continue;
+                }
+            };
+            let known = self.lazy_loader.as_ref().is_some_and(|l| l.contains(note_id));
+            match (on_disk, self.note_index(note_id)) {
+                (Some(note), Some(index)) => {
+                    if !backup::notes_equal(&self.notes[index], &note) {
+                        if let Some(ref mut lazy_loader) = self.lazy_loader {
+                            lazy_loader.update_note(&note);
+                        }
+                        self.notes[index] = note;
+                        report.updated.push(note_id);
+                    }
+                }
+                // Не загруженной в ленивом режиме заметке достаточно новых метаданных
+                (Some(note), None) if known => {
+                    if let Some(ref mut lazy_loader) = self.lazy_loader {
+                        lazy_loader.update_note(&note);
+                    }
+                    report.updated.push(note_id);
+                }
+                (Some(note), None) => {
+                    self.notes.push(note);
+                    report.created.push(note_id);
+                }
+                (None, Some(index)) => {
+                    self.notes.remove(index);
+                    self.reindex_notes();
+                    report.deleted.push(note_id);
+                }
+                (None, None) if known => report.deleted.push(note_id),
+                (None, None) => {}
+            }
+        }
+
+        if (!report.created.is_empty() || !report.deleted.is_empty())
+            && let Some(ref mut lazy_loader) = self.lazy_loader
+            && let Err(e) = lazy_loader.initialize_with_store(self.store.as_ref())
+        {
+            log_error!("update", "lazy_loader", &e);
+        }
+        self.resort_notes();
+
+        if changes.groups_changed || changes.rescan {
+            match self.store.load_groups() {
+                Ok(groups) if groups != self.groups => {
+                    self.groups = groups;
+                    report.groups_changed = true;
+                }
+                Ok(_) => {}
+                Err(e) => log_error!("reload", "groups", &e),
+            }
+        }
+
+        if !report.is_empty() {
+            log_info!("reload", "notes", &format!(
+                "Внешние изменения: создано {}, изменено {}, удалено {}{}",
+                report.created.len(),
+                report.updated.len(),
+                report.deleted.len(),
+                if report.groups_changed { ", группы обновлены" } else { "" },
+            ));
+        }
+        report
+    }
+
+    /// Сортирует заметки и пересчитывает их позиции
+    fn resort_notes(&mut self) {
+        Self::sort_notes(&mut self.notes);
+        self.reindex_notes();
+    }
+    
+    /// Пересчитывает позиции заметок после изменения списка
+    fn reindex_notes(&mut self) {
+        Self::index_notes(&self.notes, &mut self.note_positions);
+    }
+    
+    fn index_notes(notes: &[Note], positions: &mut HashMap<Uuid, usize>) {
+        positions.clear();
+        positions.extend(notes.iter().enumerate().map(|(idx, note)| (note.id, idx)));
+    }
+    
     /// Загружает и сортирует заметки
-    fn load_and_sort_notes(notes_manager: &NotesManager) -> Vec<Note> {
-        let mut notes = notes_manager.get_all_notes().unwrap_or_default();
+    fn load_and_sort_notes(store: &dyn NoteStore) -> Vec<Note> {
+        let mut notes = store.get_all_notes().unwrap_or_default();
         Self::sort_notes(&mut notes);
         notes
     }
@@ -206,19 +430,25 @@ impl AppState {
     }
     
     /// Создает новую заметку с валидацией
-    pub fn create_note(&mut self, title: String, content: String, group_id: Option<Uuid>) -> Result<Uuid, Box<dyn std::error::Error>> {
+    pub fn create_note(&mut self, title: String, content: String, group_id: Option<Uuid>) -> Result<Uuid, AppError> {
+        self.create_note_with_tags(title, content, group_id, Vec::new())
+    }
+    
+    /// Создает заметку сразу с тегами: одна операция отмены вместо создания и отдельной правки тегов
+    pub fn create_note_with_tags(&mut self, title: String, content: String, group_id: Option<Uuid>, tags: Vec<String>) -> Result<Uuid, AppError> {
         // Валидация входных данных
-        let validation = ValidationRules::validate_note_creation(&title, &content);
-        if !validation.is_valid {
-            let error_msg = validation.errors.iter()
-                .map(|e| e.to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-                         log_error!("create", "note", &std::io::Error::new(std::io::ErrorKind::InvalidInput, error_msg.clone()));
-            return Err(format!("Ошибки валидации: {}", error_msg).into());
+        let tags = Self::normalize_tags(&tags);
+        let mut validation = ValidationRules::validate_note_creation(&title, &content);
+        for error in ValidationRules::validate_tags(&tags).errors {
+            validation.add_error(error);
+        }
+        if let Err(e) = validation.into_result() {
+            log_error!("create", "note", &e);
+            return Err(e);
         }
         
         let note_id = Uuid::new_v4();
+        let sort_index = self.next_sort_index(group_id);
         let note = Note {
             id: note_id,
             title: title.trim().to_string(),
@@ -227,18 +457,23 @@ impl AppState {
             updated_at: chrono::Utc::now(),
             pinned: false,
             group_id,
+            tags,
+            copy_count: 0,
+            last_used_at: None,
+            sort_index,
         };
         
         log_info!("create", "note", &note_id.to_string(), &format!("Создание заметки '{}'", note.title));
         
-        match self.notes_manager.save_note(&note) {
+        match self.store.save_note(&note) {
             Ok(_) => {
+                self.undo_stack.record(Command::new("Создание заметки", vec![Change::note(None, Some(&note))]));
                 self.notes.push(note);
-                Self::sort_notes(&mut self.notes);
+                self.resort_notes();
                 
                 // Обновляем ленивую загрузку если нужно
                 if let Some(ref mut lazy_loader) = self.lazy_loader {
-                    if let Err(e) = lazy_loader.initialize_with_manager(&self.notes_manager) {
+                    if let Err(e) = lazy_loader.initialize_with_store(self.store.as_ref()) {
                         log_error!("update", "lazy_loader", &e);
                     }
                 }
@@ -248,105 +483,624 @@ impl AppState {
             }
             Err(e) => {
                 log_error!("create", "note", &note_id.to_string(), &e);
-                Err(Box::new(e))
+                Err(e)
             }
         }
     }
     
-    /// Удаляет заметку по индексу
-    pub fn delete_note(&mut self, index: usize) -> Result<(), Box<dyn std::error::Error>> {
-        if index >= self.notes.len() {
-            return Ok(());
+    /// Перемещает заметку в корзину
+    pub fn delete_note(&mut self, note_id: Uuid) -> Result<(), AppError> {
+        let Some(index) = self.note_index(note_id) else {
+            return Err(AppError::NoteNotFound);
+        };
+        
+        // Сначала кладем заметку в корзину, чтобы сбой между шагами не потерял её.
+        // История версий остается до окончательного удаления из корзины.
+        let note = self.notes[index].clone();
+        self.trash.notes.push(TrashedNote { note, deleted_at: chrono::Utc::now() });
+        if let Err(e) = self.store.save_trash(&self.trash) {
+            self.trash.notes.pop();
+            return Err(e);
+        }
+        if let Err(e) = self.store.delete_note(note_id) {
+            // Заметка осталась на месте - убираем ее из корзины, иначе восстановление создаст копию
+            self.trash.notes.pop();
+            if let Err(e) = self.store.save_trash(&self.trash) {
+                log_error!("save", "trash", &e);
+            }
+            return Err(e);
+        }
+        let note = self.notes.remove(index);
+        self.resort_notes();
+        if let Some(ref mut lazy_loader) = self.lazy_loader
+            && let Err(e) = lazy_loader.initialize_with_store(self.store.as_ref())
+        {
+            log_error!("update", "lazy_loader", &e);
         }
         
-        let note_id = self.notes[index].id;
-        self.notes_manager.delete_note(note_id)?;
-        self.notes.remove(index);
-        Self::sort_notes(&mut self.notes);
+        self.undo_stack.record(Command::new("Удаление заметки", vec![
+            Change::note(Some(&note), None),
+            Change::TrashedNote { note_id, before: None, after: self.trash.notes.last().cloned() },
+        ]));
         Ok(())
     }
     
     /// Обновляет заметку
-    pub fn update_note(&mut self, index: usize, title: Option<String>, content: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
-        if index >= self.notes.len() {
-            return Ok(());
-        }
+    pub fn update_note(&mut self, note_id: Uuid, title: Option<String>, content: Option<String>) -> Result<(), AppError> {
+        let Some(index) = self.note_index(note_id) else {
+            return Err(AppError::NoteNotFound);
+        };
         
-        let note = &mut self.notes[index];
+        // Изменения применяются к копии: заметка в памяти меняется только после успешной записи
+        let before = self.notes[index].clone();
+        let mut note = before.clone();
         
         if let Some(new_title) = title {
-            if !new_title.trim().is_empty() {
-                note.title = new_title.trim().to_string();
-            }
+            note.title = new_title.trim().to_string();
         }
         
         if let Some(new_content) = content {
             note.content = new_content;
         }
         
+        if let Err(e) = ValidationRules::validate_note_creation(&note.title, &note.content).into_result() {
+            log_error!("update", "note", &note_id.to_string(), &e);
+            return Err(e);
+        }
+        
         note.updated_at = chrono::Utc::now();
-        self.notes_manager.save_note(note)?;
-        Self::sort_notes(&mut self.notes);
+        self.store.save_note(&note)?;
+        if let Some(ref mut lazy_loader) = self.lazy_loader {
+            lazy_loader.update_note(&note);
+        }
+        
+        // Снимок до изменения попадает в историю версий
+        if note.title != before.title || note.content != before.content {
+            let command = Command::new("Изменение заметки", vec![Change::note(Some(&before), Some(&note))]);
+            self.undo_stack.record(command);
+            self.record_revision(NoteRevision::from_note(&before));
+        }
+        
+        self.notes[index] = note;
+        self.resort_notes();
+        Ok(())
+    }
+    
+    /// Добавляет версию в историю заметки, удаляя самые старые сверх лимита.
+    /// Ошибка записи истории не отменяет сохранение самой заметки.
+    fn record_revision(&self, revision: NoteRevision) {
+        let note_id = revision.note_id;
+        let result = self.store.load_revisions(note_id).and_then(|mut revisions| {
+            revisions.push(revision);
+            let excess = revisions.len().saturating_sub(MAX_REVISIONS_PER_NOTE);
+            revisions.drain(..excess);
+            self.store.save_revisions(note_id, &revisions)
+        });
+        if let Err(e) = result {
+            log_error!("save", "history", &note_id.to_string(), &e);
+        }
+    }
+    
+    /// История версий заметки, от новых к старым
+    pub fn note_revisions(&self, note_id: Uuid) -> Vec<NoteRevision> {
+        match self.store.load_revisions(note_id) {
+            Ok(mut revisions) => {
+                revisions.reverse();
+                revisions
+            }
+            Err(e) => {
+                log_error!("load", "history", &note_id.to_string(), &e);
+                Vec::new()
+            }
+        }
+    }
+    
+    /// Восстанавливает версию заметки; текущее содержимое при этом само становится версией в истории
+    pub fn restore_revision(&mut self, note_id: Uuid, revision_id: Uuid) -> Result<(), AppError> {
+        if self.note_index(note_id).is_none() {
+            return Err(AppError::NoteNotFound);
+        }
+        
+        let revision = self.store.load_revisions(note_id)?
+            .into_iter()
+            .find(|r| r.id == revision_id)
+            .ok_or(AppError::RevisionNotFound(revision_id))?;
+        
+        log_info!("restore", "note", &note_id.to_string(), &format!("Восстановление версии от {}", revision.saved_at));
+        self.update_note(note_id, Some(revision.title), Some(revision.content))
+    }
+    
+    /// Отмечает копирование заметки в буфер обмена или постоянный текст.
+    /// Статистика не меняет дату изменения и не попадает в историю отмены.
+    pub fn record_note_use(&mut self, note_id: Uuid) -> Result<(), AppError> {
+        let Some(index) = self.note_index(note_id) else {
+            return Err(AppError::NoteNotFound);
+        };
+        let note = &mut self.notes[index];
+        note.copy_count = note.copy_count.saturating_add(1);
+        note.last_used_at = Some(chrono::Utc::now());
+        self.store.save_note(note)?;
+        if let Some(ref mut lazy_loader) = self.lazy_loader {
+            lazy_loader.update_note(note);
+        }
         Ok(())
     }
     
+    /// Порядок заметок в группе: собственный или общий из настроек
+    pub fn effective_sort_mode(&self, group_id: Option<Uuid>) -> NoteSortMode {
+        group_id
+            .and_then(|id| self.groups.iter().find(|g| g.id == id))
+            .and_then(|group| group.sort_mode)
+            .unwrap_or(self.sort_mode)
+    }
+    
+    /// Индексы заметок группы (`None` - без группы) в порядке отображения
+    pub fn group_note_indices(&self, group_id: Option<Uuid>) -> Vec<usize> {
+        let mode = self.effective_sort_mode(group_id);
+        let mut indices: Vec<usize> = self.notes.iter().enumerate()
+            .filter(|(_, n)| n.group_id == group_id)
+            .map(|(i, _)| i)
+            .collect();
+        indices.sort_by(|&a, &b| mode.compare(&self.notes[a], &self.notes[b]));
+        indices
+    }
+    
+    /// Задает порядок заметок в группе (`None` - как в настройках)
+    pub fn set_group_sort_mode(&mut self, group_id: Uuid, sort_mode: Option<NoteSortMode>) -> Result<(), AppError> {
+        let Some(group) = self.groups.iter_mut().find(|g| g.id == group_id) else {
+            return Err(AppError::GroupNotFound(group_id));
+        };
+        group.sort_mode = sort_mode;
+        self.store.save_groups(&self.groups)
+    }
+    
     /// Переключает закрепление заметки
-    pub fn toggle_pin(&mut self, index: usize) -> Result<(), Box<dyn std::error::Error>> {
-        if index >= self.notes.len() {
+    pub fn toggle_pin(&mut self, note_id: Uuid) -> Result<(), AppError> {
+        let Some(index) = self.note_index(note_id) else {
+            return Err(AppError::NoteNotFound);
+        };
+        
+        let before = self.notes[index].clone();
+        self.notes[index].pinned = !self.notes[index].pinned;
+        self.store.save_note(&self.notes[index])?;
+        if let Some(ref mut lazy_loader) = self.lazy_loader {
+            lazy_loader.update_note(&self.notes[index]);
+        }
+        self.undo_stack.record(Command::new("Закрепление заметки", vec![Change::note(Some(&before), Some(&self.notes[index]))]));
+        self.resort_notes();
+        Ok(())
+    }
+    
+    /// Перемещает заметку в группу (`None` - без группы)
+    pub fn move_note_to_group(&mut self, note_id: Uuid, group_id: Option<Uuid>) -> Result<(), AppError> {
+        let index = self.note_index(note_id).ok_or(AppError::NoteNotFound)?;
+        if self.notes[index].group_id == group_id {
             return Ok(());
         }
+        if let Some(group_id) = group_id.filter(|id| !self.groups.iter().any(|g| g.id == *id)) {
+            return Err(AppError::GroupNotFound(group_id));
+        }
         
-        self.notes[index].pinned = !self.notes[index].pinned;
-        self.notes_manager.save_note(&self.notes[index])?;
-        Self::sort_notes(&mut self.notes);
+        let before = self.notes[index].clone();
+        self.notes[index].sort_index = self.next_sort_index(group_id);
+        self.notes[index].group_id = group_id;
+        self.store.save_note(&self.notes[index])?;
+        if let Some(ref mut lazy_loader) = self.lazy_loader {
+            lazy_loader.update_note(&self.notes[index]);
+        }
+        self.undo_stack.record(Command::new("Перемещение заметки", vec![Change::note(Some(&before), Some(&self.notes[index]))]));
         Ok(())
     }
     
-
+    /// Перетаскивание заметки: переносит ее в группу `group_id` (`None` - без группы) перед заметкой `before`
+    /// или в конец группы. Ручной порядок группы пересчитывается, вся перестановка отменяется одним действием.
+    pub fn move_note(&mut self, note_id: Uuid, group_id: Option<Uuid>, before: Option<Uuid>) -> Result<(), AppError> {
+        if before == Some(note_id) {
+            return Ok(());
+        }
+        if !self.notes.iter().any(|n| n.id == note_id) {
+            return Err(AppError::NoteNotFound);
+        }
+        if let Some(group_id) = group_id.filter(|id| !self.groups.iter().any(|g| g.id == *id)) {
+            return Err(AppError::GroupNotFound(group_id));
+        }
+        
+        // Новый ручной порядок группы
+        let mut group_notes: Vec<&Note> = self.notes.iter()
+            .filter(|n| n.group_id == group_id && n.id != note_id)
+            .collect();
+        group_notes.sort_by(|a, b| NoteSortMode::Manual.compare(a, b));
+        let mut order: Vec<Uuid> = group_notes.into_iter().map(|n| n.id).collect();
+        let position = before
+            .and_then(|id| order.iter().position(|&n| n == id))
+            .unwrap_or(order.len());
+        order.insert(position, note_id);
+        
+        let mut changes = Vec::new();
+        for (sort_index, id) in order.into_iter().enumerate() {
+            let Some(note) = self.notes.iter_mut().find(|n| n.id == id) else {
+                continue;
+            };
+            if note.sort_index == sort_index as u32 && note.group_id == group_id {
+                continue;
+            }
+            let before_change = note.clone();
+            note.sort_index = sort_index as u32;
+            note.group_id = group_id;
+            self.store.save_note(note)?;
+            if let Some(ref mut lazy_loader) = self.lazy_loader {
+                lazy_loader.update_note(note);
+            }
+            changes.push(Change::note(Some(&before_change), Some(note)));
+        }
+        
+        if !changes.is_empty() {
+            self.undo_stack.record(Command::new("Перемещение заметки", changes));
+        }
+        Ok(())
+    }
+    
+    /// Переносит выбранные заметки в группу (`None` - без группы) в конец ее ручного порядка
+    pub fn move_notes_to_group(&mut self, note_ids: &[Uuid], group_id: Option<Uuid>) -> Result<usize, AppError> {
+        if let Some(group_id) = group_id.filter(|id| !self.groups.iter().any(|g| g.id == *id)) {
+            return Err(AppError::GroupNotFound(group_id));
+        }
+        let mut sort_index = self.next_sort_index(group_id);
+        self.update_notes(note_ids, "Перемещение заметок", |note| {
+            if note.group_id == group_id {
+                return false;
+            }
+            note.group_id = group_id;
+            note.sort_index = sort_index;
+            sort_index += 1;
+            true
+        })
+    }
+    
+    /// Закрепляет или открепляет выбранные заметки
+    pub fn set_notes_pinned(&mut self, note_ids: &[Uuid], pinned: bool) -> Result<usize, AppError> {
+        let label = if pinned { "Закрепление заметок" } else { "Открепление заметок" };
+        let result = self.update_notes(note_ids, label, |note| {
+            let changed = note.pinned != pinned;
+            note.pinned = pinned;
+            changed
+        });
+        self.resort_notes();
+        result
+    }
+    
+    /// Добавляет тег выбранным заметкам (заметки с максимумом тегов пропускаются)
+    pub fn add_tag_to_notes(&mut self, note_ids: &[Uuid], tag: &str) -> Result<usize, AppError> {
+        let tag = ValidationRules::normalize_tag(tag);
+        let validation = ValidationRules::validate_tag(&tag);
+        if let Err(e) = validation.into_result() {
+            log_error!("update", "tags", &e);
+            return Err(e);
+        }
+        
+        self.update_notes(note_ids, "Добавление тега", |note| {
+            if note.tags.contains(&tag) || note.tags.len() >= ValidationRules::MAX_TAGS_PER_NOTE {
+                return false;
+            }
+            note.tags.push(tag.clone());
+            note.updated_at = chrono::Utc::now();
+            true
+        })
+    }
+    
+    /// Убирает тег у выбранных заметок
+    pub fn remove_tag_from_notes(&mut self, note_ids: &[Uuid], tag: &str) -> Result<usize, AppError> {
+        let tag = ValidationRules::normalize_tag(tag);
+        self.update_notes(note_ids, "Удаление тега", |note| {
+            let count = note.tags.len();
+            note.tags.retain(|t| *t != tag);
+            if note.tags.len() == count {
+                return false;
+            }
+            note.updated_at = chrono::Utc::now();
+            true
+        })
+    }
+    
+    /// Перемещает выбранные заметки в корзину одной операцией
+    pub fn delete_notes(&mut self, note_ids: &[Uuid]) -> Result<usize, AppError> {
+        let notes: Vec<Note> = self.notes.iter()
+            .filter(|n| note_ids.contains(&n.id))
+            .cloned()
+            .collect();
+        if notes.is_empty() {
+            return Ok(0);
+        }
+        
+        // Как и при удалении одной заметки: сначала корзина, затем хранилище
+        let deleted_at = chrono::Utc::now();
+        let trash_len = self.trash.notes.len();
+        self.trash.notes.extend(notes.iter().map(|note| TrashedNote { note: note.clone(), deleted_at }));
+        if let Err(e) = self.store.save_trash(&self.trash) {
+            self.trash.notes.truncate(trash_len);
+            return Err(e);
+        }
+        
+        let mut changes = Vec::new();
+        let mut deleted = Vec::new();
+        let mut failure = None;
+        for (note, trashed) in notes.iter().zip(self.trash.notes[trash_len..].iter()) {
+            if let Err(e) = self.store.delete_note(note.id) {
+                failure = Some(e);
+                break;
+            }
+            deleted.push(note.id);
+            changes.push(Change::note(Some(note), None));
+            changes.push(Change::TrashedNote { note_id: note.id, before: None, after: Some(trashed.clone()) });
+        }
+        // Заметки, которые не удалось удалить, остаются на месте - в корзине их быть не должно
+        if failure.is_some() {
+            self.trash.notes.truncate(trash_len + deleted.len());
+            if let Err(e) = self.store.save_trash(&self.trash) {
+                log_error!("save", "trash", &e);
+            }
+        }
+        
+        self.notes.retain(|n| !deleted.contains(&n.id));
+        self.reindex_notes();
+        if let Some(ref mut lazy_loader) = self.lazy_loader
+            && let Err(e) = lazy_loader.initialize_with_store(self.store.as_ref())
+        {
+            log_error!("update", "lazy_loader", &e);
+        }
+        
+        if !changes.is_empty() {
+            self.undo_stack.record(Command::new("Удаление заметок", changes));
+        }
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(deleted.len()),
+        }
+    }
+    
+    /// Тексты выбранных заметок в указанном порядке, разделенные пустой строкой
+    pub fn concat_notes(&self, note_ids: &[Uuid]) -> String {
+        note_ids.iter()
+            .filter_map(|id| self.notes.iter().find(|n| n.id == *id))
+            .map(|note| note.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+    
+    /// Применяет изменение к заметкам с указанными id и записывает все изменения одной операцией отмены.
+    /// `update` возвращает `false`, если заметка не изменилась. Возвращает количество изменённых заметок.
+    /// При сбое записи уже сохраненные изменения тоже записываются в отмену, а несохраненное откатывается.
+    fn update_notes(&mut self, note_ids: &[Uuid], label: &str, mut update: impl FnMut(&mut Note) -> bool) -> Result<usize, AppError> {
+        let mut changes = Vec::new();
+        let mut failure = None;
+        for note in self.notes.iter_mut().filter(|n| note_ids.contains(&n.id)) {
+            let before = note.clone();
+            if !update(note) {
+                continue;
+            }
+            if let Err(e) = self.store.save_note(note) {
+                *note = before;
+                failure = Some(e);
+                break;
+            }
+            if let Some(ref mut lazy_loader) = self.lazy_loader {
+                lazy_loader.update_note(note);
+            }
+            changes.push(Change::note(Some(&before), Some(note)));
+        }
+        
+        let updated = changes.len();
+        if updated > 0 {
+            self.undo_stack.record(Command::new(label, changes));
+        }
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(updated),
+        }
+    }
+    
+    /// Позиция в конце ручного порядка группы
+    fn next_sort_index(&self, group_id: Option<Uuid>) -> u32 {
+        self.notes.iter()
+            .filter(|n| n.group_id == group_id)
+            .map(|n| n.sort_index + 1)
+            .max()
+            .unwrap_or(0)
+    }
     
     /// Получает содержимое заметки для копирования
-    pub fn get_note_content(&self, index: usize) -> Option<String> {
-        self.notes.get(index).map(|note| note.content.clone())
+    pub fn get_note_content(&self, note_id: Uuid) -> Option<String> {
+        self.note(note_id).map(|note| note.content.clone())
+    }
+    
+    /// Добавляет текст к постоянному тексту
+    pub fn append_to_persistent(&mut self, text: &str) -> Result<(), AppError> {
+        self.persistent_text.push_str(text);
+        self.save_persistent_text()
     }
     
     /// Добавляет содержимое заметки к постоянному тексту
-    pub fn append_note_to_persistent(&mut self, index: usize) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(note) = self.notes.get(index) {
-            self.persistent_text.push_str(&note.content);
-            self.save_persistent_text()?;
+    pub fn append_note_to_persistent(&mut self, note_id: Uuid) -> Result<(), AppError> {
+        if let Some(content) = self.get_note_content(note_id) {
+            self.append_to_persistent(&content)?;
         }
         Ok(())
     }
     
     /// Сохраняет постоянный текст
-    pub fn save_persistent_text(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let persistent_file = self.notes_manager.get_base_dir().join("persistent_text.txt");
-        std::fs::write(persistent_file, &self.persistent_text)?;
+    pub fn save_persistent_text(&self) -> Result<(), AppError> {
+        self.store.save_persistent_text(&self.persistent_text)?;
         Ok(())
     }
     
-    /// Загружает постоянный текст
-    fn load_persistent_text(notes_manager: &NotesManager) -> Result<String, Box<dyn std::error::Error>> {
-        let persistent_file = notes_manager.get_base_dir().join("persistent_text.txt");
+    /// Список всех тегов с количеством заметок, отсортированный по имени.
+    /// В ленивом режиме учитываются метаданные всех заметок, а не только загруженных.
+    pub fn all_tags(&self) -> Vec<(String, usize)> {
+        let mut counts: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
         
-        if persistent_file.exists() {
-            Ok(std::fs::read_to_string(persistent_file)?)
+        if let Some(ref lazy_loader) = self.lazy_loader {
+            for meta in lazy_loader.all_metadata() {
+                for tag in &meta.tags {
+                    *counts.entry(tag.clone()).or_insert(0) += 1;
+                }
+            }
         } else {
-            Ok(String::new())
+            for note in &self.notes {
+                for tag in &note.tags {
+                    *counts.entry(tag.clone()).or_insert(0) += 1;
+                }
+            }
+        }
+        
+        counts.into_iter().collect()
+    }
+    
+    /// Нормализует, валидирует и сохраняет теги заметки
+    pub fn set_note_tags(&mut self, note_id: Uuid, tags: Vec<String>) -> Result<(), AppError> {
+        let Some(index) = self.note_index(note_id) else {
+            return Err(AppError::NoteNotFound);
+        };
+        
+        let normalized = Self::normalize_tags(&tags);
+        let validation = ValidationRules::validate_tags(&normalized);
+        if let Err(e) = validation.into_result() {
+            log_error!("update", "tags", &note_id.to_string(), &e);
+            return Err(e);
+        }
+        
+        let before = self.notes[index].clone();
+        let note = &mut self.notes[index];
+        note.tags = normalized;
+        note.updated_at = chrono::Utc::now();
+        self.store.save_note(note)?;
+        self.undo_stack.record(Command::new("Изменение тегов", vec![Change::note(Some(&before), Some(note))]));
+        
+        if let Some(ref mut lazy_loader) = self.lazy_loader {
+            lazy_loader.update_note(&self.notes[index]);
+        }
+        Ok(())
+    }
+    
+    /// Нормализованные теги без повторов, в исходном порядке
+    fn normalize_tags(tags: &[String]) -> Vec<String> {
+        let mut normalized: Vec<String> = Vec::new();
+        for tag in tags.iter().map(|t| ValidationRules::normalize_tag(t)) {
+            if !normalized.contains(&tag) {
+                normalized.push(tag);
+            }
+        }
+        normalized
+    }
+    
+    /// Переименовывает тег во всех заметках. Если новый тег уже существует, теги объединяются.
+    /// Возвращает количество изменённых заметок.
+    pub fn rename_tag(&mut self, old_tag: &str, new_tag: &str) -> Result<usize, AppError> {
+        self.merge_tags(&[old_tag.to_string()], new_tag)
+    }
+    
+    /// Объединяет несколько тегов в один во всех заметках.
+    /// Возвращает количество изменённых заметок.
+    pub fn merge_tags(&mut self, source_tags: &[String], target_tag: &str) -> Result<usize, AppError> {
+        let target = ValidationRules::normalize_tag(target_tag);
+        let validation = ValidationRules::validate_tag(&target);
+        if let Err(e) = validation.into_result() {
+            log_error!("merge", "tags", &e);
+            return Err(e);
+        }
+        
+        let sources: Vec<String> = source_tags.iter()
+            .map(|t| ValidationRules::normalize_tag(t))
+            .filter(|t| *t != target)
+            .collect();
+        if sources.is_empty() {
+            return Ok(0);
+        }
+        
+        log_info!("merge", "tags", &target, &format!("Объединение тегов {:?} в '{}'", sources, target));
+        
+        let changes = self.update_tags_everywhere(|tags| {
+            if !tags.iter().any(|t| sources.contains(t)) {
+                return false;
+            }
+            let mut merged: Vec<String> = Vec::with_capacity(tags.len());
+            for tag in tags.drain(..) {
+                let tag = if sources.contains(&tag) { target.clone() } else { tag };
+                if !merged.contains(&tag) {
+                    merged.push(tag);
+                }
+            }
+            *tags = merged;
+            true
+        })?;
+        let updated = changes.len();
+        self.undo_stack.record(Command::new("Объединение тегов", changes));
+        
+        log_success!("merge", "tags", &target);
+        Ok(updated)
+    }
+    
+    /// Применяет изменение тегов ко всем заметкам, включая не загруженные в ленивом режиме.
+    /// Возвращает изменения для стека отмены.
+    fn update_tags_everywhere(&mut self, update: impl Fn(&mut Vec<String>) -> bool) -> Result<Vec<Change>, AppError> {
+        let mut changes = Vec::new();
+        
+        // Заметки в памяти
+        for note in &mut self.notes {
+            let before = note.clone();
+            if update(&mut note.tags) {
+                self.store.save_note(note)?;
+                if let Some(ref mut lazy_loader) = self.lazy_loader {
+                    lazy_loader.update_note(note);
+                }
+                changes.push(Change::note(Some(&before), Some(note)));
+            }
+        }
+        
+        // Заметки, которые есть только на диске
+        if self.lazy_loader.is_some() {
+            for note_id in self.store.get_note_ids()? {
+                if self.notes.iter().any(|n| n.id == note_id) {
+                    continue;
+                }
+                let Some(mut note) = self.store.load_note_by_id(note_id)? else {
+                    continue;
+                };
+                let before = note.clone();
+                if update(&mut note.tags) {
+                    self.store.save_note(&note)?;
+                    if let Some(ref mut lazy_loader) = self.lazy_loader {
+                        lazy_loader.update_note(&note);
+                    }
+                    changes.push(Change::note(Some(&before), Some(&note)));
+                }
+            }
+        }
+        
+        Ok(changes)
+    }
+    
+    /// Подгружает все заметки с указанным тегом (для фильтрации в ленивом режиме)
+    pub fn load_notes_with_tag(&mut self, tag: &str) {
+        let note_ids: Vec<Uuid> = match self.lazy_loader {
+            Some(ref lazy_loader) => lazy_loader.all_metadata()
+                .filter(|meta| meta.tags.iter().any(|t| t == tag))
+                .map(|meta| meta.id)
+                .collect(),
+            None => return,
+        };
+        
+        for note_id in note_ids {
+            self.ensure_note_loaded(note_id);
         }
     }
     
     /// Создает группу с валидацией
-    pub fn create_group(&mut self, name: String, parent_id: Option<Uuid>, selected_notes: Vec<Uuid>) -> Result<Uuid, Box<dyn std::error::Error>> {
+    pub fn create_group(&mut self, name: String, parent_id: Option<Uuid>, selected_notes: Vec<Uuid>) -> Result<Uuid, AppError> {
         // Валидация входных данных
         let validation = ValidationRules::validate_group_creation(&name, parent_id, &self.groups);
-        if !validation.is_valid {
-            let error_msg = validation.errors.iter()
-                .map(|e| e.to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-                         log_error!("create", "group", &std::io::Error::new(std::io::ErrorKind::InvalidInput, error_msg.clone()));
-            return Err(format!("Ошибки валидации: {}", error_msg).into());
+        if let Err(e) = validation.into_result() {
+            log_error!("create", "group", &e);
+            return Err(e);
         }
         
         let (level, parent_id) = if let Some(parent_id) = parent_id {
@@ -366,27 +1120,33 @@ impl AppState {
             collapsed: false,
             parent_id,
             level,
+            sort_mode: None,
         };
         
         log_info!("create", "group", &group_id.to_string(), &format!("Создание группы '{}'", group.name));
         
+        let groups_before = self.groups.clone();
         self.groups.push(group);
+        let mut changes = vec![Change::Groups { before: groups_before, after: self.groups.clone() }];
         
         // Добавляем выбранные заметки в группу
         let mut updated_notes = 0;
         for note in &mut self.notes {
             if selected_notes.contains(&note.id) {
+                let before = note.clone();
                 note.group_id = Some(group_id);
-                if let Err(e) = self.notes_manager.save_note(note) {
+                if let Err(e) = self.store.save_note(note) {
                     log_error!("update", "note", &note.id.to_string(), &e);
                 } else {
+                    changes.push(Change::note(Some(&before), Some(note)));
                     updated_notes += 1;
                 }
             }
         }
         
-        match self.notes_manager.save_groups(&self.groups) {
+        match self.store.save_groups(&self.groups) {
             Ok(_) => {
+                self.undo_stack.record(Command::new("Создание группы", changes));
                 log_success!("create", "group", &group_id.to_string());
                 if updated_notes > 0 {
                     log_info!("update", "group", &group_id.to_string(), &format!("Добавлено {} заметок в группу", updated_notes));
@@ -395,86 +1155,292 @@ impl AppState {
             }
             Err(e) => {
                 log_error!("create", "group", &group_id.to_string(), &e);
-                Err(Box::new(e))
+                Err(e)
             }
         }
     }
     
     /// Переключает состояние сворачивания группы
-    pub fn toggle_group_collapsed(&mut self, group_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(group) = self.groups.iter_mut().find(|g| g.id == group_id) {
-            group.collapsed = !group.collapsed;
-            self.notes_manager.save_groups(&self.groups)?;
-        }
-        Ok(())
+    pub fn toggle_group_collapsed(&mut self, group_id: Uuid) -> Result<(), AppError> {
+        let Some(group) = self.groups.iter_mut().find(|g| g.id == group_id) else {
+            return Err(AppError::GroupNotFound(group_id));
+        };
+        group.collapsed = !group.collapsed;
+        self.store.save_groups(&self.groups)
     }
     
-    /// Удаляет группу и перемещает её содержимое в родительскую группу
-    pub fn delete_group(&mut self, group_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(group_index) = self.groups.iter().position(|g| g.id == group_id) {
-            let group = self.groups[group_index].clone();
-            
-            // Перемещаем все заметки из удаляемой группы в родительскую
-            for note in &mut self.notes {
-                if note.group_id == Some(group_id) {
-                    note.group_id = group.parent_id;
-                    let _ = self.notes_manager.save_note(note);
-                }
+    /// Удаляет группу в корзину и перемещает её содержимое в родительскую группу
+    pub fn delete_group(&mut self, group_id: Uuid) -> Result<(), AppError> {
+        let Some(group_index) = self.groups.iter().position(|g| g.id == group_id) else {
+            return Err(AppError::GroupNotFound(group_id));
+        };
+        let group = self.groups[group_index].clone();
+        let groups_before = self.groups.clone();
+        
+        // Все заметки из удаляемой группы переезжают в родительскую. Ищем их по хранилищу:
+        // в ленивом режиме загружены не все
+        let mut moved = Vec::new();
+        for meta in self.store.get_notes_metadata()? {
+            if meta.group_id == Some(group_id) {
+                let before = self.note_from_store(meta.id)?;
+                let mut after = before.clone();
+                after.group_id = group.parent_id;
+                moved.push((before, after));
             }
-            
-            // Перемещаем все подгруппы в родительскую группу
-            let parent_level = if let Some(parent_id) = group.parent_id {
-                self.groups.iter()
-                    .find(|g| g.id == parent_id)
-                    .map(|g| g.level)
-                    .unwrap_or(0)
-            } else {
-                0
-            };
-            
-            for subgroup in &mut self.groups {
-                if subgroup.parent_id == Some(group_id) {
-                    subgroup.parent_id = group.parent_id;
-                    subgroup.level = if group.parent_id.is_some() {
-                        parent_level + 1
-                    } else {
-                        0
-                    };
+        }
+        let note_ids = moved.iter().map(|(note, _)| note.id).collect();
+        
+        // Перемещаем все подгруппы в родительскую группу
+        let parent_level = if let Some(parent_id) = group.parent_id {
+            self.groups.iter()
+                .find(|g| g.id == parent_id)
+                .map(|g| g.level)
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        
+        let mut groups = self.groups.clone();
+        let mut subgroup_ids = Vec::new();
+        for subgroup in &mut groups {
+            if subgroup.parent_id == Some(group_id) {
+                subgroup.parent_id = group.parent_id;
+                subgroup.level = if group.parent_id.is_some() {
+                    parent_level + 1
+                } else {
+                    0
+                };
+                subgroup_ids.push(subgroup.id);
+            }
+        }
+        groups.remove(group_index);
+        
+        // Запоминаем иерархию, чтобы восстановление вернуло содержимое на место.
+        // Как и при удалении заметки: сначала корзина, затем заметки и группы
+        let trashed = TrashedGroup { group, deleted_at: chrono::Utc::now(), note_ids, subgroup_ids };
+        self.trash.groups.push(trashed.clone());
+        let mut saved = 0;
+        let result = self.store.save_trash(&self.trash).and_then(|()| {
+            for (_, note) in &moved {
+                self.store.save_note(note)?;
+                saved += 1;
+            }
+            self.store.save_groups(&groups)
+        });
+        if let Err(e) = result {
+            // Возвращаем хранилище к состоянию до удаления
+            for (note, _) in &moved[..saved] {
+                if let Err(e) = self.store.save_note(note) {
+                    log_error!("save", "note", &note.id.to_string(), &e);
                 }
             }
-            
-            // Удаляем группу
-            self.groups.remove(group_index);
-            self.notes_manager.save_groups(&self.groups)?;
+            self.trash.groups.pop();
+            if let Err(e) = self.store.save_trash(&self.trash) {
+                log_error!("save", "trash", &group_id.to_string(), &e);
+            }
+            return Err(e);
+        }
+        
+        self.groups = groups;
+        let mut note_changes = Vec::new();
+        for (before, after) in &moved {
+            self.sync_saved_note(after);
+            note_changes.push(Change::note(Some(before), Some(after)));
         }
+        
+        // Отмена возвращает дерево групп и заметки в точности в прежнее состояние
+        let mut changes = vec![Change::Groups { before: groups_before, after: self.groups.clone() }];
+        changes.extend(note_changes);
+        changes.push(Change::TrashedGroup { group_id, before: None, after: Some(trashed) });
+        self.undo_stack.record(Command::new("Удаление группы", changes));
+        Ok(())
+    }
+
+    /// Восстанавливает заметку из корзины. Если её группа удалена, заметка попадает в корень
+    pub fn restore_trashed_note(&mut self, note_id: Uuid) -> Result<(), AppError> {
+        let Some(position) = self.trash.notes.iter().position(|item| item.note.id == note_id) else {
+            return Err(AppError::NoteNotFound);
+        };
+
+        let mut note = self.trash.notes[position].note.clone();
+        if note.group_id.is_some_and(|group_id| !self.groups.iter().any(|g| g.id == group_id)) {
+            note.group_id = None;
+        }
+        self.store.save_note(&note)?;
+
+        let trashed = self.trash.notes.remove(position);
+        self.undo_stack.record(Command::new("Восстановление заметки", vec![
+            Change::TrashedNote { note_id, before: Some(trashed), after: None },
+            Change::note(None, Some(&note)),
+        ]));
+        if let Err(e) = self.store.save_trash(&self.trash) {
+            log_error!("save", "trash", &note_id.to_string(), &e);
+        }
+        if !self.notes.iter().any(|n| n.id == note_id) {
+            self.notes.push(note);
+        }
+        self.resort_notes();
+        log_success!("restore", "note", &note_id.to_string());
         Ok(())
     }
+
+    /// Восстанавливает группу из корзины и возвращает в неё заметки и подгруппы,
+    /// которые с момента удаления не переносились в другое место
+    pub fn restore_trashed_group(&mut self, group_id: Uuid) -> Result<(), AppError> {
+        let Some(position) = self.trash.groups.iter().position(|item| item.group.id == group_id) else {
+            return Err(AppError::GroupNotFound(group_id));
+        };
+
+        let trashed = self.trash.groups[position].clone();
+        let groups_before = self.groups.clone();
+        let mut note_changes = Vec::new();
+        let original_parent_id = trashed.group.parent_id;
+        let mut group = trashed.group.clone();
+
+        // Родитель мог быть удален - тогда группа становится корневой
+        let parent_level = group.parent_id
+            .and_then(|parent_id| self.groups.iter().find(|g| g.id == parent_id))
+            .map(|parent| parent.level);
+        match parent_level {
+            Some(level) => group.level = level + 1,
+            None => {
+                group.parent_id = None;
+                group.level = 0;
+            }
+        }
+        let level = group.level;
+        let new_parent_id = group.parent_id;
+        
+        // Заметки ищем и среди незагруженных (ленивый режим); удаленные после группы пропускаем
+        let mut notes = Vec::new();
+        for &note_id in &trashed.note_ids {
+            match self.note_from_store(note_id) {
+                Ok(note) if note.group_id == original_parent_id || note.group_id == new_parent_id => notes.push(note),
+                Ok(_) | Err(AppError::NoteNotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.groups.push(group);
+
+        for before in notes {
+            let mut note = before.clone();
+            note.group_id = Some(group_id);
+            if let Err(e) = self.store.save_note(&note) {
+                log_error!("restore", "note", &note.id.to_string(), &e);
+            }
+            self.sync_saved_note(&note);
+            note_changes.push(Change::note(Some(&before), Some(&note)));
+        }
+        for subgroup in &mut self.groups {
+            if trashed.subgroup_ids.contains(&subgroup.id)
+                && (subgroup.parent_id == original_parent_id || subgroup.parent_id == new_parent_id) {
+                subgroup.parent_id = Some(group_id);
+            }
+        }
+        self.update_subgroup_levels(group_id, level);
+        self.store.save_groups(&self.groups)?;
+
+        self.trash.groups.remove(position);
+        if let Err(e) = self.store.save_trash(&self.trash) {
+            log_error!("save", "trash", &group_id.to_string(), &e);
+        }
+        
+        let mut changes = vec![
+            Change::TrashedGroup { group_id, before: Some(trashed), after: None },
+            Change::Groups { before: groups_before, after: self.groups.clone() },
+        ];
+        changes.extend(note_changes);
+        self.undo_stack.record(Command::new("Восстановление группы", changes));
+        log_success!("restore", "group", &group_id.to_string());
+        Ok(())
+    }
+
+    /// Окончательно удаляет заметку из корзины вместе с историей версий
+    pub fn purge_trashed_note(&mut self, note_id: Uuid) -> Result<(), AppError> {
+        let Some(position) = self.trash.notes.iter().position(|item| item.note.id == note_id) else {
+            return Err(AppError::NoteNotFound);
+        };
+        self.trash.notes.remove(position);
+        self.store.save_trash(&self.trash)?;
+        if let Err(e) = self.store.delete_revisions(note_id) {
+            log_error!("delete", "history", &note_id.to_string(), &e);
+        }
+        Ok(())
+    }
+
+    /// Окончательно удаляет группу из корзины
+    pub fn purge_trashed_group(&mut self, group_id: Uuid) -> Result<(), AppError> {
+        let Some(position) = self.trash.groups.iter().position(|item| item.group.id == group_id) else {
+            return Err(AppError::GroupNotFound(group_id));
+        };
+        self.trash.groups.remove(position);
+        self.store.save_trash(&self.trash)?;
+        Ok(())
+    }
+
+    /// Очищает корзину полностью
+    pub fn empty_trash(&mut self) -> Result<(), AppError> {
+        let trash = std::mem::take(&mut self.trash);
+        if let Err(e) = self.store.save_trash(&self.trash) {
+            self.trash = trash;
+            return Err(e);
+        }
+        for item in &trash.notes {
+            if let Err(e) = self.store.delete_revisions(item.note.id) {
+                log_error!("delete", "history", &item.note.id.to_string(), &e);
+            }
+        }
+        log_info!("purge", "trash", &format!("Корзина очищена: {} элементов", trash.len()));
+        Ok(())
+    }
+
+    /// Удаляет из корзины элементы старше `retention_days` дней (0 - хранить бессрочно).
+    /// Возвращает количество удаленных элементов
+    pub fn purge_expired_trash(&mut self, retention_days: u32) -> Result<usize, AppError> {
+        if retention_days == 0 {
+            return Ok(0);
+        }
+
+        let (expired_notes, expired_groups) = self.trash.take_expired(retention_days, chrono::Utc::now());
+        let purged = expired_notes.len() + expired_groups.len();
+        if purged == 0 {
+            return Ok(0);
+        }
+
+        self.store.save_trash(&self.trash)?;
+        for item in &expired_notes {
+            if let Err(e) = self.store.delete_revisions(item.note.id) {
+                log_error!("delete", "history", &item.note.id.to_string(), &e);
+            }
+        }
+        log_info!("purge", "trash", &format!("Удалено из корзины по сроку хранения: {}", purged));
+        Ok(purged)
+    }
     
     /// Обновляет группу с валидацией
-    pub fn update_group(&mut self, group_id: Uuid, name: String, parent_id: Option<Uuid>) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn update_group(&mut self, group_id: Uuid, name: String, parent_id: Option<Uuid>) -> Result<(), AppError> {
+        let Some(index) = self.groups.iter().position(|g| g.id == group_id) else {
+            let error = AppError::GroupNotFound(group_id);
+            log_error!("update", "group", &group_id.to_string(), &error);
+            return Err(error);
+        };
+        
         // Валидация изменения родительской группы
         let parent_validation = ValidationRules::validate_group_parent_change(group_id, parent_id, &self.groups);
-        if !parent_validation.is_valid {
-            let error_msg = parent_validation.errors.iter()
-                .map(|e| e.to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-                         log_error!("update", "group", &group_id.to_string(), &std::io::Error::new(std::io::ErrorKind::InvalidInput, error_msg.clone()));
-            return Err(format!("Ошибки валидации родителя: {}", error_msg).into());
+        if let Err(e) = parent_validation.into_result() {
+            log_error!("update", "group", &group_id.to_string(), &e);
+            return Err(e);
         }
         
         // Валидация названия группы
         let name_validation = ValidationRules::validate_group_creation(&name, parent_id, &self.groups);
-        if !name_validation.is_valid {
-            let error_msg = name_validation.errors.iter()
-                .filter(|e| matches!(e, crate::validation::ValidationError::EmptyGroupName | crate::validation::ValidationError::GroupNameTooLong(_)))
-                .map(|e| e.to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-                         if !error_msg.is_empty() {
-                 log_error!("update", "group", &group_id.to_string(), &std::io::Error::new(std::io::ErrorKind::InvalidInput, error_msg.clone()));
-                 return Err(format!("Ошибки валидации названия: {}", error_msg).into());
-             }
+        let name_errors: Vec<_> = name_validation.errors.into_iter()
+            .filter(|e| matches!(e, crate::validation::ValidationError::EmptyGroupName | crate::validation::ValidationError::GroupNameTooLong(_)))
+            .collect();
+        if !name_errors.is_empty() {
+            let error = AppError::Validation(name_errors);
+            log_error!("update", "group", &group_id.to_string(), &error);
+            return Err(error);
         }
         
         // Находим новый уровень родителя
@@ -490,25 +1456,21 @@ impl AppState {
         log_info!("update", "group", &group_id.to_string(), &format!("Обновление группы: '{}'", name));
         
         // Обновляем группу
+        let groups_before = self.groups.clone();
         let (old_name, old_parent_id) = {
-            if let Some(group) = self.groups.iter_mut().find(|g| g.id == group_id) {
-                let old_name = group.name.clone();
-                let old_parent_id = group.parent_id;
-                
-                group.name = name.trim().to_string();
-                group.parent_id = parent_id;
-                
-                // Если изменился родитель, обновляем уровень
-                if old_parent_id != parent_id {
-                    group.level = new_level;
-                }
-                
-                (old_name, old_parent_id)
-            } else {
-                let error = std::io::Error::new(std::io::ErrorKind::NotFound, "Группа не найдена");
-                log_error!("update", "group", &group_id.to_string(), &error);
-                return Err(error.into());
+            let group = &mut self.groups[index];
+            let old_name = group.name.clone();
+            let old_parent_id = group.parent_id;
+            
+            group.name = name.trim().to_string();
+            group.parent_id = parent_id;
+            
+            // Если изменился родитель, обновляем уровень
+            if old_parent_id != parent_id {
+                group.level = new_level;
             }
+            
+            (old_name, old_parent_id)
         };
         
         // Обновляем подгруппы после освобождения заимствования
@@ -521,22 +1483,274 @@ impl AppState {
             log_info!("update", "group", &group_id.to_string(), &format!("Название изменено с '{}' на '{}'", old_name, name.trim()));
         }
         
-        if false {
-            let error = std::io::Error::new(std::io::ErrorKind::NotFound, "Группа не найдена");
-            log_error!("update", "group", &group_id.to_string(), &error);
-            return Err(error.into());
-        }
-        
-        match self.notes_manager.save_groups(&self.groups) {
+        match self.store.save_groups(&self.groups) {
             Ok(_) => {
+                self.undo_stack.record(Command::new("Изменение группы", vec![
+                    Change::Groups { before: groups_before, after: self.groups.clone() },
+                ]));
                 log_success!("update", "group", &group_id.to_string());
                 Ok(())
             }
             Err(e) => {
                 log_error!("update", "group", &group_id.to_string(), &e);
-                Err(Box::new(e))
+                Err(e)
+            }
+        }
+    }
+    
+    /// Перетаскивание группы: делает ее подгруппой `parent_id` (`None` - корневой группой)
+    pub fn move_group(&mut self, group_id: Uuid, parent_id: Option<Uuid>) -> Result<(), AppError> {
+        let Some(group) = self.groups.iter().find(|g| g.id == group_id) else {
+            return Err(AppError::GroupNotFound(group_id));
+        };
+        if group.parent_id == parent_id {
+            return Ok(());
+        }
+        
+        let mut validation = ValidationRules::validate_group_parent_change(group_id, parent_id, &self.groups);
+        let new_level = parent_id
+            .and_then(|id| self.groups.iter().find(|g| g.id == id))
+            .map_or(0, |parent| parent.level + 1);
+        // Вместе с группой переезжают ее подгруппы - самая глубокая не должна выйти за предел вложенности
+        if validation.is_valid && new_level + self.subtree_depth(group_id) > ValidationRules::MAX_GROUP_DEPTH {
+            validation.add_error(crate::validation::ValidationError::InvalidGroupHierarchy);
+        }
+        if let Err(e) = validation.into_result() {
+            log_error!("move", "group", &group_id.to_string(), &e);
+            return Err(e);
+        }
+        
+        let groups_before = self.groups.clone();
+        if let Some(group) = self.groups.iter_mut().find(|g| g.id == group_id) {
+            group.parent_id = parent_id;
+            group.level = new_level;
+        }
+        self.update_subgroup_levels(group_id, new_level);
+        self.store.save_groups(&self.groups)?;
+        self.undo_stack.record(Command::new("Перемещение группы", vec![
+            Change::Groups { before: groups_before, after: self.groups.clone() },
+        ]));
+        log_success!("move", "group", &group_id.to_string());
+        Ok(())
+    }
+    
+    /// Глубина поддерева группы (0 - подгрупп нет)
+    fn subtree_depth(&self, group_id: Uuid) -> u32 {
+        self.groups.iter()
+            .filter(|g| g.parent_id == Some(group_id))
+            .map(|g| self.subtree_depth(g.id) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+    
+    /// Экспортирует выбранные заметки; создаются только папки их групп и родителей этих групп
+    pub fn export_notes(&self, note_ids: &[Uuid], target_dir: &std::path::Path) -> Result<ExportReport, AppError> {
+        let notes: Vec<Note> = self.notes.iter()
+            .filter(|n| note_ids.contains(&n.id))
+            .cloned()
+            .collect();
+        
+        let mut group_ids: Vec<Uuid> = Vec::new();
+        for mut group_id in notes.iter().filter_map(|n| n.group_id) {
+            while !group_ids.contains(&group_id) {
+                group_ids.push(group_id);
+                match self.groups.iter().find(|g| g.id == group_id).and_then(|g| g.parent_id) {
+                    Some(parent_id) => group_id = parent_id,
+                    None => break,
+                }
+            }
+        }
+        let groups: Vec<NoteGroup> = self.groups.iter()
+            .filter(|g| group_ids.contains(&g.id))
+            .cloned()
+            .collect();
+        
+        export::export_markdown(&notes, &groups, target_dir)
+    }
+    
+    /// Экспортирует все заметки (включая не загруженные в ленивом режиме) и группы в папку с Markdown-файлами
+    pub fn export_markdown(&self, target_dir: &std::path::Path) -> Result<ExportReport, AppError> {
+        let notes = self.store.get_all_notes()?;
+        export::export_markdown(&notes, &self.groups, target_dir)
+    }
+    
+    /// Импортирует заметки из папки Markdown, Obsidian, Joplin или Evernote. Импорт отменяется одним шагом
+    pub fn import_notes(&mut self, source: ImportSource, path: &std::path::Path) -> Result<ImportReport, AppError> {
+        let existing_ids = self.store.get_all_notes()?.into_iter().map(|note| note.id).collect();
+        let result = import::import_notes(source, path, &self.groups, &existing_ids)?;
+        
+        let mut changes = Vec::new();
+        if !result.groups.is_empty() {
+            let mut groups_after = self.groups.clone();
+            groups_after.extend(result.groups.iter().cloned());
+            changes.push(Change::Groups { before: self.groups.clone(), after: groups_after });
+        }
+        changes.extend(result.notes.iter().map(|note| Change::note(None, Some(note))));
+        
+        if let Err(e) = self.apply_changes(&changes) {
+            log_error!("import", "markdown", &e);
+            return Err(e);
+        }
+        self.undo_stack.record(Command::new("Импорт", changes));
+        Ok(result.report)
+    }
+    
+    /// Все данные для резервной копии (в режиме ленивой загрузки заметки читаются из хранилища)
+    pub fn backup_contents(&self, settings: Option<AppSettings>) -> Result<BackupContents, AppError> {
+        Ok(BackupContents {
+            notes: self.store.get_all_notes()?,
+            groups: self.groups.clone(),
+            persistent_text: self.persistent_text.clone(),
+            settings,
+        })
+    }
+    
+    /// Создает резервную копию в архиве `path`
+    pub fn create_backup(&self, path: &std::path::Path, settings: &AppSettings) -> Result<BackupManifest, AppError> {
+        let contents = self.backup_contents(Some(settings.clone()))?;
+        backup::write_backup(path, &contents)
+    }
+    
+    /// Что изменится, если восстановить копию в указанном режиме
+    pub fn preview_restore(&self, contents: &BackupContents, mode: RestoreMode) -> Result<RestorePreview, AppError> {
+        let notes = self.store.get_all_notes()?;
+        Ok(backup::preview_restore(&notes, &self.groups, &self.persistent_text, contents, mode))
+    }
+    
+    /// Восстанавливает заметки и группы из копии. Заметки и группы отменяются одним шагом,
+    /// постоянный текст заменяется только в режиме замены. Настройки применяет вызывающий код.
+    pub fn restore_backup(&mut self, contents: &BackupContents, mode: RestoreMode) -> Result<RestorePreview, AppError> {
+        log_info!("restore", "backup", &format!("Восстановление {} заметок ({:?})", contents.notes.len(), mode));
+        let current_notes = self.store.get_all_notes()?;
+        let preview = backup::preview_restore(&current_notes, &self.groups, &self.persistent_text, contents, mode);
+        
+        let groups_after = match mode {
+            RestoreMode::Replace => contents.groups.clone(),
+            RestoreMode::Merge => {
+                let mut groups = self.groups.clone();
+                for group in &contents.groups {
+                    match groups.iter_mut().find(|g| g.id == group.id) {
+                        Some(existing) => *existing = group.clone(),
+                        None => groups.push(group.clone()),
+                    }
+                }
+                groups
+            }
+        };
+        let mut changes = vec![Change::Groups { before: self.groups.clone(), after: groups_after }];
+        
+        for note in &contents.notes {
+            let existing = current_notes.iter().find(|n| n.id == note.id);
+            if !existing.is_some_and(|existing| backup::notes_equal(existing, note)) {
+                changes.push(Change::note(existing, Some(note)));
+            }
+        }
+        if mode == RestoreMode::Replace {
+            for note in current_notes.iter().filter(|n| !contents.notes.iter().any(|b| b.id == n.id)) {
+                changes.push(Change::note(Some(note), None));
             }
         }
+        
+        if let Err(e) = self.apply_changes(&changes) {
+            log_error!("restore", "backup", &e);
+            return Err(e);
+        }
+        self.undo_stack.record(Command::new("Восстановление из копии", changes));
+        
+        if mode == RestoreMode::Replace && preview.persistent_text_changed {
+            self.persistent_text = contents.persistent_text.clone();
+            self.save_persistent_text()?;
+        }
+        
+        log_success!("restore", "backup");
+        Ok(preview)
+    }
+    
+    /// Отменяет последнюю операцию. Возвращает `false`, если отменять нечего
+    pub fn undo(&mut self) -> Result<bool, AppError> {
+        let Some(command) = self.undo_stack.pop_undo() else {
+            return Ok(false);
+        };
+        log_info!("undo", "command", &command.label);
+        
+        let inverse = command.clone().inverse();
+        if let Err(e) = self.apply_changes(&inverse.changes) {
+            // Часть изменений могла примениться - прежней истории больше нельзя доверять
+            self.undo_stack.clear();
+            log_error!("undo", "command", &e);
+            return Err(e);
+        }
+        self.undo_stack.push_redo(command);
+        Ok(true)
+    }
+    
+    /// Повторяет последнюю отмененную операцию. Возвращает `false`, если повторять нечего
+    pub fn redo(&mut self) -> Result<bool, AppError> {
+        let Some(command) = self.undo_stack.pop_redo() else {
+            return Ok(false);
+        };
+        log_info!("redo", "command", &command.label);
+        
+        if let Err(e) = self.apply_changes(&command.changes) {
+            self.undo_stack.clear();
+            log_error!("redo", "command", &e);
+            return Err(e);
+        }
+        self.undo_stack.push_undo(command);
+        Ok(true)
+    }
+    
+    /// Приводит заметки, группы и корзину к состоянию `after` каждого изменения
+    fn apply_changes(&mut self, changes: &[Change]) -> Result<(), AppError> {
+        let mut notes_added_or_removed = false;
+        
+        for change in changes {
+            match change {
+                Change::Note { before, after: Some(note) } => {
+                    self.store.save_note(note)?;
+                    match self.notes.iter_mut().find(|n| n.id == note.id) {
+                        Some(existing) => *existing = note.clone(),
+                        None => self.notes.push(note.clone()),
+                    }
+                    if let Some(ref mut lazy_loader) = self.lazy_loader {
+                        lazy_loader.update_note(note);
+                    }
+                    notes_added_or_removed |= before.is_none();
+                }
+                Change::Note { before: Some(note), after: None } => {
+                    match self.store.delete_note(note.id) {
+                        Ok(()) | Err(AppError::NoteNotFound) => {}
+                        Err(e) => return Err(e),
+                    }
+                    self.notes.retain(|n| n.id != note.id);
+                    notes_added_or_removed = true;
+                }
+                Change::Note { before: None, after: None } => {}
+                Change::Groups { after, .. } => {
+                    self.groups = after.clone();
+                    self.store.save_groups(&self.groups)?;
+                }
+                Change::TrashedNote { note_id, after, .. } => {
+                    self.trash.notes.retain(|item| item.note.id != *note_id);
+                    self.trash.notes.extend(after.iter().cloned());
+                    self.store.save_trash(&self.trash)?;
+                }
+                Change::TrashedGroup { group_id, after, .. } => {
+                    self.trash.groups.retain(|item| item.group.id != *group_id);
+                    self.trash.groups.extend(after.iter().cloned());
+                    self.store.save_trash(&self.trash)?;
+                }
+            }
+        }
+        
+        self.resort_notes();
+        if notes_added_or_removed
+            && let Some(ref mut lazy_loader) = self.lazy_loader
+            && let Err(e) = lazy_loader.initialize_with_store(self.store.as_ref())
+        {
+            log_error!("update", "lazy_loader", &e);
+        }
+        Ok(())
     }
     
     /// Обновляет уровни всех подгрупп итеративно
@@ -559,32 +1773,113 @@ impl AppState {
     }
 }
 
+/// Заметку в редакторе изменила другая программа, пока ее редактировали
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditConflict {
+    /// На диске новая версия; в редакторе остается несохраненный текст
+    Modified(Uuid),
+    /// Файл заметки удален; несохраненный текст можно сохранить новой заметкой
+    Deleted { title: String, content: String, group_id: Option<Uuid> },
+}
+
 /// UI состояние, отделенное от бизнес-логики
 pub struct UiState {
-    pub selected_note: Option<usize>,
-    pub editing_title: Option<usize>,
-    pub editing_content: Option<usize>,
+    pub selected_note: Option<Uuid>,
+    /// Несколько заметок, выбранных через Ctrl/Shift-клик (пусто - обычный выбор одной заметки)
+    pub selected_notes: Vec<Uuid>,
+    /// Заметка, от которой считается диапазон при Shift-клике
+    pub selection_anchor: Option<Uuid>,
+    /// Тег для добавления или удаления у выбранных заметок и результат последней групповой операции
+    pub bulk_tag_input: String,
+    pub bulk_status: Option<String>,
+    pub editing_title: Option<Uuid>,
+    pub editing_content: Option<Uuid>,
+    pub edit_conflict: Option<EditConflict>,
     pub theme_mode: ThemeMode,
     
     // Настройки производительности
     pub preferred_load_mode: LoadMode,
+    /// Выбранный формат хранения (вступает в силу после перезапуска)
+    pub preferred_storage_backend: StorageBackend,
     pub show_performance_stats: bool,
+    /// Срок хранения корзины в днях (значение из настроек)
+    pub trash_retention_days: u32,
+    /// Папка для экспорта в Markdown и результат последнего экспорта
+    pub export_path: String,
+    pub export_status: Option<String>,
+    /// Источник и путь для импорта, результат последнего импорта и пропущенные файлы
+    pub import_source: ImportSource,
+    pub import_path: String,
+    pub import_status: Option<String>,
+    pub import_skipped: Vec<String>,
+    /// Резервные копии: путь для новой копии, путь к восстанавливаемой, выбранный режим и предпросмотр
+    pub daily_backups_to_keep: u32,
+    pub backup_path: String,
+    pub backup_status: Option<String>,
+    pub restore_path: String,
+    pub restore_mode: RestoreMode,
+    pub pending_restore: Option<PendingRestore>,
+    pub restore_status: Option<String>,
     
     // Формы
     pub new_note_title: String,
     pub new_note_content: String,
     pub new_note_group_id: Option<Uuid>,
+    /// Ошибки проверки последней попытки создать заметку (показываются у полей формы)
+    pub note_form_errors: Vec<ValidationError>,
+    
+    // Уведомления: всплывающие, закрепленные ошибки хранилища и история
+    pub notifications: NotificationCenter,
+    pub show_notifications: bool,
     
     // Окна
     pub show_settings: bool,
     pub show_group_creation: bool,
     pub show_group_editor: bool,
     
+    // Теги
+    pub tag_filter: Option<String>,
+    pub new_tag_input: String,
+    pub show_tag_editor: bool,
+    pub editing_tag: Option<String>,
+    pub editing_tag_name: String,
+    pub tag_merge_selection: Vec<String>,
+    pub tag_merge_target: String,
+    
+    // Форма полей `{{name:...}}` перед копированием заметки
+    pub snippet_form: Option<SnippetForm>,
+    pub snippet_form_focus: bool,
+    
+    // Быстрый выбор заметки
+    pub show_quick_picker: bool,
+    pub quick_picker_query: String,
+    pub quick_picker_selected: usize,
+    pub quick_picker_focus: bool,
+    pub quick_picker_hotkey: String,
+    pub quick_picker_hotkey_status: Option<String>,
+    
+    // Сортировка
+    pub note_sort_mode: NoteSortMode,
+    
+    // Поиск
+    pub search_query: String,
+    pub search_results: Vec<SearchResult>,
+    
+    // История версий выбранной заметки (кэш, чтобы не читать хранилище каждый кадр)
+    pub show_history: bool,
+    pub history_note_id: Option<Uuid>,
+    pub history_revisions: Vec<NoteRevision>,
+    pub selected_revision: Option<Uuid>,
+    
+    // Горячие клавиши: действие, для которого ожидается новое сочетание
+    pub capturing_shortcut: Option<ShortcutAction>,
+    
     // Создание групп
     pub new_group_name: String,
     pub group_creation_selected_notes: Vec<Uuid>,
     pub creating_subgroup_for: Option<Uuid>,
     pub new_note_parent_group_id: Option<Uuid>,
+    pub group_form_errors: Vec<ValidationError>,
     
     // Редактирование групп
     pub editing_group_id: Option<Uuid>,
@@ -596,26 +1891,86 @@ impl UiState {
     pub fn new() -> Self {
         Self {
             selected_note: None,
+            selected_notes: Vec::new(),
+            selection_anchor: None,
+            bulk_tag_input: String::new(),
+            bulk_status: None,
             editing_title: None,
             editing_content: None,
+            edit_conflict: None,
             theme_mode: ThemeMode::Auto,
             
             // Настройки производительности
             preferred_load_mode: LoadMode::Auto,
+            preferred_storage_backend: StorageBackend::Json,
             show_performance_stats: false,
+            trash_retention_days: crate::trash::DEFAULT_TRASH_RETENTION_DAYS,
+            export_path: dirs::home_dir()
+                .unwrap_or_default()
+                .join("fast-bind-export")
+                .to_string_lossy()
+                .into_owned(),
+            export_status: None,
+            import_source: ImportSource::MarkdownFolder,
+            import_path: String::new(),
+            import_status: None,
+            import_skipped: Vec::new(),
+            daily_backups_to_keep: crate::backup::DEFAULT_DAILY_BACKUPS_TO_KEEP,
+            backup_path: dirs::home_dir()
+                .unwrap_or_default()
+                .join("fast-bind-backup.zip")
+                .to_string_lossy()
+                .into_owned(),
+            backup_status: None,
+            restore_path: String::new(),
+            restore_mode: RestoreMode::Replace,
+            pending_restore: None,
+            restore_status: None,
             
             new_note_title: String::new(),
             new_note_content: String::new(),
             new_note_group_id: None,
+            note_form_errors: Vec::new(),
+            
+            notifications: NotificationCenter::new(),
+            show_notifications: false,
             
             show_settings: false,
             show_group_creation: false,
             show_group_editor: false,
             
+            tag_filter: None,
+            new_tag_input: String::new(),
+            show_tag_editor: false,
+            editing_tag: None,
+            editing_tag_name: String::new(),
+            tag_merge_selection: Vec::new(),
+            tag_merge_target: String::new(),
+            snippet_form: None,
+            snippet_form_focus: false,
+            show_quick_picker: false,
+            quick_picker_query: String::new(),
+            quick_picker_selected: 0,
+            quick_picker_focus: false,
+            quick_picker_hotkey: crate::quick_picker::DEFAULT_QUICK_PICKER_HOTKEY.to_string(),
+            quick_picker_hotkey_status: None,
+            note_sort_mode: NoteSortMode::default(),
+            
+            search_query: String::new(),
+            search_results: Vec::new(),
+            
+            show_history: false,
+            history_note_id: None,
+            history_revisions: Vec::new(),
+            selected_revision: None,
+            
+            capturing_shortcut: None,
+            
             new_group_name: String::new(),
             group_creation_selected_notes: Vec::new(),
             creating_subgroup_for: None,
             new_note_parent_group_id: None,
+            group_form_errors: Vec::new(),
             
             editing_group_id: None,
             editing_group_name: String::new(),
@@ -628,7 +1983,9 @@ impl UiState {
         self.new_note_title.clear();
         self.new_note_content.clear();
         self.new_note_group_id = None;
+        self.note_form_errors.clear();
         self.selected_note = None;
+        self.selected_notes.clear();
     }
     
     /// Очищает форму создания группы
@@ -638,6 +1995,7 @@ impl UiState {
         self.new_note_parent_group_id = None;
         self.group_creation_selected_notes.clear();
         self.new_group_name.clear();
+        self.group_form_errors.clear();
     }
     
 
@@ -646,5 +2004,9 @@ impl UiState {
     pub fn stop_editing(&mut self) {
         self.editing_content = None;
         self.editing_title = None;
+        // Несохраненного текста больше нет - конфликт с версией на диске не важен
+        if matches!(self.edit_conflict, Some(EditConflict::Modified(_))) {
+            self.edit_conflict = None;
+        }
     }
 } 
\ No newline at end of file
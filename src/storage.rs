@@ -0,0 +1,227 @@
+/// Абстракция хранилища заметок, групп и черновика
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::history::NoteRevision;
+use crate::notes::{Note, NoteGroup, NoteMetadata, NotesManager};
+use crate::sqlite_store::SqliteNoteStore;
+use crate::trash::Trash;
+use crate::log_error;
+
+/// Формат хранения заметок на диске
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StorageBackend {
+    /// Одна заметка - один JSON-файл в `~/.fast-bind/data`
+    #[default]
+    Json,
+    /// Встроенная база SQLite `~/.fast-bind/notes.db` (для больших коллекций)
+    Sqlite,
+}
+
+/// Хранилище данных приложения.
+/// `AppState` работает с любой реализацией: файлами на диске (`NotesManager`) или памятью (`MemoryNoteStore`).
+pub trait NoteStore {
+    /// Сохраняет заметку (создает или перезаписывает)
+    fn save_note(&self, note: &Note) -> Result<(), AppError>;
+
+    /// Загружает заметку по ID; `None`, если заметки нет
+    fn load_note_by_id(&self, id: Uuid) -> Result<Option<Note>, AppError>;
+
+    /// Удаляет заметку; `AppError::NoteNotFound`, если заметки нет
+    fn delete_note(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Загружает все заметки, новые сверху
+    fn get_all_notes(&self) -> Result<Vec<Note>, AppError>;
+
+    /// Список ID всех заметок
+    fn get_note_ids(&self) -> Result<Vec<Uuid>, AppError> {
+        Ok(self.get_notes_metadata()?.into_iter().map(|meta| meta.id).collect())
+    }
+
+    /// Метаданные всех заметок без содержимого, новые сверху
+    fn get_notes_metadata(&self) -> Result<Vec<NoteMetadata>, AppError> {
+        Ok(self.get_all_notes()?.iter().map(NoteMetadata::from_note).collect())
+    }
+
+    /// Страница заметок в порядке `get_all_notes`
+    fn get_notes_page(&self, page: usize, page_size: usize) -> Result<Vec<Note>, AppError> {
+        Ok(self.get_all_notes()?
+            .into_iter()
+            .skip(page * page_size)
+            .take(page_size)
+            .collect())
+    }
+
+    /// Количество заметок
+    fn get_notes_count(&self) -> Result<usize, AppError> {
+        Ok(self.get_note_ids()?.len())
+    }
+
+    /// Сохраняет список групп целиком
+    fn save_groups(&self, groups: &[NoteGroup]) -> Result<(), AppError>;
+
+    /// Загружает группы; пустой список, если они еще не сохранялись
+    fn load_groups(&self) -> Result<Vec<NoteGroup>, AppError>;
+
+    /// Загружает постоянный текст (черновик); пустая строка, если он еще не сохранялся
+    fn load_persistent_text(&self) -> Result<String, AppError>;
+
+    /// Сохраняет постоянный текст
+    fn save_persistent_text(&self, text: &str) -> Result<(), AppError>;
+
+    /// Загружает историю версий заметки, от старых к новым
+    fn load_revisions(&self, note_id: Uuid) -> Result<Vec<NoteRevision>, AppError>;
+
+    /// Сохраняет историю версий заметки целиком
+    fn save_revisions(&self, note_id: Uuid, revisions: &[NoteRevision]) -> Result<(), AppError>;
+
+    /// Удаляет историю версий заметки (отсутствие истории не ошибка)
+    fn delete_revisions(&self, note_id: Uuid) -> Result<(), AppError>;
+
+    /// Загружает корзину; пустая корзина, если она еще не сохранялась
+    fn load_trash(&self) -> Result<Trash, AppError>;
+
+    /// Сохраняет корзину целиком
+    fn save_trash(&self, trash: &Trash) -> Result<(), AppError>;
+
+    /// Папка с файлами заметок, которые могут менять другие программы (синхронизация, ручная правка).
+    /// `None` - хранилище не в отдельных файлах, следить не за чем
+    fn watched_dir(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// Папка данных приложения по умолчанию: `~/.fast-bind`
+pub fn default_base_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".fast-bind")
+}
+
+/// Открывает хранилище заметок выбранного формата.
+/// При первом переходе на SQLite заметки переносятся из JSON-файлов;
+/// если базу открыть не удалось, используются JSON-файлы.
+pub fn open_store(base_dir: &Path, backend: StorageBackend) -> Box<dyn NoteStore> {
+    let notes_manager = NotesManager::new(base_dir.join("data"));
+    
+    match backend {
+        StorageBackend::Json => Box::new(notes_manager),
+        StorageBackend::Sqlite => {
+            let sqlite_store = SqliteNoteStore::open(&base_dir.join("notes.db"))
+                .and_then(|store| store.migrate_from(&notes_manager).map(|_| store));
+            match sqlite_store {
+                Ok(store) => Box::new(store),
+                Err(e) => {
+                    log_error!("open", "sqlite", &e);
+                    Box::new(notes_manager)
+                }
+            }
+        }
+    }
+}
+
+/// Открывает хранилище для команд, которые только читают данные. В отличие от `open_store`
+/// ничего не пишет на диск: временные файлы и перенос в SQLite остаются процессу, захватившему папку данных
+pub fn open_store_read_only(base_dir: &Path, backend: StorageBackend) -> Box<dyn NoteStore> {
+    let notes_manager = NotesManager::read_only(base_dir.join("data"));
+
+    match backend {
+        StorageBackend::Json => Box::new(notes_manager),
+        StorageBackend::Sqlite => match SqliteNoteStore::open_read_only(&base_dir.join("notes.db")) {
+            Ok(Some(store)) => Box::new(store),
+            Ok(None) => Box::new(notes_manager),
+            Err(e) => {
+                log_error!("open", "sqlite", &e);
+                Box::new(notes_manager)
+            }
+        },
+    }
+}
+
+/// Хранилище в памяти (для тестов и временных сессий без записи на диск)
+#[derive(Default)]
+pub struct MemoryNoteStore {
+    notes: RefCell<HashMap<Uuid, Note>>,
+    groups: RefCell<Vec<NoteGroup>>,
+    persistent_text: RefCell<String>,
+    revisions: RefCell<HashMap<Uuid, Vec<NoteRevision>>>,
+    trash: RefCell<Trash>,
+}
+
+impl MemoryNoteStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NoteStore for MemoryNoteStore {
+    fn save_note(&self, note: &Note) -> Result<(), AppError> {
+        self.notes.borrow_mut().insert(note.id, note.clone());
+        Ok(())
+    }
+
+    fn load_note_by_id(&self, id: Uuid) -> Result<Option<Note>, AppError> {
+        Ok(self.notes.borrow().get(&id).cloned())
+    }
+
+    fn delete_note(&self, id: Uuid) -> Result<(), AppError> {
+        self.notes.borrow_mut()
+            .remove(&id)
+            .map(|_| ())
+            .ok_or(AppError::NoteNotFound)
+    }
+
+    fn get_all_notes(&self) -> Result<Vec<Note>, AppError> {
+        let mut notes: Vec<Note> = self.notes.borrow().values().cloned().collect();
+        notes.sort_by_key(|note| std::cmp::Reverse(note.created_at));
+        Ok(notes)
+    }
+
+    fn get_notes_count(&self) -> Result<usize, AppError> {
+        Ok(self.notes.borrow().len())
+    }
+
+    fn save_groups(&self, groups: &[NoteGroup]) -> Result<(), AppError> {
+        *self.groups.borrow_mut() = groups.to_vec();
+        Ok(())
+    }
+
+    fn load_groups(&self) -> Result<Vec<NoteGroup>, AppError> {
+        Ok(self.groups.borrow().clone())
+    }
+
+    fn load_persistent_text(&self) -> Result<String, AppError> {
+        Ok(self.persistent_text.borrow().clone())
+    }
+
+    fn save_persistent_text(&self, text: &str) -> Result<(), AppError> {
+        *self.persistent_text.borrow_mut() = text.to_string();
+        Ok(())
+    }
+
+    fn load_revisions(&self, note_id: Uuid) -> Result<Vec<NoteRevision>, AppError> {
+        Ok(self.revisions.borrow().get(&note_id).cloned().unwrap_or_default())
+    }
+
+    fn save_revisions(&self, note_id: Uuid, revisions: &[NoteRevision]) -> Result<(), AppError> {
+        self.revisions.borrow_mut().insert(note_id, revisions.to_vec());
+        Ok(())
+    }
+
+    fn delete_revisions(&self, note_id: Uuid) -> Result<(), AppError> {
+        self.revisions.borrow_mut().remove(&note_id);
+        Ok(())
+    }
+
+    fn load_trash(&self) -> Result<Trash, AppError> {
+        Ok(self.trash.borrow().clone())
+    }
+
+    fn save_trash(&self, trash: &Trash) -> Result<(), AppError> {
+        *self.trash.borrow_mut() = trash.clone();
+        Ok(())
+    }
+}
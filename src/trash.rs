@@ -0,0 +1,65 @@
+/// Корзина: удаленные заметки и группы до окончательной очистки
+use chrono::{DateTime, Duration, Utc};
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::notes::{Note, NoteGroup};
+
+/// Срок хранения удаленных элементов по умолчанию (дней)
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+/// Удаленная заметка
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedNote {
+    pub note: Note,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Удаленная группа вместе с тем, что из неё было перенесено в родителя при удалении
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedGroup {
+    pub group: NoteGroup,
+    pub deleted_at: DateTime<Utc>,
+    /// Заметки, лежавшие прямо в группе
+    #[serde(default)]
+    pub note_ids: Vec<Uuid>,
+    /// Прямые подгруппы
+    #[serde(default)]
+    pub subgroup_ids: Vec<Uuid>,
+}
+
+/// Содержимое корзины
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Trash {
+    #[serde(default)]
+    pub notes: Vec<TrashedNote>,
+    #[serde(default)]
+    pub groups: Vec<TrashedGroup>,
+}
+
+impl Trash {
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty() && self.groups.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.notes.len() + self.groups.len()
+    }
+
+    /// Убирает элементы, удаленные раньше `now - retention_days`.
+    /// Возвращает убранные элементы (у заметок нужно почистить историю версий).
+    pub fn take_expired(&mut self, retention_days: u32, now: DateTime<Utc>) -> (Vec<TrashedNote>, Vec<TrashedGroup>) {
+        let cutoff = now - Duration::days(i64::from(retention_days));
+
+        let (expired_notes, notes) = std::mem::take(&mut self.notes)
+            .into_iter()
+            .partition(|item| item.deleted_at < cutoff);
+        self.notes = notes;
+
+        let (expired_groups, groups) = std::mem::take(&mut self.groups)
+            .into_iter()
+            .partition(|item| item.deleted_at < cutoff);
+        self.groups = groups;
+
+        (expired_notes, expired_groups)
+    }
+}
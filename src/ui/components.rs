@@ -1,6 +1,7 @@
 use eframe::egui;
 use uuid::Uuid;
 use crate::notes::NoteGroup;
+use crate::validation::{FormField, ValidationError};
 
 /// Утилиты для создания переиспользуемых UI компонентов
 pub struct UiComponents;
@@ -116,6 +117,50 @@ impl UiComponents {
         }
     }
     
+    /// Показывает под полем формы относящиеся к нему ошибки проверки
+    pub fn field_errors(ui: &mut egui::Ui, errors: &[ValidationError], field: FormField) {
+        for error in errors.iter().filter(|e| e.field() == field) {
+            ui.label(
+                egui::RichText::new(format!("⚠ {}", error))
+                    .small()
+                    .color(ui.visuals().error_fg_color)
+            );
+        }
+    }
+    
+    /// Создает текст с подсветкой указанных байтовых диапазонов (для результатов поиска)
+    pub fn highlighted_text(
+        text: &str,
+        ranges: &[std::ops::Range<usize>],
+        text_color: egui::Color32,
+        highlight_color: egui::Color32,
+        font_size: f32,
+    ) -> egui::text::LayoutJob {
+        let mut job = egui::text::LayoutJob::default();
+        let normal = egui::TextFormat {
+            font_id: egui::FontId::proportional(font_size),
+            color: text_color,
+            ..Default::default()
+        };
+        let highlighted = egui::TextFormat {
+            background: highlight_color,
+            ..normal.clone()
+        };
+        
+        let mut pos = 0;
+        for range in ranges {
+            if range.start < pos || range.end > text.len() {
+                continue;
+            }
+            job.append(&text[pos..range.start], 0.0, normal.clone());
+            job.append(&text[range.start..range.end], 0.0, highlighted.clone());
+            pos = range.end;
+        }
+        job.append(&text[pos..], 0.0, normal);
+        
+        job
+    }
+    
     /// Создает стандартный селектор групп
     pub fn group_selector(
         ui: &mut egui::Ui,
@@ -0,0 +1,238 @@
+/// Горячие клавиши приложения и их настраиваемые привязки
+use std::collections::BTreeMap;
+use std::fmt;
+use eframe::egui;
+use serde::{Serialize, Deserialize};
+
+/// Действия, доступные через горячие клавиши
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ShortcutAction {
+    NewNote,
+    SaveNote,
+    DeleteNote,
+    CopyToClipboard,
+    CopyToPersistent,
+    TogglePin,
+    SelectPrevious,
+    SelectNext,
+    Undo,
+    Redo,
+}
+
+impl ShortcutAction {
+    /// Все действия в порядке отображения в настройках
+    pub const ALL: [ShortcutAction; 10] = [
+        ShortcutAction::NewNote,
+        ShortcutAction::SaveNote,
+        ShortcutAction::DeleteNote,
+        ShortcutAction::CopyToClipboard,
+        ShortcutAction::CopyToPersistent,
+        ShortcutAction::TogglePin,
+        ShortcutAction::SelectPrevious,
+        ShortcutAction::SelectNext,
+        ShortcutAction::Undo,
+        ShortcutAction::Redo,
+    ];
+
+    /// Название действия для интерфейса
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::NewNote => "Новая заметка",
+            ShortcutAction::SaveNote => "Сохранить изменения",
+            ShortcutAction::DeleteNote => "Удалить заметку",
+            ShortcutAction::CopyToClipboard => "Копировать в буфер обмена",
+            ShortcutAction::CopyToPersistent => "Добавить в черновик",
+            ShortcutAction::TogglePin => "Закрепить/открепить",
+            ShortcutAction::SelectPrevious => "Предыдущая заметка",
+            ShortcutAction::SelectNext => "Следующая заметка",
+            ShortcutAction::Undo => "Отменить",
+            ShortcutAction::Redo => "Повторить",
+        }
+    }
+
+    /// Можно ли выполнять действие, пока фокус находится в текстовом поле.
+    /// Клавиши вроде Delete, стрелок и Ctrl+C в поле ввода должны работать как обычно.
+    pub fn works_in_text_input(&self) -> bool {
+        matches!(self, ShortcutAction::NewNote | ShortcutAction::SaveNote)
+    }
+}
+
+/// Комбинация клавиш, хранимая в настройках
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    /// Имя клавиши в формате egui (`egui::Key::name`)
+    pub key: String,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub shift: bool,
+    #[serde(default)]
+    pub alt: bool,
+}
+
+impl KeyBinding {
+    pub fn new(key: egui::Key, modifiers: egui::Modifiers) -> Self {
+        Self {
+            key: key.name().to_string(),
+            ctrl: modifiers.command || modifiers.ctrl,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+        }
+    }
+
+    fn plain(key: egui::Key) -> Self {
+        Self::new(key, egui::Modifiers::NONE)
+    }
+
+    fn ctrl(key: egui::Key) -> Self {
+        Self::new(key, egui::Modifiers::COMMAND)
+    }
+
+    fn ctrl_shift(key: egui::Key) -> Self {
+        Self::new(key, egui::Modifiers::COMMAND | egui::Modifiers::SHIFT)
+    }
+
+    /// Преобразует привязку в сочетание клавиш egui (None для неизвестной клавиши)
+    pub fn shortcut(&self) -> Option<egui::KeyboardShortcut> {
+        let key = egui::Key::from_name(&self.key)?;
+        let mut modifiers = egui::Modifiers::NONE;
+        if self.ctrl {
+            modifiers |= egui::Modifiers::COMMAND;
+        }
+        if self.shift {
+            modifiers |= egui::Modifiers::SHIFT;
+        }
+        if self.alt {
+            modifiers |= egui::Modifiers::ALT;
+        }
+        Some(egui::KeyboardShortcut::new(modifiers, key))
+    }
+
+    /// Количество модификаторов (более специфичные сочетания проверяются первыми)
+    fn modifiers_count(&self) -> usize {
+        [self.ctrl, self.shift, self.alt].iter().filter(|m| **m).count()
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// Таблица привязок горячих клавиш
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keymap {
+    pub bindings: BTreeMap<ShortcutAction, KeyBinding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = ShortcutAction::ALL.iter()
+            .map(|action| (*action, Self::default_binding(*action)))
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Привязка по умолчанию для действия
+    pub fn default_binding(action: ShortcutAction) -> KeyBinding {
+        match action {
+            ShortcutAction::NewNote => KeyBinding::ctrl(egui::Key::N),
+            ShortcutAction::SaveNote => KeyBinding::ctrl(egui::Key::S),
+            ShortcutAction::DeleteNote => KeyBinding::plain(egui::Key::Delete),
+            ShortcutAction::CopyToClipboard => KeyBinding::ctrl(egui::Key::C),
+            ShortcutAction::CopyToPersistent => KeyBinding::ctrl_shift(egui::Key::C),
+            ShortcutAction::TogglePin => KeyBinding::ctrl(egui::Key::P),
+            ShortcutAction::SelectPrevious => KeyBinding::plain(egui::Key::ArrowUp),
+            ShortcutAction::SelectNext => KeyBinding::plain(egui::Key::ArrowDown),
+            ShortcutAction::Undo => KeyBinding::ctrl(egui::Key::Z),
+            ShortcutAction::Redo => KeyBinding::ctrl_shift(egui::Key::Z),
+        }
+    }
+
+    /// Текущая привязка действия (для отсутствующих в файле настроек - по умолчанию)
+    pub fn binding(&self, action: ShortcutAction) -> KeyBinding {
+        self.bindings.get(&action)
+            .cloned()
+            .unwrap_or_else(|| Self::default_binding(action))
+    }
+
+    /// Переназначает действие; если сочетание уже занято, другое действие получает прежнее сочетание
+    pub fn rebind(&mut self, action: ShortcutAction, binding: KeyBinding) {
+        let previous = self.binding(action);
+        let conflicts: Vec<ShortcutAction> = ShortcutAction::ALL.iter()
+            .copied()
+            .filter(|other| *other != action && self.binding(*other) == binding)
+            .collect();
+
+        for other in conflicts {
+            self.bindings.insert(other, previous.clone());
+        }
+        self.bindings.insert(action, binding);
+    }
+
+    /// Собирает действия, чьи сочетания были нажаты в текущем кадре
+    pub fn poll(&self, ctx: &egui::Context) -> Vec<ShortcutAction> {
+        let text_input_focused = ctx.wants_keyboard_input();
+
+        let mut candidates: Vec<(ShortcutAction, KeyBinding)> = ShortcutAction::ALL.iter()
+            .filter(|action| !text_input_focused || action.works_in_text_input())
+            .map(|action| (*action, self.binding(*action)))
+            .collect();
+
+        // Ctrl+Shift+C должно проверяться раньше Ctrl+C
+        candidates.sort_by_key(|(_, binding)| std::cmp::Reverse(binding.modifiers_count()));
+
+        let mut triggered = Vec::new();
+        ctx.input_mut(|input| {
+            for (action, binding) in &candidates {
+                let Some(shortcut) = binding.shortcut() else {
+                    continue;
+                };
+                if input.consume_shortcut(&shortcut) || Self::consume_clipboard_command(input, &shortcut) {
+                    triggered.push(*action);
+                }
+            }
+        });
+
+        triggered
+    }
+
+    /// Сочетание, которое egui-winit передал как `Copy`, `Cut` или `Paste` вместо `Event::Key`
+    fn consume_clipboard_command(input: &mut egui::InputState, shortcut: &egui::KeyboardShortcut) -> bool {
+        let current = input.modifiers;
+        let position = input.events.iter().position(|event| {
+            matches!(event, egui::Event::Copy | egui::Event::Cut | egui::Event::Paste(_))
+                && key_press(event, current).is_some_and(|(key, modifiers)| {
+                    key == shortcut.logical_key && modifiers.matches_logically(shortcut.modifiers)
+                })
+        });
+        position.map(|index| input.events.remove(index)).is_some()
+    }
+}
+
+/// Клавиша и модификаторы нажатия из события ввода.
+/// egui-winit превращает Ctrl+C, Ctrl+X и Ctrl+V в события `Copy`, `Cut` и `Paste` без `Event::Key`,
+/// поэтому клавиша восстанавливается по модификаторам, зажатым в этом кадре
+pub fn key_press(event: &egui::Event, current: egui::Modifiers) -> Option<(egui::Key, egui::Modifiers)> {
+    let (command_key, dedicated_key) = match event {
+        egui::Event::Key { key, pressed: true, modifiers, .. } => return Some((*key, *modifiers)),
+        egui::Event::Copy => (egui::Key::C, egui::Key::Copy),
+        egui::Event::Cut => (egui::Key::X, egui::Key::Cut),
+        egui::Event::Paste(_) => (egui::Key::V, egui::Key::Paste),
+        _ => return None,
+    };
+    Some((if current.command { command_key } else { dedicated_key }, current))
+}
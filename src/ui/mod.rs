@@ -1,4 +1,5 @@
 pub mod components;
+pub mod keymap;
 pub mod panels;
 pub mod panel_actions;
 pub mod theme;
@@ -6,7 +7,7 @@ pub mod windows;
 
 pub use components::*;
 pub use panels::*;
-pub use panel_actions::{SidePanelActions, CentralPanelActions, SettingsActions};
+pub use panel_actions::{SidePanelActions, CentralPanelActions, SettingsActions, TagEditorActions, SnippetFormActions, QuickPickerActions, TrashAction, BulkAction, ConflictResolution};
 pub use theme::*;
 pub use windows::*;
 
@@ -1,7 +1,13 @@
 /// Структуры для действий, возвращаемых из панелей
+use std::path::PathBuf;
 use uuid::Uuid;
+use crate::backup::RestoreMode;
+use crate::import::ImportSource;
+use crate::notes::NoteSortMode;
 use crate::state::LoadMode;
+use crate::storage::StorageBackend;
 use crate::ui::ThemeMode;
+use crate::ui::keymap::{KeyBinding, ShortcutAction};
 
 /// Действия для окна настроек
 #[derive(Debug, Default)]
@@ -9,19 +15,107 @@ pub struct SettingsActions {
     pub theme_changed: Option<ThemeMode>,
     pub load_mode_changed: Option<LoadMode>,
     pub show_performance_stats_changed: Option<bool>,
+    pub storage_backend_changed: Option<StorageBackend>,
+    pub trash_retention_changed: Option<u32>,
+    pub export_markdown: Option<PathBuf>,
+    pub import_notes: Option<(ImportSource, PathBuf)>,
+    pub daily_backups_changed: Option<u32>,
+    pub create_backup: Option<PathBuf>,
+    pub preview_restore: Option<PathBuf>,
+    pub restore_backup: Option<(PathBuf, RestoreMode)>,
+    pub cancel_restore: bool,
+    pub shortcut_changed: Option<(ShortcutAction, KeyBinding)>,
+    pub reset_shortcuts: bool,
+    pub quick_picker_hotkey_changed: Option<String>,
+    pub sort_mode_changed: Option<NoteSortMode>,
     pub close_settings: bool,
 }
 
+/// Действия для окна управления тегами
+#[derive(Debug, Default)]
+pub struct TagEditorActions {
+    /// Переименования тегов (старое имя, новое имя)
+    pub renames: Vec<(String, String)>,
+    /// Объединение нескольких тегов в один (исходные теги, итоговый тег)
+    pub merge: Option<(Vec<String>, String)>,
+}
+
+/// Действия для формы полей подстановок
+#[derive(Debug, Default)]
+pub struct SnippetFormActions {
+    /// Значения полей введены, текст можно раскрывать
+    pub submit: bool,
+    pub cancel: bool,
+}
+
+/// Действия окна быстрого выбора
+#[derive(Debug, Default)]
+pub struct QuickPickerActions {
+    /// Заметка, которую нужно скопировать
+    pub copy_note: Option<Uuid>,
+    pub close: bool,
+}
+
 /// Действия для боковой панели
 #[derive(Debug, Default)]
 pub struct SidePanelActions {
-    pub selected_note: Option<usize>,
+    pub selected_note: Option<Uuid>,
     pub new_note_clicked: bool,
     pub create_group_clicked: bool,
     pub show_settings_clicked: bool,
+    pub show_notifications_clicked: bool,
     pub show_group_editor_clicked: bool,
     pub toggled_group: Option<Uuid>,
     pub load_more_requested: Option<(usize, usize)>, // (visible_start, visible_end)
+    pub search_changed: bool,
+    pub selected_search_result: Option<Uuid>,
+    pub tag_filter_changed: Option<Option<String>>,
+    pub show_tag_editor_clicked: bool,
+    pub trash_action: Option<TrashAction>,
+    pub undo_clicked: bool,
+    pub redo_clicked: bool,
+    pub group_sort_changed: Option<(Uuid, Option<NoteSortMode>)>,
+    pub note_moved: Option<(Uuid, Option<Uuid>, Option<Uuid>)>, // (заметка, группа, перед какой заметкой)
+    pub group_moved: Option<(Uuid, Option<Uuid>)>,              // (группа, новый родитель)
+    pub toggled_note_selection: Option<Uuid>,                   // Ctrl-клик
+    pub selected_note_range: Option<Uuid>,                      // Shift-клик
+}
+
+/// Действие с корзиной из боковой панели
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrashAction {
+    RestoreNote(Uuid),
+    RestoreGroup(Uuid),
+    PurgeNote(Uuid),
+    PurgeGroup(Uuid),
+    Empty,
+}
+
+/// Операция над несколькими выбранными заметками
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkAction {
+    MoveToGroup(Option<Uuid>),
+    SetPinned(bool),
+    Delete,
+    Export(PathBuf),
+    CopyToClipboard,
+    CopyToPersistent,
+    AddTag(String),
+    RemoveTag(String),
+    ClearSelection,
+}
+
+/// Выбор пользователя, когда редактируемую заметку изменили на диске
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Показать версию с диска, отбросив несохраненный текст
+    ReloadFromDisk,
+    /// Продолжить редактирование; сохранение перезапишет версию с диска
+    KeepMine,
+    /// Сохранить текст удаленной заметки новой заметкой
+    SaveAsNew,
+    /// Отбросить текст удаленной заметки
+    Discard,
 }
 
 /// Действия для центральной панели
@@ -31,10 +125,15 @@ pub struct CentralPanelActions {
     pub delete_note_clicked: bool,
     pub copy_to_clipboard_clicked: bool,
     pub copy_to_persistent_clicked: bool,
-    pub toggle_pin: Option<usize>,
-    pub update_title: Option<(usize, String)>,
+    pub toggle_pin: Option<Uuid>,
+    pub update_title: Option<(Uuid, String)>,
+    pub update_tags: Option<(Uuid, Vec<String>)>,
+    pub move_to_group: Option<(Uuid, Option<Uuid>)>,
+    pub restore_revision: Option<(Uuid, Uuid)>,
     pub create_note_clicked: bool,
     pub persistent_text_changed: bool,
+    pub bulk_action: Option<BulkAction>,
+    pub resolve_conflict: Option<ConflictResolution>,
 }
 
 impl SidePanelActions {
@@ -42,8 +141,8 @@ impl SidePanelActions {
         Self::default()
     }
     
-    pub fn select_note(&mut self, idx: usize) {
-        self.selected_note = Some(idx);
+    pub fn select_note(&mut self, note_id: Uuid) {
+        self.selected_note = Some(note_id);
     }
     
     pub fn new_note(&mut self) {
@@ -58,6 +157,10 @@ impl SidePanelActions {
         self.show_settings_clicked = true;
     }
     
+    pub fn show_notifications(&mut self) {
+        self.show_notifications_clicked = true;
+    }
+    
     pub fn show_group_editor(&mut self) {
         self.show_group_editor_clicked = true;
     }
@@ -69,6 +172,54 @@ impl SidePanelActions {
     pub fn request_load_more(&mut self, visible_start: usize, visible_end: usize) {
         self.load_more_requested = Some((visible_start, visible_end));
     }
+    
+    pub fn search_changed(&mut self) {
+        self.search_changed = true;
+    }
+    
+    pub fn select_search_result(&mut self, note_id: Uuid) {
+        self.selected_search_result = Some(note_id);
+    }
+    
+    pub fn set_tag_filter(&mut self, tag: Option<String>) {
+        self.tag_filter_changed = Some(tag);
+    }
+    
+    pub fn show_tag_editor(&mut self) {
+        self.show_tag_editor_clicked = true;
+    }
+    
+    pub fn trash(&mut self, action: TrashAction) {
+        self.trash_action = Some(action);
+    }
+    
+    pub fn undo(&mut self) {
+        self.undo_clicked = true;
+    }
+    
+    pub fn redo(&mut self) {
+        self.redo_clicked = true;
+    }
+    
+    pub fn set_group_sort(&mut self, group_id: Uuid, sort_mode: Option<NoteSortMode>) {
+        self.group_sort_changed = Some((group_id, sort_mode));
+    }
+    
+    pub fn move_note(&mut self, note_id: Uuid, group_id: Option<Uuid>, before: Option<Uuid>) {
+        self.note_moved = Some((note_id, group_id, before));
+    }
+    
+    pub fn move_group(&mut self, group_id: Uuid, parent_id: Option<Uuid>) {
+        self.group_moved = Some((group_id, parent_id));
+    }
+    
+    pub fn toggle_note_selection(&mut self, note_id: Uuid) {
+        self.toggled_note_selection = Some(note_id);
+    }
+    
+    pub fn select_note_range(&mut self, note_id: Uuid) {
+        self.selected_note_range = Some(note_id);
+    }
 }
 
 impl CentralPanelActions {
@@ -92,18 +243,38 @@ impl CentralPanelActions {
         self.copy_to_persistent_clicked = true;
     }
     
-    pub fn toggle_pin(&mut self, idx: usize) {
-        self.toggle_pin = Some(idx);
+    pub fn toggle_pin(&mut self, note_id: Uuid) {
+        self.toggle_pin = Some(note_id);
+    }
+    
+    pub fn update_title(&mut self, note_id: Uuid, title: String) {
+        self.update_title = Some((note_id, title));
+    }
+    
+    pub fn update_tags(&mut self, note_id: Uuid, tags: Vec<String>) {
+        self.update_tags = Some((note_id, tags));
+    }
+    
+    pub fn move_to_group(&mut self, note_id: Uuid, group_id: Option<Uuid>) {
+        self.move_to_group = Some((note_id, group_id));
     }
     
-    pub fn update_title(&mut self, idx: usize, title: String) {
-        self.update_title = Some((idx, title));
+    pub fn restore_revision(&mut self, note_id: Uuid, revision_id: Uuid) {
+        self.restore_revision = Some((note_id, revision_id));
     }
     
     pub fn create_note(&mut self) {
         self.create_note_clicked = true;
     }
     
+    pub fn bulk(&mut self, action: BulkAction) {
+        self.bulk_action = Some(action);
+    }
+    
+    pub fn resolve_conflict(&mut self, resolution: ConflictResolution) {
+        self.resolve_conflict = Some(resolution);
+    }
+    
     pub fn persistent_text_changed(&mut self) {
         self.persistent_text_changed = true;
     }
@@ -126,7 +297,101 @@ impl SettingsActions {
         self.show_performance_stats_changed = Some(show);
     }
     
+    pub fn change_storage_backend(&mut self, backend: StorageBackend) {
+        self.storage_backend_changed = Some(backend);
+    }
+    
+    pub fn change_trash_retention(&mut self, days: u32) {
+        self.trash_retention_changed = Some(days);
+    }
+    
+    pub fn export_markdown(&mut self, target_dir: PathBuf) {
+        self.export_markdown = Some(target_dir);
+    }
+    
+    pub fn import_notes(&mut self, source: ImportSource, path: PathBuf) {
+        self.import_notes = Some((source, path));
+    }
+    
+    pub fn change_daily_backups(&mut self, keep: u32) {
+        self.daily_backups_changed = Some(keep);
+    }
+    
+    pub fn create_backup(&mut self, path: PathBuf) {
+        self.create_backup = Some(path);
+    }
+    
+    pub fn preview_restore(&mut self, path: PathBuf) {
+        self.preview_restore = Some(path);
+    }
+    
+    pub fn restore_backup(&mut self, path: PathBuf, mode: RestoreMode) {
+        self.restore_backup = Some((path, mode));
+    }
+    
+    pub fn cancel_restore(&mut self) {
+        self.cancel_restore = true;
+    }
+    
+    pub fn change_shortcut(&mut self, action: ShortcutAction, binding: KeyBinding) {
+        self.shortcut_changed = Some((action, binding));
+    }
+    
+    pub fn reset_shortcuts(&mut self) {
+        self.reset_shortcuts = true;
+    }
+    
+    pub fn change_quick_picker_hotkey(&mut self, spec: String) {
+        self.quick_picker_hotkey_changed = Some(spec);
+    }
+    
+    pub fn change_sort_mode(&mut self, sort_mode: NoteSortMode) {
+        self.sort_mode_changed = Some(sort_mode);
+    }
+    
     pub fn close(&mut self) {
         self.close_settings = true;
     }
-} 
\ No newline at end of file
+}
+
+impl QuickPickerActions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn copy_note(&mut self, note_id: Uuid) {
+        self.copy_note = Some(note_id);
+    }
+    
+    pub fn close(&mut self) {
+        self.close = true;
+    }
+}
+
+impl SnippetFormActions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn submit(&mut self) {
+        self.submit = true;
+    }
+    
+    pub fn cancel(&mut self) {
+        self.cancel = true;
+    }
+}
+
+impl TagEditorActions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn rename(&mut self, old_tag: String, new_tag: String) {
+        self.renames.push((old_tag, new_tag));
+    }
+    
+    pub fn merge(&mut self, source_tags: Vec<String>, target_tag: String) {
+        self.merge = Some((source_tags, target_tag));
+    }
+}
@@ -1,9 +1,19 @@
 use eframe::egui;
 use uuid::Uuid;
 
-use crate::state::{AppState, UiState};
+use crate::history::{diff_lines, DiffLine};
+use crate::notes::{Note, NoteSortMode};
+use crate::state::{AppState, EditConflict, UiState};
 use crate::ui::{theme::ThemeColors, UiComponents};
-use crate::ui::panel_actions::{SidePanelActions, CentralPanelActions};
+use crate::ui::panel_actions::{BulkAction, ConflictResolution, SidePanelActions, CentralPanelActions, TrashAction};
+use crate::validation::FormField;
+
+/// Что перетаскивается в боковой панели
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DragItem {
+    Note(Uuid),
+    Group(Uuid),
+}
 
 /// Управление панелями интерфейса
 pub struct PanelManager;
@@ -63,6 +73,15 @@ impl PanelManager {
                                 actions.show_settings();
                             }
                             
+                            let unread = ui_state.notifications.unread_count();
+                            let bell = if unread > 0 { format!("🔔{}", unread) } else { "🔔".to_string() };
+                            if UiComponents::icon_button(
+                                ui, &bell, "Уведомления",
+                                colors.button_bg, colors.button_border
+                            ).clicked() {
+                                actions.show_notifications();
+                            }
+                            
                             if UiComponents::icon_button(
                                 ui, "📁", "Редактор групп",
                                 colors.button_bg, colors.button_border
@@ -70,6 +89,29 @@ impl PanelManager {
                                 actions.show_group_editor();
                             }
                             
+                            let undo_stack = &app_state.undo_stack;
+                            let redo_hint = undo_stack.redo_label()
+                                .map_or("Повторить".to_string(), |label| format!("Повторить: {} (Ctrl+Shift+Z)", label));
+                            ui.add_enabled_ui(undo_stack.can_redo(), |ui| {
+                                if UiComponents::icon_button(
+                                    ui, "↷", &redo_hint,
+                                    colors.button_bg, colors.button_border
+                                ).clicked() {
+                                    actions.redo();
+                                }
+                            });
+                            
+                            let undo_hint = undo_stack.undo_label()
+                                .map_or("Отменить".to_string(), |label| format!("Отменить: {} (Ctrl+Z)", label));
+                            ui.add_enabled_ui(undo_stack.can_undo(), |ui| {
+                                if UiComponents::icon_button(
+                                    ui, "↶", &undo_hint,
+                                    colors.button_bg, colors.button_border
+                                ).clicked() {
+                                    actions.undo();
+                                }
+                            });
+                            
                             // Индикатор режима загрузки
                             if ui_state.show_performance_stats {
                                 let mode_icon = match app_state.load_mode {
@@ -85,6 +127,32 @@ impl PanelManager {
                     
                     ui.add_space(10.0);
                     
+                    // Поле поиска по заметкам
+                    ui.horizontal(|ui| {
+                        UiComponents::text_field_frame().show(ui, |ui| {
+                            let response = UiComponents::single_line_text_edit(
+                                ui,
+                                &mut ui_state.search_query,
+                                "🔍 Поиск по заметкам",
+                                panel_width - 56.0,
+                                20.0
+                            );
+                            if response.changed() {
+                                actions.search_changed();
+                            }
+                        });
+                        
+                        if !ui_state.search_query.is_empty() && UiComponents::icon_button(
+                            ui, "✖", "Очистить поиск",
+                            colors.button_bg, colors.button_border
+                        ).clicked() {
+                            ui_state.search_query.clear();
+                            actions.search_changed();
+                        }
+                    });
+                    
+                    ui.add_space(10.0);
+                    
                     // Кнопки управления
                     if ui.add_sized([panel_width - 12.0, 32.0], egui::Button::new("+ Новая заметка")).clicked() {
                         actions.new_note();
@@ -97,13 +165,22 @@ impl PanelManager {
                     }
                     
                     ui.add_space(10.0);
+                    
+                    Self::show_tag_cloud(app_state, ui_state, ui, colors, &mut actions);
+                    Self::show_trash(app_state, ui, colors, &mut actions);
 
                     // Отображение групп и заметок с подсчетом видимых элементов
                     let notes_start_y = ui.cursor().top();
                     
-                    Self::show_groups_and_notes_simple(
-                        app_state, ui_state, ui, colors, panel_width, &mut actions
-                    );
+                    if ui_state.search_query.trim().is_empty() {
+                        Self::show_groups_and_notes_simple(
+                            app_state, ui_state, ui, colors, panel_width, &mut actions
+                        );
+                    } else {
+                        Self::show_search_results(
+                            ui_state, ui, colors, panel_width, &mut actions
+                        );
+                    }
                     
                     let notes_end_y = ui.cursor().top();
                     
@@ -168,10 +245,22 @@ impl PanelManager {
             })
             .show_inside(ui, |ui| {
                 ui.vertical(|ui| {
-                    if let Some(idx) = ui_state.selected_note {
-                        if idx < app_state.notes.len() {
+                    Self::show_error_banners(ui_state, ui, colors);
+                    
+                    // Конфликт правки показывается, пока заметка открыта для редактирования
+                    if let Some(conflict) = &ui_state.edit_conflict
+                        && !matches!(conflict, EditConflict::Modified(id) if ui_state.editing_content != Some(*id))
+                    {
+                        Self::show_edit_conflict(conflict, ui, colors, &mut actions);
+                        ui.add_space(8.0);
+                    }
+                    
+                    if ui_state.selected_notes.len() > 1 {
+                        Self::show_bulk_actions(app_state, ui_state, ui, colors, &mut actions);
+                    } else if let Some(note_id) = ui_state.selected_note {
+                        if let Some(note) = app_state.note(note_id) {
                             Self::show_note_editor_simple(
-                                app_state, ui_state, ui, idx, colors, &mut actions
+                                app_state, ui_state, ui, note, colors, &mut actions
                             );
                         }
                     } else {
@@ -187,6 +276,359 @@ impl PanelManager {
         actions
     }
     
+    /// Закрепленные ошибки хранилища: остаются, пока пользователь их не закроет
+    fn show_error_banners(ui_state: &mut UiState, ui: &mut egui::Ui, colors: &ThemeColors) {
+        let mut dismissed = Vec::new();
+        for banner in ui_state.notifications.banners() {
+            egui::Frame::new()
+                .fill(colors.diff_removed)
+                .inner_margin(egui::Margin::same(8))
+                .corner_radius(4)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.horizontal(|ui| {
+                        if ui.small_button("✖").on_hover_text("Скрыть").clicked() {
+                            dismissed.push(banner.id);
+                        }
+                        let text = match banner.repeats {
+                            1 => format!("⚠ {}", banner.message),
+                            repeats => format!("⚠ {} (×{})", banner.message, repeats),
+                        };
+                        ui.add(egui::Label::new(text).wrap());
+                    });
+                });
+            ui.add_space(8.0);
+        }
+        for id in dismissed {
+            ui_state.notifications.dismiss(id);
+        }
+    }
+    
+    /// Предупреждение о заметке, измененной на диске во время редактирования
+    fn show_edit_conflict(
+        conflict: &EditConflict,
+        ui: &mut egui::Ui,
+        colors: &ThemeColors,
+        actions: &mut CentralPanelActions,
+    ) {
+        egui::Frame::new()
+            .fill(colors.diff_removed)
+            .inner_margin(egui::Margin::same(8))
+            .corner_radius(4)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                match conflict {
+                    EditConflict::Modified(_) => {
+                        ui.label("⚠ Заметку изменили на диске, пока вы ее редактировали");
+                        ui.horizontal(|ui| {
+                            if ui.button("🔄 Загрузить с диска").clicked() {
+                                actions.resolve_conflict(ConflictResolution::ReloadFromDisk);
+                            }
+                            if ui.button("✏ Оставить мои изменения").clicked() {
+                                actions.resolve_conflict(ConflictResolution::KeepMine);
+                            }
+                        });
+                    }
+                    EditConflict::Deleted { title, .. } => {
+                        ui.label(format!("⚠ Заметку '{}' удалили на диске, пока вы ее редактировали", title));
+                        ui.horizontal(|ui| {
+                            if ui.button("💾 Сохранить как новую").clicked() {
+                                actions.resolve_conflict(ConflictResolution::SaveAsNew);
+                            }
+                            if ui.button("❌ Отбросить").clicked() {
+                                actions.resolve_conflict(ConflictResolution::Discard);
+                            }
+                        });
+                    }
+                }
+            });
+    }
+    
+    /// Операции над несколькими выбранными заметками (вместо редактора)
+    fn show_bulk_actions(
+        app_state: &AppState,
+        ui_state: &mut UiState,
+        ui: &mut egui::Ui,
+        colors: &ThemeColors,
+        actions: &mut CentralPanelActions,
+    ) {
+        let selected: Vec<&Note> = app_state.notes.iter()
+            .filter(|n| ui_state.selected_notes.contains(&n.id))
+            .collect();
+        
+        ui.horizontal(|ui| {
+            ui.heading(egui::RichText::new(format!("Выбрано заметок: {}", selected.len())).color(colors.header));
+            if ui.button("✖ Снять выделение").clicked() {
+                actions.bulk(BulkAction::ClearSelection);
+            }
+        });
+        ui.label(egui::RichText::new("Ctrl+клик добавляет или убирает заметку, Shift+клик выбирает диапазон").small().weak());
+        ui.add_space(8.0);
+        
+        ui.horizontal(|ui| {
+            if ui.button("📌 Закрепить").clicked() {
+                actions.bulk(BulkAction::SetPinned(true));
+            }
+            if ui.button("Открепить").clicked() {
+                actions.bulk(BulkAction::SetPinned(false));
+            }
+            if ui.button("🗑 Удалить").clicked() {
+                actions.bulk(BulkAction::Delete);
+            }
+        });
+        ui.add_space(6.0);
+        
+        // Текущая группа показывается, только если она у всех выбранных заметок одна
+        let common_group = selected.first()
+            .map(|n| n.group_id)
+            .filter(|group_id| selected.iter().all(|n| n.group_id == *group_id))
+            .flatten();
+        ui.horizontal(|ui| {
+            ui.label("📁 Переместить в:");
+            UiComponents::group_selector(
+                ui,
+                common_group,
+                &app_state.groups,
+                "bulk_group_select",
+                |group_id| actions.bulk(BulkAction::MoveToGroup(group_id)),
+                20
+            );
+        });
+        ui.add_space(6.0);
+        
+        ui.horizontal(|ui| {
+            ui.label("🏷");
+            UiComponents::single_line_text_edit(ui, &mut ui_state.bulk_tag_input, "Тег", 160.0, 24.0);
+            let tag = ui_state.bulk_tag_input.trim().to_string();
+            if ui.add_enabled(!tag.is_empty(), egui::Button::new("➕ Добавить")).clicked() {
+                actions.bulk(BulkAction::AddTag(tag.clone()));
+            }
+            if ui.add_enabled(!tag.is_empty(), egui::Button::new("➖ Убрать")).clicked() {
+                actions.bulk(BulkAction::RemoveTag(tag));
+            }
+        });
+        ui.add_space(6.0);
+        
+        ui.horizontal(|ui| {
+            if ui.button("📋 Копировать вместе").clicked() {
+                actions.bulk(BulkAction::CopyToClipboard);
+            }
+            if ui.button("📝 В постоянный текст").clicked() {
+                actions.bulk(BulkAction::CopyToPersistent);
+            }
+        });
+        ui.add_space(6.0);
+        
+        ui.horizontal(|ui| {
+            UiComponents::single_line_text_edit(ui, &mut ui_state.export_path, "Папка для экспорта", 240.0, 24.0);
+            if ui.button("📤 Экспортировать выбранные").clicked() && !ui_state.export_path.trim().is_empty() {
+                actions.bulk(BulkAction::Export(std::path::PathBuf::from(ui_state.export_path.trim())));
+            }
+        });
+        
+        if let Some(status) = &ui_state.bulk_status {
+            ui.add_space(6.0);
+            ui.label(egui::RichText::new(status).color(colors.text));
+        }
+    }
+    
+    /// Индексы заметок в порядке их отображения в боковой панели
+    /// (заметки свёрнутых групп и не прошедшие фильтр по тегу пропускаются)
+    pub fn note_display_order(app_state: &AppState, tag_filter: Option<&str>) -> Vec<usize> {
+        let mut order = Vec::new();
+        let mut stack: Vec<Uuid> = app_state.groups.iter()
+            .filter(|g| g.parent_id.is_none())
+            .map(|g| g.id)
+            .rev()
+            .collect();
+        
+        while let Some(group_id) = stack.pop() {
+            let collapsed = app_state.groups.iter()
+                .find(|g| g.id == group_id)
+                .map(|g| g.collapsed)
+                .unwrap_or(false);
+            if collapsed {
+                continue;
+            }
+            
+            order.extend(app_state.group_note_indices(Some(group_id)).into_iter()
+                .filter(|&i| Self::matches_tag_filter(&app_state.notes[i], tag_filter)));
+            
+            stack.extend(app_state.groups.iter()
+                .filter(|g| g.parent_id == Some(group_id))
+                .map(|g| g.id)
+                .rev());
+        }
+        
+        order.extend(app_state.group_note_indices(None).into_iter()
+            .filter(|&i| Self::matches_tag_filter(&app_state.notes[i], tag_filter)));
+        
+        order
+    }
+    
+    /// Проверяет, проходит ли заметка фильтр по тегу
+    fn matches_tag_filter(note: &Note, tag_filter: Option<&str>) -> bool {
+        tag_filter.is_none_or(|tag| note.tags.iter().any(|t| t == tag))
+    }
+    
+    /// Облако тегов с количеством заметок; клик по тегу включает/выключает фильтр
+    fn show_tag_cloud(
+        app_state: &AppState,
+        ui_state: &UiState,
+        ui: &mut egui::Ui,
+        colors: &ThemeColors,
+        actions: &mut SidePanelActions,
+    ) {
+        let tags = app_state.all_tags();
+        if tags.is_empty() {
+            return;
+        }
+        
+        egui::CollapsingHeader::new(
+            egui::RichText::new("🏷 Теги")
+                .color(colors.header)
+                .strong()
+        )
+            .id_salt("tag_cloud")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    for (tag, count) in &tags {
+                        let is_active = ui_state.tag_filter.as_deref() == Some(tag.as_str());
+                        let label = egui::SelectableLabel::new(
+                            is_active,
+                            egui::RichText::new(format!("#{} ({})", tag, count)).color(colors.text)
+                        );
+                        if ui.add(label).clicked() {
+                            actions.set_tag_filter(if is_active { None } else { Some(tag.clone()) });
+                        }
+                    }
+                });
+                
+                ui.horizontal(|ui| {
+                    if ui_state.tag_filter.is_some() && ui.small_button("Сбросить фильтр").clicked() {
+                        actions.set_tag_filter(None);
+                    }
+                    if ui.small_button("✏ Управление тегами").clicked() {
+                        actions.show_tag_editor();
+                    }
+                });
+            });
+        
+        ui.add_space(10.0);
+    }
+    
+    /// Корзина: удаленные заметки и группы с восстановлением и окончательным удалением
+    fn show_trash(
+        app_state: &AppState,
+        ui: &mut egui::Ui,
+        colors: &ThemeColors,
+        actions: &mut SidePanelActions,
+    ) {
+        let trash = &app_state.trash;
+        if trash.is_empty() {
+            return;
+        }
+        
+        egui::CollapsingHeader::new(
+            egui::RichText::new(format!("🗑 Корзина ({})", trash.len()))
+                .color(colors.header)
+                .strong()
+        )
+            .id_salt("trash")
+            .default_open(false)
+            .show(ui, |ui| {
+                for item in trash.groups.iter().rev() {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(format!("📁 {}", item.group.name)).color(colors.text))
+                            .on_hover_text(format!("Удалена {}", item.deleted_at.with_timezone(&chrono::Local).format("%d.%m.%Y %H:%M")));
+                        if ui.small_button("↩").on_hover_text("Восстановить").clicked() {
+                            actions.trash(TrashAction::RestoreGroup(item.group.id));
+                        }
+                        if ui.small_button("✖").on_hover_text("Удалить навсегда").clicked() {
+                            actions.trash(TrashAction::PurgeGroup(item.group.id));
+                        }
+                    });
+                }
+                for item in trash.notes.iter().rev() {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(format!("📝 {}", item.note.title)).color(colors.text))
+                            .on_hover_text(format!("Удалена {}", item.deleted_at.with_timezone(&chrono::Local).format("%d.%m.%Y %H:%M")));
+                        if ui.small_button("↩").on_hover_text("Восстановить").clicked() {
+                            actions.trash(TrashAction::RestoreNote(item.note.id));
+                        }
+                        if ui.small_button("✖").on_hover_text("Удалить навсегда").clicked() {
+                            actions.trash(TrashAction::PurgeNote(item.note.id));
+                        }
+                    });
+                }
+                
+                if ui.small_button("Очистить корзину").clicked() {
+                    actions.trash(TrashAction::Empty);
+                }
+            });
+        
+        ui.add_space(10.0);
+    }
+    
+    /// Отображение результатов поиска с подсветкой совпадений
+    fn show_search_results(
+        ui_state: &UiState,
+        ui: &mut egui::Ui,
+        colors: &ThemeColors,
+        panel_width: f32,
+        actions: &mut SidePanelActions,
+    ) {
+        let results = &ui_state.search_results;
+        
+        ui.label(
+            egui::RichText::new(format!("Найдено: {}", results.len()))
+                .color(colors.header)
+                .strong()
+        );
+        ui.add_space(6.0);
+        
+        if results.is_empty() {
+            ui.label(egui::RichText::new("Ничего не найдено").color(colors.text));
+            return;
+        }
+        
+        for result in results {
+            let is_selected = ui_state.selected_note == Some(result.note_id);
+            
+            let frame = egui::Frame::new()
+                .inner_margin(egui::Margin::same(4))
+                .corner_radius(4)
+                .fill(if is_selected { colors.button_bg } else { egui::Color32::TRANSPARENT });
+            
+            let response = frame.show(ui, |ui| {
+                ui.set_width(panel_width - 28.0);
+                
+                let title = UiComponents::highlighted_text(
+                    &result.title, &result.title_matches, colors.header, colors.highlight, 14.0
+                );
+                ui.horizontal(|ui| {
+                    if result.pinned {
+                        ui.label("📌");
+                    }
+                    ui.add(egui::Label::new(title).selectable(false));
+                });
+                
+                if !result.snippet.is_empty() {
+                    let snippet = UiComponents::highlighted_text(
+                        &result.snippet, &result.snippet_matches, colors.text, colors.highlight, 12.0
+                    );
+                    ui.add(egui::Label::new(snippet).selectable(false).truncate());
+                }
+            }).response;
+            
+            if response.interact(egui::Sense::click()).clicked() {
+                actions.select_search_result(result.note_id);
+            }
+            ui.add_space(4.0);
+        }
+    }
+    
     /// Упрощенное отображение групп и заметок
     fn show_groups_and_notes_simple(
         app_state: &AppState,
@@ -235,9 +677,9 @@ impl PanelManager {
             ui.add_space(depth as f32 * 15.0);
         }
         
-        let note_indices: Vec<usize> = app_state.notes.iter().enumerate()
-            .filter(|(_, n)| n.group_id == Some(group_id))
-            .map(|(i, _)| i)
+        let tag_filter = ui_state.tag_filter.as_deref();
+        let note_indices: Vec<usize> = app_state.group_note_indices(Some(group_id)).into_iter()
+            .filter(|&i| Self::matches_tag_filter(&app_state.notes[i], tag_filter))
             .collect();
         
         let header = egui::CollapsingHeader::new(
@@ -250,23 +692,7 @@ impl PanelManager {
             .show(ui, |ui| {
                 // Показываем заметки в этой группе
                 for idx in note_indices {
-                    if let Some(note) = app_state.notes.get(idx) {
-                        let is_selected = ui_state.selected_note == Some(idx);
-                        let mut title = String::new();
-                        if note.pinned { 
-                            title.push_str("📌 "); 
-                        }
-                        title.push_str(&note.title);
-                        
-                        let label = egui::SelectableLabel::new(is_selected, 
-                            egui::RichText::new(title).color(colors.text)
-                        );
-                        
-                        if ui.add_sized([panel_width - 20.0, 28.0], label).clicked() {
-                            actions.select_note(idx);
-                        }
-                        ui.add_space(4.0);
-                    }
+                    Self::show_note_row(app_state, ui_state, ui, colors, idx, panel_width, actions);
                 }
                 
                 // Показываем подгруппы
@@ -285,7 +711,39 @@ impl PanelManager {
                 }
             });
         
-        if header.header_response.clicked() {
+        // Заголовок можно перетащить в другую группу, а на него - заметку или группу
+        let header_response = header.header_response.interact(egui::Sense::drag());
+        header_response.dnd_set_drag_payload(DragItem::Group(group_id));
+        if let Some(item) = header_response.dnd_hover_payload::<DragItem>()
+            && *item != DragItem::Group(group_id)
+        {
+            ui.painter().rect_stroke(header_response.rect, 2.0, egui::Stroke::new(2.0, colors.highlight), egui::StrokeKind::Inside);
+        }
+        if let Some(item) = header_response.dnd_release_payload::<DragItem>() {
+            match *item {
+                DragItem::Note(note_id) => actions.move_note(note_id, Some(group_id), None),
+                DragItem::Group(dragged_id) if dragged_id != group_id => actions.move_group(dragged_id, Some(group_id)),
+                DragItem::Group(_) => {}
+            }
+        }
+        
+        // Порядок заметок в группе выбирается в контекстном меню заголовка
+        header_response.context_menu(|ui| {
+            let current = app_state.groups.iter().find(|g| g.id == group_id).and_then(|g| g.sort_mode);
+            ui.label("Сортировка заметок:");
+            if ui.radio(current.is_none(), "Как в настройках").clicked() {
+                actions.set_group_sort(group_id, None);
+                ui.close_menu();
+            }
+            for mode in NoteSortMode::ALL {
+                if ui.radio(current == Some(mode), mode.label()).clicked() {
+                    actions.set_group_sort(group_id, Some(mode));
+                    ui.close_menu();
+                }
+            }
+        });
+        
+        if header_response.clicked() {
             return Some(group_id);
         }
         
@@ -301,49 +759,107 @@ impl PanelManager {
         panel_width: f32,
         actions: &mut SidePanelActions,
     ) {
-        let no_group_notes: Vec<_> = app_state.notes.iter().enumerate()
-            .filter(|(_, n)| n.group_id.is_none())
+        let tag_filter = ui_state.tag_filter.as_deref();
+        let no_group_notes: Vec<usize> = app_state.group_note_indices(None).into_iter()
+            .filter(|&i| Self::matches_tag_filter(&app_state.notes[i], tag_filter))
             .collect();
+        // Во время перетаскивания раздел нужен как цель, даже если он пуст
+        let dragging = egui::DragAndDrop::has_payload_of_type::<DragItem>(ui.ctx());
         
-        if !no_group_notes.is_empty() {
-            egui::CollapsingHeader::new(
+        if !no_group_notes.is_empty() || dragging {
+            let header = egui::CollapsingHeader::new(
                 egui::RichText::new("Без группы")
                     .color(colors.header)
                     .strong()
             )
                 .default_open(true)
                 .show(ui, |ui| {
-                    for (idx, note) in no_group_notes {
-                        let is_selected = ui_state.selected_note == Some(idx);
-                        let mut title = String::new();
-                        if note.pinned { 
-                            title.push_str("📌 "); 
-                        }
-                        title.push_str(&note.title);
-                        
-                        let label = egui::SelectableLabel::new(is_selected, 
-                            egui::RichText::new(title).color(colors.text)
-                        );
-                        
-                        if ui.add_sized([panel_width - 20.0, 28.0], label).clicked() {
-                            actions.select_note(idx);
-                        }
-                        ui.add_space(4.0);
+                    for idx in no_group_notes {
+                        Self::show_note_row(app_state, ui_state, ui, colors, idx, panel_width, actions);
                     }
                 });
+            
+            // Сюда заметки переносятся из групп, а группы становятся корневыми
+            let header_response = header.header_response;
+            if header_response.dnd_hover_payload::<DragItem>().is_some() {
+                ui.painter().rect_stroke(header_response.rect, 2.0, egui::Stroke::new(2.0, colors.highlight), egui::StrokeKind::Inside);
+            }
+            if let Some(item) = header_response.dnd_release_payload::<DragItem>() {
+                match *item {
+                    DragItem::Note(note_id) => actions.move_note(note_id, None, None),
+                    DragItem::Group(group_id) => actions.move_group(group_id, None),
+                }
+            }
         }
     }
     
+    /// Строка заметки в списке группы. Заметку можно перетащить; в ручном порядке
+    /// брошенная на строку заметка встает перед ней, иначе просто переходит в ее группу.
+    fn show_note_row(
+        app_state: &AppState,
+        ui_state: &UiState,
+        ui: &mut egui::Ui,
+        colors: &ThemeColors,
+        idx: usize,
+        panel_width: f32,
+        actions: &mut SidePanelActions,
+    ) {
+        let Some(note) = app_state.notes.get(idx) else {
+            return;
+        };
+        let is_selected = ui_state.selected_note == Some(note.id) || ui_state.selected_notes.contains(&note.id);
+        let manual = app_state.effective_sort_mode(note.group_id) == NoteSortMode::Manual;
+        let mut title = String::new();
+        if note.pinned { 
+            title.push_str("📌 "); 
+        }
+        title.push_str(&note.title);
+        
+        let label = egui::SelectableLabel::new(is_selected, 
+            egui::RichText::new(title).color(colors.text)
+        );
+        
+        let response = ui.add_sized([panel_width - 20.0, 28.0], label).interact(egui::Sense::drag());
+        if response.clicked() {
+            // Ctrl-клик добавляет заметку к выбору, Shift-клик выбирает диапазон
+            let modifiers = ui.input(|i| i.modifiers);
+            if modifiers.shift {
+                actions.select_note_range(note.id);
+            } else if modifiers.command {
+                actions.toggle_note_selection(note.id);
+            } else {
+                actions.select_note(note.id);
+            }
+        }
+        response.dnd_set_drag_payload(DragItem::Note(note.id));
+        
+        if let Some(item) = response.dnd_hover_payload::<DragItem>()
+            && matches!(*item, DragItem::Note(id) if id != note.id)
+        {
+            let stroke = egui::Stroke::new(2.0, colors.highlight);
+            if manual {
+                ui.painter().hline(response.rect.x_range(), response.rect.top(), stroke);
+            } else {
+                ui.painter().rect_stroke(response.rect, 2.0, stroke, egui::StrokeKind::Inside);
+            }
+        }
+        if let Some(item) = response.dnd_release_payload::<DragItem>()
+            && let DragItem::Note(dragged_id) = *item
+        {
+            actions.move_note(dragged_id, note.group_id, manual.then_some(note.id));
+        }
+        ui.add_space(4.0);
+    }
+    
     /// Упрощенный редактор заметки
     fn show_note_editor_simple(
         app_state: &AppState,
         ui_state: &mut UiState,
         ui: &mut egui::Ui,
-        idx: usize,
+        note: &Note,
         colors: &ThemeColors,
         actions: &mut CentralPanelActions,
     ) {
-        let note = &app_state.notes[idx];
         
         ui.add_space(6.0);
         
@@ -355,10 +871,10 @@ impl PanelManager {
                 .on_hover_text(if note.pinned { "Открепить" } else { "Закрепить" })
                 .clicked() 
             {
-                actions.toggle_pin(idx);
+                actions.toggle_pin(note.id);
             }
             
-            if ui_state.editing_title == Some(idx) {
+            if ui_state.editing_title == Some(note.id) {
                 let mut title = note.title.clone();
                 let response = UiComponents::single_line_text_edit(
                     ui, 
@@ -372,14 +888,14 @@ impl PanelManager {
                    response.lost_focus() && !response.has_focus() 
                 {
                     if !title.trim().is_empty() {
-                        actions.update_title(idx, title.clone());
+                        actions.update_title(note.id, title.clone());
                     }
                     ui_state.editing_title = None;
                 }
                 
                 if ui.add(egui::Button::new("✔️")).on_hover_text("Сохранить").clicked() {
                     if !title.trim().is_empty() {
-                        actions.update_title(idx, title.clone());
+                        actions.update_title(note.id, title.clone());
                     }
                     ui_state.editing_title = None;
                 }
@@ -393,15 +909,35 @@ impl PanelManager {
                     .on_hover_text("Редактировать заголовок")
                     .clicked() 
                 {
-                    ui_state.editing_title = Some(idx);
+                    ui_state.editing_title = Some(note.id);
                 }
             }
         });
         
         ui.add_space(6.0);
         
+        // Теги заметки
+        Self::show_note_tags_editor(note, ui_state, ui, colors, actions);
+        
+        ui.add_space(6.0);
+        
+        // Группа заметки
+        ui.horizontal(|ui| {
+            ui.label("📁");
+            UiComponents::group_selector(
+                ui,
+                note.group_id,
+                &app_state.groups,
+                "note_group_select",
+                |group_id| actions.move_to_group(note.id, group_id),
+                20
+            );
+        });
+        
+        ui.add_space(6.0);
+        
         // Содержимое заметки
-        if ui_state.editing_content == Some(idx) {
+        if ui_state.editing_content == Some(note.id) {
             // Режим редактирования
             UiComponents::multiline_text_edit(
                 ui, 
@@ -420,7 +956,7 @@ impl PanelManager {
         
         // Кнопки действий
         ui.horizontal(|ui| {
-            if ui_state.editing_content == Some(idx) {
+            if ui_state.editing_content == Some(note.id) {
                 // Режим редактирования
                 if ui.add_sized([80.0, 32.0], egui::Button::new("💾 Сохранить")).clicked() {
                     actions.save_note();
@@ -442,13 +978,154 @@ impl PanelManager {
                 ui.add_space(8.0);
                 
                 if ui.add_sized([110.0, 32.0], egui::Button::new("📝 Редактировать")).clicked() {
-                    ui_state.editing_content = Some(idx);
+                    ui_state.editing_content = Some(note.id);
                 }
                 ui.add_space(8.0);
                 
                 if ui.add_sized([80.0, 32.0], egui::Button::new("🗑 Удалить")).clicked() {
                     actions.delete_note();
                 }
+                ui.add_space(8.0);
+                
+                let history_label = if ui_state.show_history { "🕘 Скрыть историю" } else { "🕘 История" };
+                if ui.add_sized([110.0, 32.0], egui::Button::new(history_label)).clicked() {
+                    ui_state.show_history = !ui_state.show_history;
+                    ui_state.selected_revision = None;
+                }
+            }
+        });
+        
+        if ui_state.show_history && ui_state.editing_content != Some(note.id) {
+            ui.add_space(8.0);
+            Self::show_note_history(note, ui_state, ui, colors, actions);
+        }
+    }
+    
+    /// Панель истории версий: список версий и построчное сравнение выбранной версии с текущей
+    fn show_note_history(
+        note: &Note,
+        ui_state: &mut UiState,
+        ui: &mut egui::Ui,
+        colors: &ThemeColors,
+        actions: &mut CentralPanelActions,
+    ) {
+        ui.group(|ui| {
+            ui.label(egui::RichText::new("🕘 История версий").strong());
+            ui.add_space(4.0);
+            
+            if ui_state.history_revisions.is_empty() {
+                ui.label("Предыдущих версий пока нет");
+                return;
+            }
+            
+            egui::ScrollArea::vertical()
+                .id_salt("history_list")
+                .max_height(120.0)
+                .show(ui, |ui| {
+                    for revision in &ui_state.history_revisions {
+                        let label = format!(
+                            "{} — {}",
+                            revision.saved_at.with_timezone(&chrono::Local).format("%d.%m.%Y %H:%M:%S"),
+                            revision.title
+                        );
+                        let selected = ui_state.selected_revision == Some(revision.id);
+                        if ui.selectable_label(selected, label).clicked() {
+                            ui_state.selected_revision = Some(revision.id);
+                        }
+                    }
+                });
+            
+            let Some(revision) = ui_state.selected_revision
+                .and_then(|id| ui_state.history_revisions.iter().find(|r| r.id == id))
+            else {
+                ui.label("Выберите версию, чтобы сравнить её с текущей");
+                return;
+            };
+            
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Изменения от выбранной версии к текущей:");
+                if ui.button("↩ Восстановить эту версию").clicked() {
+                    actions.restore_revision(note.id, revision.id);
+                }
+            });
+            
+            if revision.title != note.title {
+                ui.label(format!("Заголовок: «{}» → «{}»", revision.title, note.title));
+            }
+            
+            egui::ScrollArea::vertical()
+                .id_salt("history_diff")
+                .max_height(250.0)
+                .show(ui, |ui| {
+                    for line in diff_lines(&revision.content, &note.content) {
+                        let (prefix, text, background) = match &line {
+                            DiffLine::Unchanged(text) => ("  ", text, egui::Color32::TRANSPARENT),
+                            DiffLine::Added(text) => ("+ ", text, colors.diff_added),
+                            DiffLine::Removed(text) => ("- ", text, colors.diff_removed),
+                        };
+                        ui.label(
+                            egui::RichText::new(format!("{}{}", prefix, text))
+                                .monospace()
+                                .color(colors.text)
+                                .background_color(background)
+                        );
+                    }
+                });
+        });
+    }
+    
+    /// Редактирование тегов заметки: удаление по клику на "✖", добавление через поле ввода
+    fn show_note_tags_editor(
+        note: &Note,
+        ui_state: &mut UiState,
+        ui: &mut egui::Ui,
+        colors: &ThemeColors,
+        actions: &mut CentralPanelActions,
+    ) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("🏷");
+            
+            for tag in &note.tags {
+                egui::Frame::new()
+                    .fill(colors.button_bg)
+                    .stroke(egui::Stroke::new(1.0, colors.button_border))
+                    .corner_radius(8)
+                    .inner_margin(egui::Margin::symmetric(6, 2))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new(format!("#{}", tag)).color(colors.text));
+                            if ui.add(egui::Label::new("✖").sense(egui::Sense::click()))
+                                .on_hover_text("Удалить тег")
+                                .clicked()
+                            {
+                                let tags = note.tags.iter()
+                                    .filter(|t| *t != tag)
+                                    .cloned()
+                                    .collect();
+                                actions.update_tags(note.id, tags);
+                            }
+                        });
+                    });
+            }
+            
+            let response = UiComponents::single_line_text_edit(
+                ui,
+                &mut ui_state.new_tag_input,
+                "Новый тег",
+                120.0,
+                20.0
+            );
+            
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if (submitted || ui.small_button("+").on_hover_text("Добавить тег").clicked())
+                && !ui_state.new_tag_input.trim().is_empty()
+            {
+                let mut tags = note.tags.clone();
+                // Через запятую можно добавить сразу несколько тегов
+                tags.extend(ui_state.new_tag_input.split(',').map(|t| t.to_string()).filter(|t| !t.trim().is_empty()));
+                actions.update_tags(note.id, tags);
+                ui_state.new_tag_input.clear();
             }
         });
     }
@@ -462,28 +1139,31 @@ impl PanelManager {
     ) {
         ui.vertical(|ui| {
             // Поле заголовка
-            UiComponents::text_field_frame().show(ui, |ui| {
+            let title_changed = UiComponents::text_field_frame().show(ui, |ui| {
                 UiComponents::single_line_text_edit(
                     ui,
                     &mut ui_state.new_note_title,
                     "Заголовок заметки",
                     ui.available_width(),
                     28.0
-                );
-            });
+                )
+            }).inner.changed();
+            UiComponents::field_errors(ui, &ui_state.note_form_errors, FormField::Title);
             
             ui.add_space(10.0);
             
             // Поле содержимого
-            UiComponents::multiline_text_edit(
+            let content_changed = UiComponents::multiline_text_edit(
                 ui,
                 &mut ui_state.new_note_content,
                 "Текст заметки..."
-            );
+            ).changed();
+            UiComponents::field_errors(ui, &ui_state.note_form_errors, FormField::Content);
             
             ui.add_space(10.0);
             
             // Выбор группы для новой заметки
+            let mut group_changed = false;
             ui.horizontal(|ui| {
                 ui.label("Группа:");
                 UiComponents::group_selector(
@@ -491,10 +1171,22 @@ impl PanelManager {
                     ui_state.new_note_group_id,
                     &app_state.groups,
                     "new_note_group_select",
-                    |group_id| ui_state.new_note_group_id = group_id,
+                    |group_id| {
+                        ui_state.new_note_group_id = group_id;
+                        group_changed = true;
+                    },
                     20
                 );
             });
+            UiComponents::field_errors(ui, &ui_state.note_form_errors, FormField::Group);
+            
+            // Исправленное поле больше не помечается ошибкой
+            ui_state.note_form_errors.retain(|error| match error.field() {
+                FormField::Title => !title_changed,
+                FormField::Content => !content_changed,
+                FormField::Group => !group_changed,
+                _ => true,
+            });
             
             ui.add_space(16.0);
             
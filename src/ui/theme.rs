@@ -1,6 +1,7 @@
 use eframe::egui;
+use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ThemeMode {
     Auto,    // Следовать системной теме
     Light,   // Светлая тема
@@ -47,6 +48,10 @@ pub struct ThemeColors {
     pub central_bg: egui::Color32,
     pub button_bg: egui::Color32,
     pub button_border: egui::Color32,
+    pub highlight: egui::Color32,
+    /// Фон добавленных и удалённых строк при сравнении версий
+    pub diff_added: egui::Color32,
+    pub diff_removed: egui::Color32,
 }
 
 impl ThemeColors {
@@ -58,6 +63,9 @@ impl ThemeColors {
             central_bg: egui::Color32::from_gray(35),
             button_bg: egui::Color32::from_gray(50),
             button_border: egui::Color32::from_gray(80),
+            highlight: egui::Color32::from_rgb(120, 90, 0),
+            diff_added: egui::Color32::from_rgb(30, 75, 35),
+            diff_removed: egui::Color32::from_rgb(90, 35, 35),
         }
     }
     
@@ -69,6 +77,9 @@ impl ThemeColors {
             central_bg: egui::Color32::WHITE,
             button_bg: egui::Color32::from_gray(220),
             button_border: egui::Color32::from_gray(160),
+            highlight: egui::Color32::from_rgb(255, 230, 120),
+            diff_added: egui::Color32::from_rgb(210, 245, 210),
+            diff_removed: egui::Color32::from_rgb(250, 215, 215),
         }
     }
     
@@ -1,8 +1,17 @@
+use std::time::Instant;
 use eframe::egui;
 use uuid::Uuid;
-use crate::notes::Note;
+use crate::backup::RestoreMode;
+use crate::import::ImportSource;
+use crate::notes::{Note, NoteSortMode};
+use crate::notifications::{Notification, NotificationLevel};
 use crate::state::{AppState, UiState, LoadMode};
-use crate::ui::{UiComponents, ThemeMode, SettingsActions};
+use crate::storage::StorageBackend;
+use crate::quick_picker::{self, QUICK_PICKER_FLAG, QUICK_PICKER_LIMIT};
+use crate::snippet::CopyTarget;
+use crate::ui::{theme::ThemeColors, UiComponents, ThemeMode, QuickPickerActions, SettingsActions, SnippetFormActions, TagEditorActions};
+use crate::ui::keymap::{self, Keymap, KeyBinding, ShortcutAction};
+use crate::validation::FormField;
 
 /// Управление всеми окнами приложения
 pub struct WindowManager;
@@ -13,6 +22,7 @@ impl WindowManager {
     pub fn show_settings_window(
         app_state: &AppState,
         ui_state: &mut UiState,
+        keymap: &Keymap,
         ctx: &egui::Context,
     ) -> SettingsActions {
         let mut actions = SettingsActions::new();
@@ -47,6 +57,21 @@ impl WindowManager {
                         ui.separator();
                         ui.add_space(15.0);
                         
+                        // Секция сортировки
+                        ui.heading("↕ Сортировка заметок");
+                        ui.add_space(10.0);
+                        
+                        for mode in NoteSortMode::ALL {
+                            if ui.radio_value(&mut ui_state.note_sort_mode, mode, mode.label()).clicked() {
+                                actions.change_sort_mode(mode);
+                            }
+                        }
+                        ui.label("Закреплённые заметки всегда выше. Для отдельной группы порядок задаётся правым кликом по её заголовку.");
+                        
+                        ui.add_space(20.0);
+                        ui.separator();
+                        ui.add_space(15.0);
+                        
                         // Секция производительности
                         ui.heading("⚡ Производительность");
                         ui.add_space(10.0);
@@ -70,6 +95,26 @@ impl WindowManager {
                         
                         ui.add_space(10.0);
                         
+                        ui.label("Хранилище заметок (применяется после перезапуска):");
+                        ui.add_space(5.0);
+                        if ui.radio_value(&mut ui_state.preferred_storage_backend, StorageBackend::Json, "📄 JSON-файлы").clicked() {
+                            actions.change_storage_backend(ui_state.preferred_storage_backend);
+                        }
+                        if ui.radio_value(&mut ui_state.preferred_storage_backend, StorageBackend::Sqlite, "🗄 SQLite (для больших коллекций)").clicked() {
+                            actions.change_storage_backend(ui_state.preferred_storage_backend);
+                        }
+                        
+                        ui.add_space(10.0);
+                        
+                        ui.horizontal(|ui| {
+                            ui.label("🗑 Хранить удалённое в корзине (дней, 0 - бессрочно):");
+                            if ui.add(egui::DragValue::new(&mut ui_state.trash_retention_days).range(0..=3650)).changed() {
+                                actions.change_trash_retention(ui_state.trash_retention_days);
+                            }
+                        });
+                        
+                        ui.add_space(10.0);
+                        
                         let old_stats = ui_state.show_performance_stats;
                         if ui.checkbox(&mut ui_state.show_performance_stats, "📊 Показывать статистику производительности").clicked() {
                             if old_stats != ui_state.show_performance_stats {
@@ -137,6 +182,19 @@ impl WindowManager {
                             ui.label("⚡ Ленивая загрузка: Загружает заметки по мере необходимости (для больших коллекций)");
                         });
                         
+                        ui.add_space(20.0);
+                        ui.separator();
+                        ui.add_space(15.0);
+                        
+                        // Секция горячих клавиш
+                        Self::show_shortcuts_section(ui_state, keymap, ui, &mut actions);
+                        
+                        ui.add_space(20.0);
+                        ui.separator();
+                        ui.add_space(15.0);
+                        
+                        Self::show_data_section(ui_state, ui, &mut actions);
+                        
                         ui.add_space(20.0);
                         ui.separator();
                         ui.add_space(10.0);
@@ -151,7 +209,205 @@ impl WindowManager {
         actions
     }
     
-    /// Отображает окно создания группы
+    /// Экспорт и импорт данных
+    fn show_data_section(
+        ui_state: &mut UiState,
+        ui: &mut egui::Ui,
+        actions: &mut SettingsActions,
+    ) {
+        ui.heading("📦 Данные");
+        ui.add_space(10.0);
+        
+        ui.label("Экспорт в Markdown (группы - папки, заметки - .md файлы):");
+        ui.horizontal(|ui| {
+            UiComponents::single_line_text_edit(ui, &mut ui_state.export_path, "Папка для экспорта", 320.0, 24.0);
+            if ui.button("📤 Экспортировать").clicked() && !ui_state.export_path.trim().is_empty() {
+                actions.export_markdown(std::path::PathBuf::from(ui_state.export_path.trim()));
+            }
+        });
+        
+        if let Some(ref status) = ui_state.export_status {
+            ui.label(status);
+        }
+        
+        ui.add_space(15.0);
+        ui.label("Импорт заметок (папки и блокноты станут группами):");
+        ui.horizontal_wrapped(|ui| {
+            for source in ImportSource::ALL {
+                ui.radio_value(&mut ui_state.import_source, source, source.label());
+            }
+        });
+        let hint = match ui_state.import_source {
+            ImportSource::MarkdownFolder => "Папка с .md и .txt файлами",
+            ImportSource::Obsidian => "Папка хранилища Obsidian",
+            ImportSource::JoplinRaw => "Папка экспорта Joplin (RAW)",
+            ImportSource::Enex => "Файл .enex или папка с ними",
+        };
+        ui.horizontal(|ui| {
+            UiComponents::single_line_text_edit(ui, &mut ui_state.import_path, hint, 320.0, 24.0);
+            if ui.button("📥 Импортировать").clicked() && !ui_state.import_path.trim().is_empty() {
+                actions.import_notes(ui_state.import_source, std::path::PathBuf::from(ui_state.import_path.trim()));
+            }
+        });
+        
+        if let Some(ref status) = ui_state.import_status {
+            ui.label(status);
+        }
+        if !ui_state.import_skipped.is_empty() {
+            ui.collapsing(format!("Пропущено файлов: {}", ui_state.import_skipped.len()), |ui| {
+                egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                    for line in &ui_state.import_skipped {
+                        ui.label(line);
+                    }
+                });
+            });
+        }
+        
+        ui.add_space(15.0);
+        ui.label("Резервная копия (все заметки, группы, постоянный текст и настройки в одном архиве):");
+        ui.horizontal(|ui| {
+            UiComponents::single_line_text_edit(ui, &mut ui_state.backup_path, "Файл копии (.zip)", 320.0, 24.0);
+            if ui.button("💾 Создать копию").clicked() && !ui_state.backup_path.trim().is_empty() {
+                actions.create_backup(std::path::PathBuf::from(ui_state.backup_path.trim()));
+            }
+        });
+        if let Some(ref status) = ui_state.backup_status {
+            ui.label(status);
+        }
+        ui.horizontal(|ui| {
+            ui.label("Ежедневных копий хранить (0 - не создавать):");
+            if ui.add(egui::DragValue::new(&mut ui_state.daily_backups_to_keep).range(0..=365)).changed() {
+                actions.change_daily_backups(ui_state.daily_backups_to_keep);
+            }
+        });
+        
+        ui.add_space(15.0);
+        ui.label("Восстановление из копии:");
+        ui.horizontal(|ui| {
+            UiComponents::single_line_text_edit(ui, &mut ui_state.restore_path, "Файл копии (.zip)", 320.0, 24.0);
+            if ui.button("🔍 Проверить").clicked() && !ui_state.restore_path.trim().is_empty() {
+                actions.preview_restore(std::path::PathBuf::from(ui_state.restore_path.trim()));
+            }
+        });
+        
+        if let Some(ref pending) = ui_state.pending_restore {
+            ui.group(|ui| {
+                ui.label(format!(
+                    "Копия от {} (версия {}): заметок {}, групп {}",
+                    pending.manifest.created_at.with_timezone(&chrono::Local).format("%d.%m.%Y %H:%M"),
+                    pending.manifest.app_version,
+                    pending.manifest.note_count,
+                    pending.manifest.group_count,
+                ));
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut ui_state.restore_mode, RestoreMode::Replace, "Заменить всё");
+                    ui.radio_value(&mut ui_state.restore_mode, RestoreMode::Merge, "Объединить по id");
+                });
+                
+                let preview = match ui_state.restore_mode {
+                    RestoreMode::Replace => &pending.replace_preview,
+                    RestoreMode::Merge => &pending.merge_preview,
+                };
+                ui.label(format!(
+                    "Заметки: +{} новых, {} изменится, {} удалится, {} без изменений",
+                    preview.notes_added, preview.notes_updated, preview.notes_removed, preview.notes_unchanged
+                ));
+                ui.label(format!("Группы: +{} новых, {} удалится", preview.groups_added, preview.groups_removed));
+                if preview.persistent_text_changed {
+                    ui.label("Постоянный текст будет заменён");
+                }
+                if ui_state.restore_mode == RestoreMode::Replace && pending.has_settings {
+                    ui.label("Настройки будут заменены настройками из копии");
+                }
+                
+                ui.horizontal(|ui| {
+                    if ui.button("♻ Восстановить").clicked() {
+                        actions.restore_backup(pending.path.clone(), ui_state.restore_mode);
+                    }
+                    if ui.button("Отмена").clicked() {
+                        actions.cancel_restore();
+                    }
+                });
+            });
+        }
+        if let Some(ref status) = ui_state.restore_status {
+            ui.label(status);
+        }
+    }
+    
+    /// Отображает список горячих клавиш с возможностью переназначения
+    fn show_shortcuts_section(
+        ui_state: &mut UiState,
+        keymap: &Keymap,
+        ui: &mut egui::Ui,
+        actions: &mut SettingsActions,
+    ) {
+        ui.heading("⌨ Горячие клавиши");
+        ui.add_space(10.0);
+        
+        // Ожидаем нажатие нового сочетания для выбранного действия
+        if let Some(action) = ui_state.capturing_shortcut {
+            let pressed = ui.input(|i| {
+                i.events.iter().find_map(|event| keymap::key_press(event, i.modifiers))
+            });
+            
+            if let Some((key, modifiers)) = pressed {
+                if key != egui::Key::Escape {
+                    actions.change_shortcut(action, KeyBinding::new(key, modifiers));
+                }
+                ui_state.capturing_shortcut = None;
+            }
+        }
+        
+        egui::Grid::new("shortcuts_grid")
+            .num_columns(3)
+            .spacing([12.0, 6.0])
+            .show(ui, |ui| {
+                for action in ShortcutAction::ALL {
+                    ui.label(action.label());
+                    
+                    if ui_state.capturing_shortcut == Some(action) {
+                        ui.colored_label(egui::Color32::from_rgb(200, 100, 0), "Нажмите сочетание...");
+                        if ui.small_button("Отмена").clicked() {
+                            ui_state.capturing_shortcut = None;
+                        }
+                    } else {
+                        ui.monospace(keymap.binding(action).to_string());
+                        if ui.small_button("Изменить").clicked() {
+                            ui_state.capturing_shortcut = Some(action);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        
+        ui.add_space(5.0);
+        if ui.button("Сбросить по умолчанию").clicked() {
+            ui_state.capturing_shortcut = None;
+            actions.reset_shortcuts();
+        }
+        
+        // Глобальное сочетание работает, даже когда окно свернуто или не в фокусе
+        ui.add_space(10.0);
+        ui.label("Быстрый выбор заметки (глобально):");
+        ui.horizontal(|ui| {
+            UiComponents::text_field_frame().show(ui, |ui| {
+                UiComponents::single_line_text_edit(ui, &mut ui_state.quick_picker_hotkey, "Ctrl+Alt+Space", 150.0, 20.0);
+            });
+            if ui.button("Применить").clicked() {
+                actions.change_quick_picker_hotkey(ui_state.quick_picker_hotkey.clone());
+            }
+        });
+        if let Some(status) = &ui_state.quick_picker_hotkey_status {
+            ui.label(status);
+        }
+        ui.label(format!(
+            "Пустое поле отключает сочетание. На Wayland назначьте команду `fast-bind {}` в настройках системы.",
+            QUICK_PICKER_FLAG
+        ));
+    }
+    
+    /// Отображает окно создания группы; форму очищает приложение, когда группа создана
     pub fn show_group_creation_window(
         app_state: &AppState,
         ui_state: &mut UiState,
@@ -181,15 +437,19 @@ impl WindowManager {
                     }
 
                     ui.label("Имя группы:");
-                    UiComponents::text_field_frame().show(ui, |ui| {
+                    let name_changed = UiComponents::text_field_frame().show(ui, |ui| {
                         UiComponents::single_line_text_edit(
                             ui,
                             &mut ui_state.new_group_name,
                             "Название группы",
                             ui.available_width(),
                             24.0
-                        );
-                    });
+                        )
+                    }).inner.changed();
+                    if name_changed {
+                        ui_state.group_form_errors.retain(|e| e.field() != FormField::GroupName);
+                    }
+                    UiComponents::field_errors(ui, &ui_state.group_form_errors, FormField::GroupName);
                     ui.add_space(10.0);
                     
                     // Выбор родительской группы (только если не создаём подгруппу)
@@ -200,10 +460,14 @@ impl WindowManager {
                             ui_state.new_note_parent_group_id,
                             &app_state.groups,
                             "parent_group_select",
-                            |group_id| ui_state.new_note_parent_group_id = group_id,
+                            |group_id| {
+                                ui_state.new_note_parent_group_id = group_id;
+                                ui_state.group_form_errors.retain(|e| e.field() != FormField::Group);
+                            },
                             20
                         );
                     }
+                    UiComponents::field_errors(ui, &ui_state.group_form_errors, FormField::Group);
                     
                     ui.separator();
                     
@@ -231,7 +495,7 @@ impl WindowManager {
                     ui.separator();
                     
                     ui.horizontal(|ui| {
-                        if ui.button("Создать").clicked() && !ui_state.new_group_name.trim().is_empty() {
+                        if ui.button("Создать").clicked() {
                             should_create = true;
                         }
                         
@@ -254,7 +518,6 @@ impl WindowManager {
                 parent_id,
                 ui_state.group_creation_selected_notes.clone()
             );
-            ui_state.clear_group_form();
         }
         
         should_create
@@ -385,4 +648,394 @@ impl WindowManager {
         
         (groups_to_save, groups_to_delete)
     }
-} 
\ No newline at end of file
+    
+    /// Отображает окно управления тегами (переименование и объединение)
+    pub fn show_tag_editor_window(
+        app_state: &AppState,
+        ui_state: &mut UiState,
+        ctx: &egui::Context,
+    ) -> TagEditorActions {
+        let mut actions = TagEditorActions::new();
+        
+        if ui_state.show_tag_editor {
+            let tags = app_state.all_tags();
+            
+            egui::Window::new("Управление тегами")
+                .collapsible(false)
+                .resizable(true)
+                .min_width(350.0)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label("Переименование в уже существующий тег объединяет теги.");
+                    ui.separator();
+                    
+                    egui::ScrollArea::vertical()
+                        .max_height(350.0)
+                        .show(ui, |ui| {
+                            if tags.is_empty() {
+                                ui.label("Тегов пока нет");
+                            }
+                            
+                            for (tag, count) in &tags {
+                                ui.horizontal(|ui| {
+                                    let mut selected = ui_state.tag_merge_selection.contains(tag);
+                                    if ui.checkbox(&mut selected, "").on_hover_text("Выбрать для объединения").changed() {
+                                        if selected {
+                                            ui_state.tag_merge_selection.push(tag.clone());
+                                        } else {
+                                            ui_state.tag_merge_selection.retain(|t| t != tag);
+                                        }
+                                    }
+                                    
+                                    if ui_state.editing_tag.as_deref() == Some(tag.as_str()) {
+                                        UiComponents::text_field_frame().show(ui, |ui| {
+                                            UiComponents::single_line_text_edit(
+                                                ui,
+                                                &mut ui_state.editing_tag_name,
+                                                "Новое имя тега",
+                                                150.0,
+                                                20.0
+                                            );
+                                        });
+                                        if ui.small_button("✔️").on_hover_text("Сохранить").clicked() {
+                                            actions.rename(tag.clone(), ui_state.editing_tag_name.clone());
+                                            ui_state.editing_tag = None;
+                                            ui_state.editing_tag_name.clear();
+                                        }
+                                        if ui.small_button("❌").on_hover_text("Отмена").clicked() {
+                                            ui_state.editing_tag = None;
+                                            ui_state.editing_tag_name.clear();
+                                        }
+                                    } else {
+                                        ui.label(format!("#{}", tag));
+                                        ui.label(format!("({} заметок)", count));
+                                        
+                                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                            if ui.small_button("✏️").on_hover_text("Переименовать тег").clicked() {
+                                                ui_state.editing_tag = Some(tag.clone());
+                                                ui_state.editing_tag_name = tag.clone();
+                                            }
+                                        });
+                                    }
+                                });
+                            }
+                        });
+                    
+                    // Объединение выбранных тегов
+                    if ui_state.tag_merge_selection.len() >= 2 {
+                        ui.separator();
+                        ui.label(format!("Объединить выбранные теги ({}) в:", ui_state.tag_merge_selection.len()));
+                        ui.horizontal(|ui| {
+                            UiComponents::text_field_frame().show(ui, |ui| {
+                                UiComponents::single_line_text_edit(
+                                    ui,
+                                    &mut ui_state.tag_merge_target,
+                                    "Итоговый тег",
+                                    150.0,
+                                    20.0
+                                );
+                            });
+                            if ui.button("Объединить").clicked() && !ui_state.tag_merge_target.trim().is_empty() {
+                                actions.merge(
+                                    ui_state.tag_merge_selection.clone(),
+                                    ui_state.tag_merge_target.clone()
+                                );
+                                ui_state.tag_merge_selection.clear();
+                                ui_state.tag_merge_target.clear();
+                            }
+                        });
+                    }
+                    
+                    ui.separator();
+                    
+                    if ui.button("Закрыть").clicked() {
+                        ui_state.show_tag_editor = false;
+                        ui_state.editing_tag = None;
+                        ui_state.tag_merge_selection.clear();
+                    }
+                });
+        }
+        
+        actions
+    }
+    
+    /// Окно ввода значений полей `{{name:...}}` перед копированием заметки
+    pub fn show_snippet_form_window(
+        ui_state: &mut UiState,
+        ctx: &egui::Context,
+    ) -> SnippetFormActions {
+        let mut actions = SnippetFormActions::new();
+        
+        if let Some(form) = ui_state.snippet_form.as_mut() {
+            egui::Window::new("Заполнение полей")
+                .collapsible(false)
+                .resizable(false)
+                .min_width(300.0)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(format!("Заметка: {}", form.note_title));
+                    ui.separator();
+                    
+                    egui::Grid::new("snippet_fields")
+                        .num_columns(2)
+                        .spacing([8.0, 6.0])
+                        .show(ui, |ui| {
+                            let last_index = form.fields.len() - 1;
+                            for (index, (name, value)) in form.fields.iter_mut().enumerate() {
+                                ui.label(format!("{}:", name));
+                                let response = UiComponents::text_field_frame().show(ui, |ui| {
+                                    UiComponents::single_line_text_edit(ui, value, name, 200.0, 20.0)
+                                }).inner;
+                                // Фокус сразу на первое поле, чтобы можно было печатать без мыши
+                                if index == 0 && ui_state.snippet_form_focus {
+                                    response.request_focus();
+                                    ui_state.snippet_form_focus = false;
+                                }
+                                // Enter в последнем поле подтверждает форму
+                                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) && index == last_index {
+                                    actions.submit();
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    
+                    ui.separator();
+                    
+                    ui.horizontal(|ui| {
+                        let submit_label = match form.target {
+                            CopyTarget::Clipboard => "📋 Копировать",
+                            CopyTarget::PersistentText => "📝 В постоянный текст",
+                        };
+                        if ui.button(submit_label).clicked() {
+                            actions.submit();
+                        }
+                        if ui.button("Отмена").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                            actions.cancel();
+                        }
+                    });
+                });
+        }
+        
+        actions
+    }
+    
+    /// Всплывающие уведомления в правом нижнем углу; клик по уведомлению закрывает его
+    pub fn show_toasts(ui_state: &mut UiState, ctx: &egui::Context, colors: &ThemeColors) {
+        let now = Instant::now();
+        let notifications = &mut ui_state.notifications;
+        notifications.expire_toasts(now);
+        let Some(next_expiry) = notifications.next_expiry(now) else {
+            return;
+        };
+        ctx.request_repaint_after(next_expiry);
+        
+        let mut dismissed = Vec::new();
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -12.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_max_width(320.0);
+                for toast in notifications.toasts() {
+                    let response = egui::Frame::new()
+                        .fill(Self::notification_fill(toast.level, colors))
+                        .stroke(egui::Stroke::new(0.7, colors.button_border))
+                        .inner_margin(egui::Margin::same(8))
+                        .corner_radius(6)
+                        .show(ui, |ui| {
+                            ui.set_width(ui.available_width());
+                            ui.label(Self::notification_text(toast));
+                        })
+                        .response
+                        .interact(egui::Sense::click())
+                        .on_hover_text("Закрыть");
+                    if response.clicked() {
+                        dismissed.push(toast.id);
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+        for id in dismissed {
+            notifications.dismiss(id);
+        }
+    }
+    
+    /// Окно истории уведомлений
+    pub fn show_notifications_window(ui_state: &mut UiState, ctx: &egui::Context, colors: &ThemeColors) {
+        if !ui_state.show_notifications {
+            return;
+        }
+        
+        let mut open = true;
+        egui::Window::new("Уведомления")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                // Пока окно открыто, новые уведомления считаются прочитанными
+                ui_state.notifications.mark_read();
+                
+                if ui_state.notifications.history().next().is_none() {
+                    ui.label("Уведомлений нет");
+                    return;
+                }
+                
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    for notification in ui_state.notifications.history() {
+                        egui::Frame::new()
+                            .fill(Self::notification_fill(notification.level, colors))
+                            .inner_margin(egui::Margin::same(6))
+                            .corner_radius(4)
+                            .show(ui, |ui| {
+                                ui.set_width(ui.available_width());
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        egui::RichText::new(notification.time.format("%d.%m %H:%M:%S").to_string())
+                                            .small()
+                                            .weak()
+                                    );
+                                    if let Some(code) = notification.code {
+                                        ui.label(egui::RichText::new(code).small().monospace());
+                                    }
+                                });
+                                ui.label(Self::notification_text(notification));
+                            });
+                        ui.add_space(4.0);
+                    }
+                });
+                
+                ui.separator();
+                if ui.button("🗑 Очистить историю").clicked() {
+                    ui_state.notifications.clear_history();
+                }
+            });
+        ui_state.show_notifications = open;
+    }
+    
+    fn notification_fill(level: NotificationLevel, colors: &ThemeColors) -> egui::Color32 {
+        match level {
+            NotificationLevel::Success => colors.diff_added,
+            NotificationLevel::Info => colors.button_bg,
+            NotificationLevel::Error => colors.diff_removed,
+        }
+    }
+    
+    fn notification_text(notification: &Notification) -> String {
+        let icon = match notification.level {
+            NotificationLevel::Success => "✔",
+            NotificationLevel::Info => "ℹ",
+            NotificationLevel::Error => "❌",
+        };
+        if notification.repeats > 1 {
+            format!("{} {} (×{})", icon, notification.message, notification.repeats)
+        } else {
+            format!("{} {}", icon, notification.message)
+        }
+    }
+    
+    /// Окно быстрого выбора во втором окне (viewport): поиск по названию, стрелки, Enter - копировать, Esc - закрыть.
+    /// Если бэкенд не поддерживает отдельные окна, список показывается внутри главного окна.
+    pub fn show_quick_picker_window(
+        app_state: &AppState,
+        ui_state: &mut UiState,
+        ctx: &egui::Context,
+    ) -> QuickPickerActions {
+        let mut actions = QuickPickerActions::new();
+        if !ui_state.show_quick_picker {
+            return actions;
+        }
+        
+        let viewport_id = egui::ViewportId::from_hash_of("quick_picker");
+        let builder = egui::ViewportBuilder::default()
+            .with_title("Быстрый выбор")
+            .with_inner_size([480.0, 360.0])
+            .with_always_on_top()
+            .with_active(true);
+        
+        ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+            if ctx.input(|i| i.viewport().close_requested()) {
+                actions.close();
+            }
+            
+            if class == egui::ViewportClass::Embedded {
+                egui::Window::new("Быстрый выбор")
+                    .collapsible(false)
+                    .resizable(false)
+                    .default_width(420.0)
+                    .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+                    .show(ctx, |ui| Self::show_quick_picker_contents(app_state, ui_state, ui, &mut actions));
+            } else {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    Self::show_quick_picker_contents(app_state, ui_state, ui, &mut actions);
+                });
+            }
+        });
+        
+        actions
+    }
+    
+    fn show_quick_picker_contents(
+        app_state: &AppState,
+        ui_state: &mut UiState,
+        ui: &mut egui::Ui,
+        actions: &mut QuickPickerActions,
+    ) {
+        let matches = quick_picker::rank_notes(
+            app_state.quick_picker_candidates(),
+            &ui_state.quick_picker_query,
+            QUICK_PICKER_LIMIT,
+        );
+        
+        // Стрелки забираем до поля ввода, чтобы они не двигали курсор
+        let (up, down, enter, escape) = ui.input_mut(|i| (
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+            i.key_pressed(egui::Key::Enter),
+            i.key_pressed(egui::Key::Escape),
+        ));
+        if down && ui_state.quick_picker_selected + 1 < matches.len() {
+            ui_state.quick_picker_selected += 1;
+        }
+        if up {
+            ui_state.quick_picker_selected = ui_state.quick_picker_selected.saturating_sub(1);
+        }
+        
+        let response = UiComponents::text_field_frame().show(ui, |ui| {
+            UiComponents::single_line_text_edit(ui, &mut ui_state.quick_picker_query, "Название или #тег", ui.available_width(), 24.0)
+        }).inner;
+        if ui_state.quick_picker_focus {
+            response.request_focus();
+            ui_state.quick_picker_focus = false;
+        }
+        if response.changed() {
+            ui_state.quick_picker_selected = 0;
+        }
+        ui_state.quick_picker_selected = ui_state.quick_picker_selected.min(matches.len().saturating_sub(1));
+        
+        ui.separator();
+        
+        if matches.is_empty() {
+            ui.label("Ничего не найдено");
+        }
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (index, found) in matches.iter().enumerate() {
+                let selected = index == ui_state.quick_picker_selected;
+                let pin = if found.note.pinned { "📌 " } else { "" };
+                let row = ui.selectable_label(selected, format!("{}{}", pin, found.note.title));
+                if selected && (up || down) {
+                    row.scroll_to_me(None);
+                }
+                if row.clicked() {
+                    actions.copy_note(found.note.id);
+                }
+            }
+        });
+        
+        if enter && let Some(found) = matches.get(ui_state.quick_picker_selected) {
+            actions.copy_note(found.note.id);
+        }
+        if escape {
+            actions.close();
+        }
+    }
+}
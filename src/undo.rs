@@ -0,0 +1,127 @@
+/// Стек отмены и повтора изменений состояния приложения
+use uuid::Uuid;
+use crate::notes::{Note, NoteGroup};
+use crate::trash::{TrashedGroup, TrashedNote};
+
+/// Сколько последних операций можно отменить
+pub const MAX_UNDO_STEPS: usize = 100;
+
+/// Одно атомарное изменение: состояние до и после операции.
+/// Обратное изменение получается перестановкой `before` и `after`.
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// Заметка (`None` - заметки нет в хранилище)
+    Note { before: Option<Note>, after: Option<Note> },
+    /// Список групп целиком (групп мало, проще хранить снимок всей иерархии)
+    Groups { before: Vec<NoteGroup>, after: Vec<NoteGroup> },
+    /// Заметка в корзине (`None` - в корзине её нет)
+    TrashedNote { note_id: Uuid, before: Option<TrashedNote>, after: Option<TrashedNote> },
+    /// Группа в корзине
+    TrashedGroup { group_id: Uuid, before: Option<TrashedGroup>, after: Option<TrashedGroup> },
+}
+
+impl Change {
+    /// Изменение заметки (оба состояния известны)
+    pub fn note(before: Option<&Note>, after: Option<&Note>) -> Self {
+        Change::Note { before: before.cloned(), after: after.cloned() }
+    }
+
+    /// Обратное изменение
+    pub fn inverse(self) -> Self {
+        match self {
+            Change::Note { before, after } => Change::Note { before: after, after: before },
+            Change::Groups { before, after } => Change::Groups { before: after, after: before },
+            Change::TrashedNote { note_id, before, after } => Change::TrashedNote { note_id, before: after, after: before },
+            Change::TrashedGroup { group_id, before, after } => Change::TrashedGroup { group_id, before: after, after: before },
+        }
+    }
+}
+
+/// Пользовательская операция: набор изменений, отменяемых и повторяемых вместе
+#[derive(Debug, Clone)]
+pub struct Command {
+    /// Название операции для интерфейса ("Удаление группы")
+    pub label: String,
+    pub changes: Vec<Change>,
+}
+
+impl Command {
+    pub fn new(label: &str, changes: Vec<Change>) -> Self {
+        Self { label: label.to_string(), changes }
+    }
+
+    /// Обратная операция: изменения отменяются в обратном порядке
+    pub fn inverse(self) -> Self {
+        Self {
+            label: self.label,
+            changes: self.changes.into_iter().rev().map(Change::inverse).collect(),
+        }
+    }
+}
+
+/// Стеки отмены и повтора
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    undo: Vec<Command>,
+    redo: Vec<Command>,
+}
+
+impl UndoStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Запоминает выполненную операцию; новая операция сбрасывает возможность повтора
+    pub fn record(&mut self, command: Command) {
+        if command.changes.is_empty() {
+            return;
+        }
+        self.undo.push(command);
+        let excess = self.undo.len().saturating_sub(MAX_UNDO_STEPS);
+        self.undo.drain(..excess);
+        self.redo.clear();
+    }
+
+    /// Последняя операция для отмены
+    pub fn pop_undo(&mut self) -> Option<Command> {
+        self.undo.pop()
+    }
+
+    /// Последняя отмененная операция для повтора
+    pub fn pop_redo(&mut self) -> Option<Command> {
+        self.redo.pop()
+    }
+
+    /// Возвращает отмененную операцию в стек повтора
+    pub fn push_redo(&mut self, command: Command) {
+        self.redo.push(command);
+    }
+
+    /// Возвращает повторенную операцию в стек отмены, не трогая стек повтора
+    pub fn push_undo(&mut self, command: Command) {
+        self.undo.push(command);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Название операции, которая будет отменена
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.last().map(|command| command.label.as_str())
+    }
+
+    /// Название операции, которая будет повторена
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|command| command.label.as_str())
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
@@ -1,5 +1,6 @@
 /// Модуль для валидации данных приложения
 use uuid::Uuid;
+use crate::error::AppError;
 use crate::notes::NoteGroup;
 
 /// Результат валидации
@@ -23,10 +24,19 @@ impl ValidationResult {
         self.errors.push(error);
         self.is_valid = false;
     }
+    
+    /// `Err(AppError)` со всеми найденными ошибками
+    pub fn into_result(self) -> Result<(), AppError> {
+        if self.is_valid {
+            Ok(())
+        } else {
+            Err(AppError::from_validation(self.errors))
+        }
+    }
 }
 
 /// Типы ошибок валидации
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ValidationError {
     EmptyTitle,
     TitleTooLong(usize),
@@ -36,6 +46,38 @@ pub enum ValidationError {
     CircularGroupDependency(Uuid),
     GroupNotFound(Uuid),
     InvalidGroupHierarchy,
+    EmptyTag,
+    TagTooLong(usize),
+    InvalidTagCharacters(String),
+    TooManyTags(usize),
+}
+
+/// Поле формы, рядом с которым показывается ошибка валидации
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormField {
+    Title,
+    Content,
+    GroupName,
+    /// Выбор группы или родительской группы
+    Group,
+    Tags,
+}
+
+impl ValidationError {
+    pub fn field(&self) -> FormField {
+        match self {
+            ValidationError::EmptyTitle | ValidationError::TitleTooLong(_) => FormField::Title,
+            ValidationError::ContentTooLong(_) => FormField::Content,
+            ValidationError::EmptyGroupName | ValidationError::GroupNameTooLong(_) => FormField::GroupName,
+            ValidationError::CircularGroupDependency(_)
+            | ValidationError::GroupNotFound(_)
+            | ValidationError::InvalidGroupHierarchy => FormField::Group,
+            ValidationError::EmptyTag
+            | ValidationError::TagTooLong(_)
+            | ValidationError::InvalidTagCharacters(_)
+            | ValidationError::TooManyTags(_) => FormField::Tags,
+        }
+    }
 }
 
 impl std::fmt::Display for ValidationError {
@@ -49,6 +91,10 @@ impl std::fmt::Display for ValidationError {
             ValidationError::CircularGroupDependency(id) => write!(f, "Обнаружена циклическая зависимость в группе {}", id),
             ValidationError::GroupNotFound(id) => write!(f, "Группа с ID {} не найдена", id),
             ValidationError::InvalidGroupHierarchy => write!(f, "Неверная иерархия групп"),
+            ValidationError::EmptyTag => write!(f, "Тег не может быть пустым"),
+            ValidationError::TagTooLong(len) => write!(f, "Тег слишком длинный: {} символов (максимум 50)", len),
+            ValidationError::InvalidTagCharacters(tag) => write!(f, "Тег '{}' содержит недопустимые символы (пробелы или запятые)", tag),
+            ValidationError::TooManyTags(count) => write!(f, "Слишком много тегов: {} (максимум 20)", count),
         }
     }
 }
@@ -64,6 +110,8 @@ impl ValidationRules {
     pub const MAX_CONTENT_LENGTH: usize = 1_048_576; // 1MB
     pub const MAX_GROUP_NAME_LENGTH: usize = 100;
     pub const MAX_GROUP_DEPTH: u32 = 10;
+    pub const MAX_TAG_LENGTH: usize = 50;
+    pub const MAX_TAGS_PER_NOTE: usize = 20;
     
 
     
@@ -88,6 +136,43 @@ impl ValidationRules {
     
 
     
+    /// Приводит тег к каноническому виду: без пробелов по краям, без ведущего '#', в нижнем регистре
+    pub fn normalize_tag(tag: &str) -> String {
+        tag.trim().trim_start_matches('#').trim().to_lowercase()
+    }
+    
+    /// Валидация одного тега (ожидается уже нормализованный тег)
+    pub fn validate_tag(tag: &str) -> ValidationResult {
+        let mut result = ValidationResult::valid();
+        
+        if tag.is_empty() {
+            result.add_error(ValidationError::EmptyTag);
+        } else if tag.chars().count() > Self::MAX_TAG_LENGTH {
+            result.add_error(ValidationError::TagTooLong(tag.chars().count()));
+        } else if tag.chars().any(|c| c.is_whitespace() || c == ',') {
+            result.add_error(ValidationError::InvalidTagCharacters(tag.to_string()));
+        }
+        
+        result
+    }
+    
+    /// Валидация набора тегов заметки
+    pub fn validate_tags(tags: &[String]) -> ValidationResult {
+        let mut result = ValidationResult::valid();
+        
+        if tags.len() > Self::MAX_TAGS_PER_NOTE {
+            result.add_error(ValidationError::TooManyTags(tags.len()));
+        }
+        
+        for tag in tags {
+            for error in Self::validate_tag(tag).errors {
+                result.add_error(error);
+            }
+        }
+        
+        result
+    }
+    
     /// Валидация данных для создания группы
     pub fn validate_group_creation(name: &str, parent_id: Option<Uuid>, groups: &[NoteGroup]) -> ValidationResult {
         let mut result = ValidationResult::valid();
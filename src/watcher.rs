@@ -0,0 +1,116 @@
+/// Слежение за папкой заметок: правки, синхронизация (например, Syncthing) и удаление файлов
+/// другими программами попадают в приложение без перезапуска
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::notes::GROUPS_FILE_NAME;
+use crate::{log_error, log_info};
+
+/// Сколько ждать тишины после последнего события: файл часто пишется в несколько приемов,
+/// а синхронизация меняет несколько файлов подряд
+pub const SETTLE_DELAY: Duration = Duration::from_millis(300);
+
+/// Изменения папки, накопленные с прошлой проверки
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExternalChanges {
+    /// Заметки, чьи файлы созданы, изменены или удалены
+    pub note_ids: HashSet<Uuid>,
+    pub groups_changed: bool,
+    /// Часть событий потеряна - нужно сверить все заметки
+    pub rescan: bool,
+}
+
+impl ExternalChanges {
+    pub fn is_empty(&self) -> bool {
+        self.note_ids.is_empty() && !self.groups_changed && !self.rescan
+    }
+
+    /// Учитывает файл из события. Временные файлы атомарной записи (`<id>.json.tmp`)
+    /// пропускаются: заметка появится, когда файл переименуют
+    pub fn add_path(&mut self, path: &Path) {
+        if path.file_name().is_some_and(|name| name == GROUPS_FILE_NAME) {
+            self.groups_changed = true;
+        } else if path.extension().is_some_and(|ext| ext == "json")
+            && let Some(note_id) = path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| Uuid::parse_str(stem).ok())
+        {
+            self.note_ids.insert(note_id);
+        }
+    }
+}
+
+/// Итог переноса внешних изменений в `AppState`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncReport {
+    pub created: Vec<Uuid>,
+    pub updated: Vec<Uuid>,
+    pub deleted: Vec<Uuid>,
+    pub groups_changed: bool,
+}
+
+impl SyncReport {
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.updated.is_empty() && self.deleted.is_empty() && !self.groups_changed
+    }
+}
+
+#[derive(Default)]
+struct Pending {
+    changes: ExternalChanges,
+    last_event: Option<Instant>,
+}
+
+/// Слежение за папкой без подпапок (история и корзина не отслеживаются).
+/// События копятся в фоновом потоке `notify` и будят цикл отрисовки, а главный поток забирает их через `take_changes`
+pub struct DataWatcher {
+    _watcher: RecommendedWatcher,
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl DataWatcher {
+    pub fn start(dir: &Path, ctx: &egui::Context) -> Result<Self, AppError> {
+        let pending = Arc::new(Mutex::new(Pending::default()));
+
+        let (handler_pending, ctx) = (pending.clone(), ctx.clone());
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            let mut pending = handler_pending.lock().unwrap_or_else(PoisonError::into_inner);
+            match result {
+                Ok(event) => {
+                    pending.changes.rescan |= event.need_rescan();
+                    for path in &event.paths {
+                        pending.changes.add_path(path);
+                    }
+                }
+                Err(e) => {
+                    log_error!("watch", "data_dir", &e);
+                    pending.changes.rescan = true;
+                }
+            }
+            if !pending.changes.is_empty() {
+                pending.last_event = Some(Instant::now());
+                ctx.request_repaint_after(SETTLE_DELAY);
+            }
+        }).map_err(|e| AppError::Watch(e.to_string()))?;
+        watcher.watch(dir, RecursiveMode::NonRecursive).map_err(|e| AppError::Watch(e.to_string()))?;
+
+        log_info!("start", "watcher", &format!("Слежение за {}", dir.display()));
+        Ok(Self { _watcher: watcher, pending })
+    }
+
+    /// Накопленные изменения, если с последнего события прошло `SETTLE_DELAY`.
+    /// Пока папка меняется, возвращает `None` и просит перерисовать окно позже
+    pub fn take_changes(&self, ctx: &egui::Context) -> Option<ExternalChanges> {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        let elapsed = pending.last_event?.elapsed();
+        if elapsed < SETTLE_DELAY {
+            ctx.request_repaint_after(SETTLE_DELAY - elapsed);
+            return None;
+        }
+        pending.last_event = None;
+        Some(std::mem::take(&mut pending.changes))
+    }
+}
@@ -1,4 +1,8 @@
+use fast_bind::error::AppError;
+use fast_bind::notes::NotesManager;
+use fast_bind::validation::ValidationError;
 use fast_bind::state::AppState;
+use tempfile::TempDir;
 
 mod common;
 use common::*;
@@ -10,7 +14,7 @@ mod tests {
     #[test]
     fn test_create_note_success() {
         // Тест успешного создания заметки
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
 
@@ -23,17 +27,17 @@ mod tests {
     #[test]
     fn test_create_note_empty_title() {
         // Тест создания заметки с пустым заголовком
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_content = "This is a test note".to_string();
         
         let result = app_state.create_note("".to_string(), note_content, None);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(AppError::Validation(ref errors)) if errors == &[ValidationError::EmptyTitle]));
     }
 
     #[test]
     fn test_note_creation_before_deletion() {
         // Тест что заметка была создана перед удалением
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
         
@@ -44,26 +48,26 @@ mod tests {
     #[test]  
     fn test_delete_note_returns_ok() {
         // Тест что удаление заметки возвращает Ok
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
         
         create_test_note(&mut app_state, &note_title, &note_content);
 
-        let res_delete = app_state.delete_note(0);
+        let res_delete = app_state.delete_note(app_state.notes[0].id);
         assert!(res_delete.is_ok());
     }
 
     #[test]
     fn test_delete_note_removes_from_collection() {
         // Тест что заметка действительно удалена из коллекции
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
         
         create_test_note(&mut app_state, &note_title, &note_content);
 
-        let _ = app_state.delete_note(0);
+        let _ = app_state.delete_note(app_state.notes[0].id);
         
         assert_eq!(app_state.notes.len(), 0);
     }
@@ -71,7 +75,7 @@ mod tests {
     #[test]
     fn test_note_creation_before_update() {
         // Тест что заметка была создана перед обновлением
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
         
@@ -82,26 +86,26 @@ mod tests {
     #[test]
     fn test_update_note_returns_ok() {
         // Тест что обновление заметки возвращает Ok
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
         
         create_test_note(&mut app_state, &note_title, &note_content);
 
-        let res_update = app_state.update_note(0, Some(note_title), Some("test".to_string()));
+        let res_update = app_state.update_note(app_state.notes[0].id, Some(note_title), Some("test".to_string()));
         assert!(res_update.is_ok());
     }
 
     #[test]
     fn test_update_note_changes_content() {
         // Тест что содержимое заметки действительно изменилось
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
         
         create_test_note(&mut app_state, &note_title, &note_content);
 
-        let _ = app_state.update_note(0, Some(note_title), Some("test".to_string()));
+        let _ = app_state.update_note(app_state.notes[0].id, Some(note_title), Some("test".to_string()));
 
         assert_eq!(app_state.notes[0].content, "test");
     }
@@ -109,34 +113,85 @@ mod tests {
     #[test]
     fn test_toggle_pin_to_true() {
         // Тест закрепления заметки
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
         
         create_test_note(&mut app_state, &note_title, &note_content);
 
-        let _ = app_state.toggle_pin(0);
+        let _ = app_state.toggle_pin(app_state.notes[0].id);
         assert_eq!(app_state.notes[0].pinned, true);
     }
 
     #[test]
     fn test_toggle_pin_to_false() {
         // Тест открепления заметки
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
         
         create_test_note(&mut app_state, &note_title, &note_content);
 
-        let _ = app_state.toggle_pin(0);
-        let _ = app_state.toggle_pin(0);
+        let _ = app_state.toggle_pin(app_state.notes[0].id);
+        let _ = app_state.toggle_pin(app_state.notes[0].id);
         assert_eq!(app_state.notes[0].pinned, false);
     }
 
+    #[test]
+    fn test_note_id_survives_resort() {
+        // Тест что id заметки остается валидным после пересортировки
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Old", "Content");
+        create_test_note(&mut app_state, "New", "Content");
+        let old_id = app_state.notes.iter().find(|n| n.title == "Old").unwrap().id;
+        let old_index = app_state.note_index(old_id).unwrap();
+
+        app_state.toggle_pin(old_id).unwrap();
+        app_state.update_note(old_id, None, Some("Changed".to_string())).unwrap();
+
+        let new_index = app_state.note_index(old_id).unwrap();
+        assert_ne!(old_index, new_index);
+        assert_eq!(app_state.notes[new_index].id, old_id);
+        let note = app_state.note(old_id).unwrap();
+        assert!(note.pinned);
+        assert_eq!(note.content, "Changed");
+    }
+
+    #[test]
+    fn test_note_index_after_delete() {
+        // Тест что позиции пересчитываются после удаления заметки
+        let mut app_state = create_test_app_state();
+        create_multiple_test_notes(&mut app_state, 3);
+        let ids: Vec<_> = app_state.notes.iter().map(|n| n.id).collect();
+
+        app_state.delete_note(ids[0]).unwrap();
+
+        assert_eq!(app_state.note_index(ids[0]), None);
+        assert_eq!(app_state.note_index(ids[1]), Some(0));
+        assert_eq!(app_state.note_index(ids[2]), Some(1));
+    }
+
+    #[test]
+    fn test_operations_on_unknown_id_change_nothing() {
+        // Тест что операции с несуществующим id завершаются ошибкой и не меняют заметки
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Test Note", "Content");
+        let unknown = uuid::Uuid::new_v4();
+
+        assert!(app_state.toggle_pin(unknown).is_err());
+        assert!(app_state.update_note(unknown, Some("Other".to_string()), None).is_err());
+        assert!(app_state.delete_note(unknown).is_err());
+
+        assert_notes_count(&app_state, 1);
+        assert_eq!(app_state.notes[0].title, "Test Note");
+        assert!(!app_state.notes[0].pinned);
+        assert!(app_state.note(unknown).is_none());
+    }
+
     #[test]
     fn test_sort_notes_preparation() {
         // Тест создания заметок для сортировки
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_content = "This is a test note".to_string();
         
         create_test_note(&mut app_state, "Test Note", &note_content);
@@ -148,7 +203,7 @@ mod tests {
     #[test]
     fn test_sort_notes_alphabetical_order() {
         // Тест сортировки заметок по алфавиту
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_content = "This is a test note".to_string();
         
         create_test_note(&mut app_state, "Test Note", &note_content);
@@ -161,30 +216,30 @@ mod tests {
 
     #[test]
     fn test_get_note_content() {
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let note_title = "Test Note".to_string();
         let note_content = "This is a test note".to_string();
 
         create_test_note(&mut app_state, &note_title, &note_content);
 
-        let content = app_state.get_note_content(0);
+        let content = app_state.get_note_content(app_state.notes[0].id);
 
         assert_eq!(content, Some(note_content));
     }
 
     #[test]
     fn test_get_note_content_invalid_index() {
-        let (app_state, _temp_dir) = create_test_app_state();
+        let app_state = create_test_app_state();
         
         // Пробуем получить содержимое несуществующей заметки
-        let content = app_state.get_note_content(999);
+        let content = app_state.get_note_content(uuid::Uuid::new_v4());
         
         assert_eq!(content, None);
     }
 
     #[test]
     fn test_append_note_to_persistent() {
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         // Устанавливаем начальный текст
         app_state.persistent_text = "Existing text. ".to_string();
@@ -192,7 +247,7 @@ mod tests {
         let note_content = "This is a test note".to_string();
         create_test_note(&mut app_state, "Test Note", &note_content);
 
-        let _ = app_state.append_note_to_persistent(0);
+        let _ = app_state.append_note_to_persistent(app_state.notes[0].id);
 
         assert_eq!(app_state.persistent_text, "Existing text. This is a test note");
     }
@@ -200,10 +255,10 @@ mod tests {
     #[test]
     fn test_append_note_to_persistent_invalid_index_returns_ok() {
         // Тест что добавление несуществующей заметки не падает
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let _initial_text = app_state.persistent_text.clone();
         
-        let result = app_state.append_note_to_persistent(999);
+        let result = app_state.append_note_to_persistent(uuid::Uuid::new_v4());
         
         assert!(result.is_ok());
     }
@@ -211,10 +266,10 @@ mod tests {
     #[test]
     fn test_append_note_to_persistent_invalid_index_text_unchanged() {
         // Тест что текст не изменился при добавлении несуществующей заметки
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let initial_text = app_state.persistent_text.clone();
         
-        let _ = app_state.append_note_to_persistent(999);
+        let _ = app_state.append_note_to_persistent(uuid::Uuid::new_v4());
         
         assert_eq!(app_state.persistent_text, initial_text);
     }
@@ -222,7 +277,7 @@ mod tests {
     #[test]
     fn test_save_persistent_text_returns_ok() {
         // Тест что сохранение постоянного текста возвращает Ok
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         app_state.persistent_text = "Test persistent content".to_string();
         
@@ -233,26 +288,28 @@ mod tests {
     #[test]
     fn test_save_persistent_text_creates_file() {
         // Тест что файл действительно создается
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(temp_dir.path().join("notes")));
         
         app_state.persistent_text = "Test persistent content".to_string();
         
         let _ = app_state.save_persistent_text();
         
-        let persistent_file = app_state.notes_manager.get_base_dir().join("persistent_text.txt");
+        let persistent_file = temp_dir.path().join("persistent_text.txt");
         assert!(persistent_file.exists());
     }
 
     #[test]
     fn test_save_persistent_text_correct_content() {
         // Тест что файл содержит правильные данные
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(temp_dir.path().join("notes")));
         
         app_state.persistent_text = "Test persistent content".to_string();
         
         let _ = app_state.save_persistent_text();
         
-        let persistent_file = app_state.notes_manager.get_base_dir().join("persistent_text.txt");
+        let persistent_file = temp_dir.path().join("persistent_text.txt");
         let file_content = std::fs::read_to_string(persistent_file).unwrap();
         assert_eq!(file_content, "Test persistent content");
     }
@@ -260,7 +317,7 @@ mod tests {
     #[test]
     fn test_create_group_success() {
         // Тест успешного создания группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let group_name = "Test Group".to_string();
 
         create_test_group(&mut app_state, &group_name);
@@ -270,7 +327,7 @@ mod tests {
     #[test]
     fn test_create_group_invalid_name() {
         // Тест создания группы с некорректным именем
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let result = app_state.create_group("".to_string(), None, vec![]);
         assert!(result.is_err());
@@ -279,7 +336,7 @@ mod tests {
     #[test]
     fn test_delete_group() {
         // Тест удаления группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let group_name = "Test Group".to_string();
 
         let uuid = create_test_group(&mut app_state, &group_name);
@@ -292,7 +349,7 @@ mod tests {
     #[test]
     fn test_update_group() {
         // Тест обновления группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let group_name = "Test Group".to_string();
 
         let uuid = create_test_group(&mut app_state, &group_name);
@@ -305,7 +362,7 @@ mod tests {
     #[test]
     fn test_toggle_group_collapsed_to_true() {
         // Тест сворачивания группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let group_name = "Test Group".to_string();
 
         let uuid = create_test_group(&mut app_state, &group_name);
@@ -318,7 +375,7 @@ mod tests {
     #[test]
     fn test_toggle_group_collapsed_to_false() {
         // Тест разворачивания группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let group_name = "Test Group".to_string();
 
         let uuid = create_test_group(&mut app_state, &group_name);
@@ -332,7 +389,7 @@ mod tests {
     #[test]
     fn test_root_group_properties() {
         // Тест свойств корневой группы в иерархии
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (root_group_id, _, _) = create_test_group_hierarchy(&mut app_state);
         
@@ -343,7 +400,7 @@ mod tests {
     #[test]
     fn test_root_group_has_no_parent() {
         // Тест что корневая группа не имеет родителя
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (root_group_id, _, _) = create_test_group_hierarchy(&mut app_state);
         
@@ -354,7 +411,7 @@ mod tests {
     #[test]
     fn test_child_group_properties() {
         // Тест свойств дочерней группы в иерархии
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (_root_group_id, child_group_id, _) = create_test_group_hierarchy(&mut app_state);
         
@@ -365,7 +422,7 @@ mod tests {
     #[test]
     fn test_child_group_has_correct_parent() {
         // Тест что дочерняя группа имеет правильного родителя
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (root_group_id, child_group_id, _) = create_test_group_hierarchy(&mut app_state);
         
@@ -376,7 +433,7 @@ mod tests {
     #[test]
     fn test_grandchild_group_properties() {
         // Тест свойств внучатой группы в иерархии
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (_, _, grandchild_group_id) = create_test_group_hierarchy(&mut app_state);
         
@@ -387,7 +444,7 @@ mod tests {
     #[test]
     fn test_grandchild_group_has_correct_parent() {
         // Тест что внучатая группа имеет правильного родителя
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (_, child_group_id, grandchild_group_id) = create_test_group_hierarchy(&mut app_state);
         
@@ -398,7 +455,7 @@ mod tests {
     #[test]
     fn test_group_deletion_updates_children_parent() {
         // Тест что удаление промежуточной группы обновляет родителя дочерних групп
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (root_group_id, child_group_id, grandchild_group_id) = create_test_group_hierarchy(&mut app_state);
         
@@ -411,7 +468,7 @@ mod tests {
     #[test]
     fn test_group_deletion_updates_children_level() {
         // Тест что удаление промежуточной группы обновляет уровень дочерних групп
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (_root_group_id, child_group_id, grandchild_group_id) = create_test_group_hierarchy(&mut app_state);
         
@@ -424,7 +481,7 @@ mod tests {
     #[test]
     fn test_group_deletion_removes_group_from_collection() {
         // Тест что удаленная группа исчезает из коллекции
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (_, child_group_id, _) = create_test_group_hierarchy(&mut app_state);
         
@@ -436,7 +493,7 @@ mod tests {
     #[test]
     fn test_group_deletion_correct_remaining_count() {
         // Тест что после удаления промежуточной группы остается правильное количество групп
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         
         let (_, child_group_id, _) = create_test_group_hierarchy(&mut app_state);
         
@@ -448,7 +505,7 @@ mod tests {
     #[test]
     fn test_parent_group_notes_count() {
         // Тест количества заметок в родительской группе
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let parent_group_id = create_test_group(&mut app_state, "Parent Group");
         let child_group_id = create_test_group_full(&mut app_state, "Child Group", Some(parent_group_id), vec![]);
@@ -467,7 +524,7 @@ mod tests {
     #[test]
     fn test_child_group_notes_count() {
         // Тест количества заметок в дочерней группе
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let parent_group_id = create_test_group(&mut app_state, "Parent Group");
         let child_group_id = create_test_group_full(&mut app_state, "Child Group", Some(parent_group_id), vec![]);
@@ -486,7 +543,7 @@ mod tests {
     #[test]
     fn test_ungrouped_notes_count() {
         // Тест количества заметок без группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let parent_group_id = create_test_group(&mut app_state, "Parent Group");
         let child_group_id = create_test_group_full(&mut app_state, "Child Group", Some(parent_group_id), vec![]);
@@ -505,7 +562,7 @@ mod tests {
     #[test]
     fn test_child_group_deletion_moves_notes_to_parent() {
         // Тест что удаление дочерней группы перемещает заметки к родителю
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let parent_group_id = create_test_group(&mut app_state, "Parent Group");
         let child_group_id = create_test_group_full(&mut app_state, "Child Group", Some(parent_group_id), vec![]);
@@ -525,7 +582,7 @@ mod tests {
     #[test]
     fn test_group_initial_parent() {
         // Тест начального родителя дочерней группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group1_id = create_test_group(&mut app_state, "Group 1");
         let _group2_id = create_test_group(&mut app_state, "Group 2");
@@ -539,7 +596,7 @@ mod tests {
     #[test]
     fn test_group_initial_level() {
         // Тест начального уровня дочерней группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group1_id = create_test_group(&mut app_state, "Group 1");
         let _group2_id = create_test_group(&mut app_state, "Group 2");
@@ -553,7 +610,7 @@ mod tests {
     #[test]
     fn test_group_parent_change() {
         // Тест изменения родителя группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group1_id = create_test_group(&mut app_state, "Group 1");
         let group2_id = create_test_group(&mut app_state, "Group 2");
@@ -569,7 +626,7 @@ mod tests {
     #[test]
     fn test_group_parent_change_maintains_level() {
         // Тест что изменение родителя сохраняет уровень группы
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group1_id = create_test_group(&mut app_state, "Group 1");
         let group2_id = create_test_group(&mut app_state, "Group 2");
@@ -585,7 +642,7 @@ mod tests {
     #[test]
     fn test_group_becomes_root_parent() {
         // Тест что группа становится корневой (без родителя)
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group1_id = create_test_group(&mut app_state, "Group 1");
         let _group2_id = create_test_group(&mut app_state, "Group 2");
@@ -601,7 +658,7 @@ mod tests {
     #[test]
     fn test_group_becomes_root_level() {
         // Тест что группа становится корневой (уровень 0)
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group1_id = create_test_group(&mut app_state, "Group 1");
         let _group2_id = create_test_group(&mut app_state, "Group 2");
@@ -617,7 +674,7 @@ mod tests {
     #[test]
     fn test_circular_dependency_prevention_root_to_grandchild() {
         // Тест предотвращения циклической зависимости: корневая группа не может стать дочерней внучатой
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group_a_id = create_test_group(&mut app_state, "Group A");
         let group_b_id = create_test_group_full(&mut app_state, "Group B", Some(group_a_id), vec![]);
@@ -630,7 +687,7 @@ mod tests {
     #[test]
     fn test_circular_dependency_prevention_root_parent_unchanged() {
         // Тест что родитель корневой группы остается неизменным при попытке создания цикла
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group_a_id = create_test_group(&mut app_state, "Group A");
         let group_b_id = create_test_group_full(&mut app_state, "Group B", Some(group_a_id), vec![]);
@@ -645,7 +702,7 @@ mod tests {
     #[test]
     fn test_circular_dependency_prevention_child_to_grandchild() {
         // Тест предотвращения циклической зависимости: дочерняя группа не может стать дочерней внучатой
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group_a_id = create_test_group(&mut app_state, "Group A");
         let group_b_id = create_test_group_full(&mut app_state, "Group B", Some(group_a_id), vec![]);
@@ -658,7 +715,7 @@ mod tests {
     #[test]
     fn test_circular_dependency_prevention_child_parent_unchanged() {
         // Тест что родитель дочерней группы остается неизменным при попытке создания цикла
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
 
         let group_a_id = create_test_group(&mut app_state, "Group A");
         let group_b_id = create_test_group_full(&mut app_state, "Group B", Some(group_a_id), vec![]);
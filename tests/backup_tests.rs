@@ -0,0 +1,173 @@
+use std::fs;
+use std::io::Write;
+use chrono::NaiveDate;
+use fast_bind::backup::{
+    daily_backup_path, list_daily_backups, read_backup, rotate_daily_backups, run_daily_backup,
+    write_backup, BackupContents, RestoreMode,
+};
+use fast_bind::settings::AppSettings;
+use fast_bind::ui::ThemeMode;
+use tempfile::TempDir;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    #[test]
+    fn test_backup_roundtrip() {
+        // Тест создания и чтения копии со всеми данными
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Group");
+        create_test_note_with_group(&mut app_state, "Note", "Content", Some(group_id));
+        app_state.persistent_text = "persistent".to_string();
+        let settings = AppSettings { theme_mode: ThemeMode::Dark, ..AppSettings::default() };
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("backup.zip");
+        let manifest = app_state.create_backup(&path, &settings).unwrap();
+
+        assert_eq!(manifest.note_count, 1);
+        assert_eq!(manifest.group_count, 1);
+        assert_eq!(manifest.app_version, env!("CARGO_PKG_VERSION"));
+
+        let (read_manifest, contents) = read_backup(&path).unwrap();
+        assert_eq!(read_manifest, manifest);
+        assert_eq!(contents.notes[0].id, app_state.notes[0].id);
+        assert_eq!(contents.groups[0].id, group_id);
+        assert_eq!(contents.persistent_text, "persistent");
+        assert_eq!(contents.settings.unwrap().theme_mode, ThemeMode::Dark);
+    }
+
+    #[test]
+    fn test_corrupted_backup_rejected() {
+        // Тест: архив с измененным содержимым не проходит проверку контрольной суммы
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("backup.zip");
+        let contents = BackupContents { persistent_text: "original".to_string(), ..BackupContents::default() };
+        let manifest = write_backup(&path, &contents).unwrap();
+
+        // Собираем архив с тем же манифестом, но другим содержимым
+        let tampered_path = dir.path().join("tampered.zip");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&tampered_path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("manifest.json", options).unwrap();
+        writer.write_all(serde_json::to_string(&manifest).unwrap().as_bytes()).unwrap();
+        writer.start_file("groups.json", options).unwrap();
+        writer.write_all(b"[]").unwrap();
+        writer.start_file("persistent_text.txt", options).unwrap();
+        writer.write_all(b"changed").unwrap();
+        writer.finish().unwrap();
+
+        assert!(read_backup(&tampered_path).is_err());
+        fs::write(&path, b"not a zip").unwrap();
+        assert!(read_backup(&path).is_err());
+    }
+
+    #[test]
+    fn test_restore_replace() {
+        // Тест восстановления с заменой: лишние заметки удаляются, измененные возвращаются
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Kept", "Original");
+        app_state.persistent_text = "old text".to_string();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("backup.zip");
+        app_state.create_backup(&path, &AppSettings::default()).unwrap();
+
+        app_state.update_note(app_state.notes[0].id, None, Some("Changed".to_string())).unwrap();
+        create_test_note(&mut app_state, "Extra", "Extra");
+        create_test_group(&mut app_state, "Extra Group");
+        app_state.persistent_text = "new text".to_string();
+
+        let (_, contents) = read_backup(&path).unwrap();
+        let preview = app_state.preview_restore(&contents, RestoreMode::Replace).unwrap();
+        assert_eq!((preview.notes_added, preview.notes_updated, preview.notes_removed), (0, 1, 1));
+        assert_eq!(preview.groups_removed, 1);
+        assert!(preview.persistent_text_changed);
+
+        app_state.restore_backup(&contents, RestoreMode::Replace).unwrap();
+
+        assert_notes_count(&app_state, 1);
+        assert_groups_count(&app_state, 0);
+        assert_eq!(app_state.notes[0].content, "Original");
+        assert_eq!(app_state.persistent_text, "old text");
+        assert_eq!(app_state.store.get_all_notes().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_restore_merge() {
+        // Тест объединения по id: новые заметки сохраняются, заметки из копии возвращаются
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "From backup", "Original");
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("backup.zip");
+        app_state.create_backup(&path, &AppSettings::default()).unwrap();
+
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+        create_test_note(&mut app_state, "Local", "Local");
+        app_state.persistent_text = "local text".to_string();
+
+        let (_, contents) = read_backup(&path).unwrap();
+        let preview = app_state.restore_backup(&contents, RestoreMode::Merge).unwrap();
+
+        assert_eq!((preview.notes_added, preview.notes_removed), (1, 0));
+        assert_notes_count(&app_state, 2);
+        assert_eq!(app_state.persistent_text, "local text");
+    }
+
+    #[test]
+    fn test_restore_is_undoable() {
+        // Тест: восстановление из копии отменяется одним шагом
+        let mut app_state = create_test_app_state();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("backup.zip");
+        app_state.create_backup(&path, &AppSettings::default()).unwrap();
+        create_multiple_test_notes(&mut app_state, 3);
+
+        let (_, contents) = read_backup(&path).unwrap();
+        app_state.restore_backup(&contents, RestoreMode::Replace).unwrap();
+        assert_notes_count(&app_state, 0);
+
+        assert!(app_state.undo().unwrap());
+        assert_notes_count(&app_state, 3);
+    }
+
+    #[test]
+    fn test_daily_backup_rotation() {
+        // Тест: одна копия в день, старые удаляются сверх лимита
+        let dir = TempDir::new().unwrap();
+        let contents = BackupContents::default();
+
+        for day in 1..=5 {
+            assert!(run_daily_backup(dir.path(), &contents, 3, date(day)).unwrap().is_some());
+        }
+        assert!(run_daily_backup(dir.path(), &contents, 3, date(5)).unwrap().is_none());
+
+        let backups = list_daily_backups(dir.path()).unwrap();
+        assert_eq!(backups, vec![
+            daily_backup_path(dir.path(), date(3)),
+            daily_backup_path(dir.path(), date(4)),
+            daily_backup_path(dir.path(), date(5)),
+        ]);
+
+        // Посторонние файлы в папке не трогаются
+        fs::write(dir.path().join("manual.zip"), b"").unwrap();
+        assert_eq!(rotate_daily_backups(dir.path(), 1).unwrap(), 2);
+        assert!(dir.path().join("manual.zip").exists());
+    }
+
+    #[test]
+    fn test_daily_backup_disabled() {
+        // Тест: при лимите 0 копии не создаются
+        let dir = TempDir::new().unwrap();
+
+        assert!(run_daily_backup(dir.path(), &BackupContents::default(), 0, date(1)).unwrap().is_none());
+        assert!(list_daily_backups(dir.path()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use fast_bind::notes::NotesManager;
+use fast_bind::state::AppState;
+use fast_bind::storage::NoteStore;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Три заметки без группы, возвращает их id в порядке создания
+    fn create_three_notes(app_state: &mut AppState) -> Vec<Uuid> {
+        let mut ids = Vec::new();
+        for title in ["One", "Two", "Three"] {
+            create_test_note(app_state, title, &format!("{} content", title));
+            ids.push(app_state.notes.iter().find(|n| n.title == title).unwrap().id);
+        }
+        ids
+    }
+
+    fn note(app_state: &AppState, id: Uuid) -> &fast_bind::notes::Note {
+        app_state.notes.iter().find(|n| n.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_move_notes_to_group_single_undo() {
+        // Тест переноса нескольких заметок в группу и отмены одним действием
+        let mut app_state = create_test_app_state();
+        let ids = create_three_notes(&mut app_state);
+        let group_id = create_test_group(&mut app_state, "Target");
+
+        let moved = app_state.move_notes_to_group(&ids[..2], Some(group_id)).unwrap();
+        assert_eq!(moved, 2);
+        assert_eq!(note(&app_state, ids[0]).group_id, Some(group_id));
+        assert_eq!(note(&app_state, ids[1]).group_id, Some(group_id));
+        assert_eq!(note(&app_state, ids[2]).group_id, None);
+        assert_eq!(app_state.undo_stack.undo_label(), Some("Перемещение заметок"));
+
+        assert!(app_state.undo().unwrap());
+        assert!(app_state.notes.iter().all(|n| n.group_id.is_none()));
+    }
+
+    #[test]
+    fn test_bulk_update_failure_keeps_saved_changes_undoable() {
+        // Тест что при сбое записи сохраненные изменения отменяются одним действием, а несохраненное откатывается
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+        create_three_notes(&mut app_state);
+        let group_id = create_test_group(&mut app_state, "Target");
+        let ids: Vec<Uuid> = app_state.notes.iter().map(|n| n.id).collect();
+        // На месте файла второй заметки папка - записать заметку не получится
+        let blocked = temp_dir.path().join(format!("{}.json", ids[1]));
+        std::fs::remove_file(&blocked).unwrap();
+        std::fs::create_dir_all(blocked.join("blocked")).unwrap();
+
+        assert!(app_state.move_notes_to_group(&ids, Some(group_id)).is_err());
+
+        assert_eq!(note(&app_state, ids[0]).group_id, Some(group_id));
+        assert_eq!(note(&app_state, ids[1]).group_id, None);
+        assert_eq!(note(&app_state, ids[2]).group_id, None);
+        assert_eq!(app_state.undo_stack.undo_label(), Some("Перемещение заметок"));
+
+        assert!(app_state.undo().unwrap());
+        assert!(app_state.notes.iter().all(|n| n.group_id.is_none()));
+    }
+
+    #[test]
+    fn test_set_notes_pinned() {
+        // Тест закрепления и открепления нескольких заметок
+        let mut app_state = create_test_app_state();
+        let ids = create_three_notes(&mut app_state);
+
+        assert_eq!(app_state.set_notes_pinned(&ids, true).unwrap(), 3);
+        assert!(app_state.notes.iter().all(|n| n.pinned));
+        // Уже закрепленные заметки не считаются измененными
+        assert_eq!(app_state.set_notes_pinned(&ids[..1], true).unwrap(), 0);
+
+        assert!(app_state.undo().unwrap());
+        assert!(app_state.notes.iter().all(|n| !n.pinned));
+    }
+
+    #[test]
+    fn test_delete_notes_to_trash_single_undo() {
+        // Тест удаления нескольких заметок в корзину и их восстановления одной отменой
+        let mut app_state = create_test_app_state();
+        let ids = create_three_notes(&mut app_state);
+
+        assert_eq!(app_state.delete_notes(&ids[1..]).unwrap(), 2);
+        assert_notes_count(&app_state, 1);
+        assert_eq!(app_state.trash.notes.len(), 2);
+        assert_eq!(app_state.undo_stack.undo_label(), Some("Удаление заметок"));
+
+        assert!(app_state.undo().unwrap());
+        assert_notes_count(&app_state, 3);
+        assert!(app_state.trash.notes.is_empty());
+
+        assert!(app_state.redo().unwrap());
+        assert_notes_count(&app_state, 1);
+        assert_eq!(app_state.trash.notes.len(), 2);
+    }
+
+    #[test]
+    fn test_delete_notes_failure_trashes_only_deleted() {
+        // Тест что при сбое посреди удаления в корзине только удаленные заметки, а удаление отменяется
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+        create_three_notes(&mut app_state);
+        let ids: Vec<Uuid> = app_state.notes.iter().map(|n| n.id).collect();
+        // Файл второй заметки пропал - хранилище не сможет ее удалить
+        std::fs::remove_file(temp_dir.path().join(format!("{}.json", ids[1]))).unwrap();
+
+        assert!(app_state.delete_notes(&ids).is_err());
+
+        assert_eq!(app_state.notes.iter().map(|n| n.id).collect::<Vec<_>>(), ids[1..]);
+        assert_eq!(app_state.trash.notes.iter().map(|t| t.note.id).collect::<Vec<_>>(), [ids[0]]);
+        assert_eq!(NotesManager::new(temp_dir.path().to_path_buf()).load_trash().unwrap().notes.len(), 1);
+        assert_eq!(app_state.undo_stack.undo_label(), Some("Удаление заметок"));
+
+        assert!(app_state.undo().unwrap());
+        assert_notes_count(&app_state, 3);
+        assert!(app_state.trash.notes.is_empty());
+    }
+
+    #[test]
+    fn test_add_and_remove_tag() {
+        // Тест добавления и удаления тега у нескольких заметок
+        let mut app_state = create_test_app_state();
+        let ids = create_three_notes(&mut app_state);
+        app_state.set_note_tags(ids[0], vec!["work".to_string()]).unwrap();
+
+        assert_eq!(app_state.add_tag_to_notes(&ids, "#Work").unwrap(), 2);
+        assert!(app_state.notes.iter().all(|n| n.tags == ["work"]));
+
+        assert_eq!(app_state.remove_tag_from_notes(&ids[..2], "work").unwrap(), 2);
+        assert!(note(&app_state, ids[0]).tags.is_empty());
+        assert_eq!(note(&app_state, ids[2]).tags, ["work"]);
+
+        assert!(app_state.undo().unwrap());
+        assert!(app_state.notes.iter().all(|n| n.tags == ["work"]));
+    }
+
+    #[test]
+    fn test_add_invalid_tag_fails() {
+        // Тест что невалидный тег не добавляется
+        let mut app_state = create_test_app_state();
+        let ids = create_three_notes(&mut app_state);
+
+        assert!(app_state.add_tag_to_notes(&ids, "   ").is_err());
+        assert!(app_state.notes.iter().all(|n| n.tags.is_empty()));
+    }
+
+    #[test]
+    fn test_concat_notes_in_given_order() {
+        // Тест объединения текстов выбранных заметок
+        let mut app_state = create_test_app_state();
+        let ids = create_three_notes(&mut app_state);
+
+        let text = app_state.concat_notes(&[ids[2], ids[0]]);
+
+        assert_eq!(text, "Three content\n\nOne content");
+    }
+
+    #[test]
+    fn test_export_selected_notes() {
+        // Тест экспорта только выбранных заметок и папок их групп
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = create_test_app_state();
+        let (_root, child, _grandchild) = create_test_group_hierarchy(&mut app_state);
+        let other = create_test_group(&mut app_state, "Other");
+        create_test_note_with_group(&mut app_state, "Nested", "Content", Some(child));
+        create_test_note_with_group(&mut app_state, "Elsewhere", "Content", Some(other));
+        create_test_note(&mut app_state, "Loose", "Content");
+        let ids: Vec<Uuid> = app_state.notes.iter()
+            .filter(|n| n.title != "Elsewhere")
+            .map(|n| n.id)
+            .collect();
+
+        let report = app_state.export_notes(&ids, temp_dir.path()).unwrap();
+
+        assert_eq!(report.notes_exported, 2);
+        assert_eq!(report.groups_exported, 2);
+        assert!(temp_dir.path().join("Root Group").join("Child Group").join("Nested.md").exists());
+        assert!(temp_dir.path().join("Loose.md").exists());
+        assert!(!temp_dir.path().join("Other").exists());
+    }
+}
@@ -0,0 +1,185 @@
+use fast_bind::cli::{self, CliError, Command, EXIT_BUSY, EXIT_IO, EXIT_NOT_FOUND, EXIT_USAGE, EXIT_VALIDATION};
+use fast_bind::error::AppError;
+use fast_bind::state::AppState;
+use fast_bind::validation::ValidationError;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    /// Выполняет команду и возвращает вывод; `stdin` подается на вход команды
+    fn run_command(app_state: &mut AppState, line: &str, stdin: &str) -> Result<String, CliError> {
+        let cli_args = cli::parse_args(&args(line))?;
+        let mut out = Vec::new();
+        cli::execute(app_state, &cli_args.command, cli_args.json, &mut stdin.as_bytes(), &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_parse_global_flags_anywhere() {
+        // Тест что --json и --data-dir разбираются до и после команды
+        let cli_args = cli::parse_args(&args("--data-dir /tmp/notes add --title Hello --tag a --tag b --json")).unwrap();
+
+        assert!(cli_args.json);
+        assert_eq!(cli_args.data_dir, Some("/tmp/notes".into()));
+        assert_eq!(cli_args.command, Command::Add {
+            title: "Hello".to_string(),
+            group: None,
+            tags: vec!["a".to_string(), "b".to_string()],
+            content: None,
+        });
+    }
+
+    #[test]
+    fn test_parse_errors_are_usage_errors() {
+        // Тест что неизвестная команда, лишние аргументы и параметры дают код неверного использования
+        for line in ["frobnicate", "show", "show a b", "list --bogus 1", "edit note", "add", "copy n --set novalue"] {
+            let error = cli::parse_args(&args(line)).unwrap_err();
+            assert_eq!(error.exit_code(), EXIT_USAGE, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_is_cli_invocation() {
+        // Тест что окно запускается без команды или с флагом быстрого выбора
+        assert!(cli::is_cli_invocation(&args("list")));
+        assert!(cli::is_cli_invocation(&args("--json --data-dir x search foo")));
+        assert!(cli::is_cli_invocation(&args("lsit")));
+        assert!(!cli::is_cli_invocation(&args("")));
+        assert!(!cli::is_cli_invocation(&args("--quick-picker")));
+    }
+
+    #[test]
+    fn test_add_reads_content_from_stdin() {
+        // Тест создания заметки с содержимым из stdin, тегами и группой
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Work");
+
+        let output = run_command(&mut app_state, "add --title Piped --group work --tag #Todo --content -", "from stdin\n").unwrap();
+
+        let note_id = output.trim().parse().unwrap();
+        let note = app_state.note(note_id).unwrap();
+        assert_eq!(note.content, "from stdin\n");
+        assert_eq!(note.group_id, Some(group_id));
+        assert_eq!(note.tags, ["todo"]);
+
+        // Заметка с тегами создается одной операцией отмены
+        assert_eq!(app_state.undo_stack.undo_label(), Some("Создание заметки"));
+        app_state.undo().unwrap();
+        assert!(app_state.note(note_id).is_none());
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[test]
+    fn test_copy_without_window_fails_on_x11() {
+        // Тест что без окна приложения copy не сообщает об успехе и не считает использование
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Plain", "text");
+
+        let error = run_command(&mut app_state, "copy plain", "").unwrap_err();
+
+        assert_eq!(error.exit_code(), EXIT_IO);
+        assert_eq!(app_state.notes[0].copy_count, 0);
+    }
+
+    #[test]
+    fn test_show_and_list_json() {
+        // Тест вывода содержимого по заголовку и списка в JSON
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "First", "first content");
+        create_test_note(&mut app_state, "Second", "second content");
+
+        assert_eq!(run_command(&mut app_state, "show first", "").unwrap(), "first content\n");
+
+        let output = run_command(&mut app_state, "list --json", "").unwrap();
+        let list: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let titles: Vec<&str> = list.as_array().unwrap().iter().map(|n| n["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, ["Second", "First"]);
+    }
+
+    #[test]
+    fn test_note_lookup_by_id_prefix() {
+        // Тест поиска заметки по началу id
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Note", "by prefix");
+        let prefix = app_state.notes[0].id.to_string()[..8].to_string();
+
+        assert_eq!(run_command(&mut app_state, &format!("show {}", prefix), "").unwrap(), "by prefix\n");
+    }
+
+    #[test]
+    fn test_edit_and_rm() {
+        // Тест изменения заметки и перемещения ее в корзину
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Draft", "old");
+        let note_id = app_state.notes[0].id;
+
+        run_command(&mut app_state, "edit draft --title Final --content new", "").unwrap();
+        let note = app_state.note(note_id).unwrap();
+        assert_eq!((note.title.as_str(), note.content.as_str()), ("Final", "new"));
+
+        run_command(&mut app_state, "rm Final", "").unwrap();
+        assert_notes_count(&app_state, 0);
+        assert_eq!(app_state.trash.notes.len(), 1);
+    }
+
+    #[test]
+    fn test_exit_codes_for_command_errors() {
+        // Тест что ненайденная заметка, ошибка проверки и неоднозначный заголовок дают разные коды
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Twin", "a");
+        create_test_note(&mut app_state, "Twin", "b");
+        create_test_note(&mut app_state, "Form", "Hi {{name:Client}}");
+
+        let cases = [
+            ("show missing", EXIT_NOT_FOUND),
+            ("list --group nowhere", EXIT_NOT_FOUND),
+            ("show twin", EXIT_VALIDATION),
+            ("add --title Tagged --tag bad,tag --content x", EXIT_VALIDATION),
+            ("copy form", EXIT_VALIDATION),
+        ];
+        for (line, code) in cases {
+            let error = run_command(&mut app_state, line, "").unwrap_err();
+            assert_eq!(error.exit_code(), code, "{}: {}", line, error);
+        }
+        assert_notes_count(&app_state, 3);
+    }
+
+    #[test]
+    fn test_app_errors_map_to_exit_codes() {
+        // Тест что вид ошибки приложения определяет код завершения, а ошибки хранилища - ошибки ввода-вывода
+        let cases = [
+            (AppError::Io(std::io::Error::other("disk full")), EXIT_IO),
+            (AppError::corrupted("groups.json", serde_json::from_str::<u8>("{").unwrap_err()), EXIT_IO),
+            (AppError::Validation(vec![ValidationError::EmptyTitle]), EXIT_VALIDATION),
+            (AppError::GroupCycle(uuid::Uuid::new_v4()), EXIT_VALIDATION),
+            (AppError::GroupNotFound(uuid::Uuid::new_v4()), EXIT_NOT_FOUND),
+            (AppError::LockHeld("/tmp/notes".into()), EXIT_BUSY),
+        ];
+        for (error, code) in cases {
+            assert_eq!(CliError::from(error).exit_code(), code);
+        }
+    }
+
+    #[test]
+    fn test_groups_tree_order() {
+        // Тест вывода групп: вложенные сразу после родителя
+        let mut app_state = create_test_app_state();
+        let (root, _child, _grandchild) = create_test_group_hierarchy(&mut app_state);
+        create_test_group(&mut app_state, "Other");
+        create_test_note_with_group(&mut app_state, "In root", "x", Some(root));
+
+        let output = run_command(&mut app_state, "groups --json", "").unwrap();
+        let groups: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let levels: Vec<u64> = groups.as_array().unwrap().iter().map(|g| g["level"].as_u64().unwrap()).collect();
+        assert_eq!(levels, [0, 1, 2, 0]);
+        assert_eq!(groups[0]["notes"], 1);
+    }
+}
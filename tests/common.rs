@@ -1,16 +1,12 @@
 #![allow(dead_code)]
 
 use fast_bind::state::AppState;
-use fast_bind::notes::NotesManager;
+use fast_bind::storage::MemoryNoteStore;
 use uuid::Uuid;
-use tempfile::TempDir;
 
-/// Создает тестовое состояние приложения с временной директорией
-pub fn create_test_app_state() -> (AppState, TempDir) {
-    let temp_dir = TempDir::new().unwrap();
-    let notes_manager = NotesManager::new(temp_dir.path().to_path_buf());
-    let app_state = AppState::new(notes_manager);
-    (app_state, temp_dir)
+/// Создает тестовое состояние приложения поверх хранилища в памяти
+pub fn create_test_app_state() -> AppState {
+    AppState::new(MemoryNoteStore::new())
 }
 
 /// Создает тестовую заметку без группы
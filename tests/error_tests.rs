@@ -0,0 +1,140 @@
+use fast_bind::error::AppError;
+use fast_bind::notes::NotesManager;
+use fast_bind::storage::NoteStore;
+use fast_bind::validation::ValidationError;
+use std::collections::HashSet;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_are_stable_and_unique() {
+        // Тест что коды ошибок не меняются и не повторяются
+        let id = Uuid::new_v4();
+        let errors = [
+            (AppError::Io(std::io::Error::other("x")), "io"),
+            (AppError::Json(serde_json::from_str::<u8>("x").unwrap_err()), "json"),
+            (AppError::Database(rusqlite::Error::InvalidQuery), "database"),
+            (AppError::NoteNotFound, "note_not_found"),
+            (AppError::GroupNotFound(id), "group_not_found"),
+            (AppError::RevisionNotFound(id), "revision_not_found"),
+            (AppError::GroupCycle(id), "group_cycle"),
+            (AppError::Validation(vec![]), "validation"),
+            (AppError::Corrupted { path: "a.json".into(), reason: "x".to_string() }, "corrupted"),
+            (AppError::LockHeld("/tmp".into()), "lock_held"),
+            (AppError::Backup("x".to_string()), "backup"),
+            (AppError::Hotkey("x".to_string()), "hotkey"),
+            (AppError::Watch("x".to_string()), "watch"),
+        ];
+
+        let codes: HashSet<&str> = errors.iter().map(|(error, _)| error.code()).collect();
+        assert_eq!(codes.len(), errors.len());
+        for (error, code) in &errors {
+            assert_eq!(error.code(), *code);
+        }
+    }
+
+    #[test]
+    fn test_validation_keeps_each_error() {
+        // Тест что ошибки проверки доступны по отдельности, а ссылки на группы получают свой вид
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Work");
+        let long_title = "x".repeat(300);
+
+        match app_state.create_note(long_title, String::new(), None) {
+            Err(AppError::Validation(errors)) => assert_eq!(errors, [ValidationError::TitleTooLong(300)]),
+            other => panic!("ожидалась ошибка проверки: {:?}", other),
+        }
+        assert!(matches!(
+            app_state.update_group(group_id, " ".to_string(), None),
+            Err(AppError::Validation(ref errors)) if errors == &[ValidationError::EmptyGroupName]
+        ));
+        let missing = Uuid::new_v4();
+        assert!(matches!(
+            app_state.create_group("Child".to_string(), Some(missing), vec![]),
+            Err(AppError::GroupNotFound(id)) if id == missing
+        ));
+    }
+
+    #[test]
+    fn test_not_found_errors_carry_id() {
+        // Тест что отсутствующие версия и группа в корзине возвращают свои виды ошибок
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Note", "text");
+        let note_id = app_state.notes[0].id;
+        let (revision_id, group_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(matches!(app_state.restore_revision(note_id, revision_id), Err(AppError::RevisionNotFound(id)) if id == revision_id));
+        assert!(matches!(app_state.restore_trashed_group(group_id), Err(AppError::GroupNotFound(id)) if id == group_id));
+        assert!(matches!(app_state.purge_trashed_note(Uuid::new_v4()), Err(AppError::NoteNotFound)));
+    }
+
+    #[test]
+    fn test_unknown_note_id_returns_not_found() {
+        // Тест что операции с несуществующей заметкой возвращают NoteNotFound, а не молча ничего не делают
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Work");
+        let unknown = Uuid::new_v4();
+        let undo_label = app_state.undo_stack.undo_label().map(str::to_string);
+
+        let results = [
+            app_state.delete_note(unknown),
+            app_state.update_note(unknown, Some("Title".to_string()), None),
+            app_state.toggle_pin(unknown),
+            app_state.restore_revision(unknown, Uuid::new_v4()),
+            app_state.record_note_use(unknown),
+            app_state.set_note_tags(unknown, vec!["tag".to_string()]),
+            app_state.move_note_to_group(unknown, Some(group_id)),
+        ];
+        for result in results {
+            assert!(matches!(result, Err(AppError::NoteNotFound)), "ожидалась NoteNotFound: {:?}", result);
+        }
+        assert_eq!(app_state.undo_stack.undo_label().map(str::to_string), undo_label);
+
+        // Перемещение в ту же группу - не ошибка
+        create_test_note(&mut app_state, "Note", "text");
+        let note_id = app_state.notes[0].id;
+        assert!(app_state.move_note_to_group(note_id, None).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_group_id_returns_group_not_found() {
+        // Тест что операции с несуществующей группой возвращают GroupNotFound с ее id
+        let mut app_state = create_test_app_state();
+        create_test_group(&mut app_state, "Work");
+        let unknown = Uuid::new_v4();
+
+        let results = [
+            app_state.toggle_group_collapsed(unknown),
+            app_state.set_group_sort_mode(unknown, None),
+            app_state.delete_group(unknown),
+            app_state.update_group(unknown, "Name".to_string(), None),
+        ];
+        for result in results {
+            assert!(matches!(result, Err(AppError::GroupNotFound(id)) if id == unknown), "ожидалась GroupNotFound: {:?}", result);
+        }
+        assert_eq!(app_state.groups.len(), 1);
+        assert!(app_state.trash.is_empty());
+    }
+
+    #[test]
+    fn test_corrupted_file_reports_path() {
+        // Тест что испорченный файл заметки дает ошибку повреждения с путем к файлу
+        let temp_dir = TempDir::new().unwrap();
+        let store = NotesManager::new(temp_dir.path().to_path_buf());
+        let note_id = Uuid::new_v4();
+        let path = temp_dir.path().join(format!("{}.json", note_id));
+        std::fs::write(&path, "{ not json").unwrap();
+
+        match store.load_note_by_id(note_id) {
+            Err(AppError::Corrupted { path: reported, .. }) => assert_eq!(reported, path),
+            other => panic!("ожидалась ошибка повреждения: {:?}", other),
+        }
+    }
+}
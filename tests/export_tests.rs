@@ -0,0 +1,136 @@
+use std::fs;
+use fast_bind::export::export_markdown;
+use fast_bind::markdown::{render_note, sanitize_file_name, UniqueNames};
+use fast_bind::notes::{Note, NoteGroup};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(name: &str, parent_id: Option<Uuid>, level: u32) -> NoteGroup {
+        NoteGroup { id: Uuid::new_v4(), name: name.to_string(), collapsed: false, parent_id, level, sort_mode: None }
+    }
+
+    #[test]
+    fn test_render_note_front_matter() {
+        // Тест front matter с метаданными заметки
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title \"quoted\"", "Body");
+        app_state.set_note_tags(app_state.notes[0].id, vec!["rust".to_string(), "work".to_string()]).unwrap();
+        let note = &app_state.notes[0];
+
+        let rendered = render_note(note);
+
+        assert!(rendered.starts_with("---\n"));
+        assert!(rendered.contains(&format!("id: {}\n", note.id)));
+        assert!(rendered.contains("title: \"Title \\\"quoted\\\"\"\n"));
+        assert!(rendered.contains("pinned: false\n"));
+        assert!(rendered.contains("tags:\n  - \"rust\"\n  - \"work\"\n"));
+        assert!(rendered.ends_with("---\n\nBody\n"));
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        // Тест замены недопустимых символов и зарезервированных имен
+        assert_eq!(sanitize_file_name("a/b\\c:d*e?f\"g<h>i|j"), "a_b_c_d_e_f_g_h_i_j");
+        assert_eq!(sanitize_file_name("  .hidden.  "), "hidden");
+        assert_eq!(sanitize_file_name("CON"), "_CON");
+        assert_eq!(sanitize_file_name("nul.txt"), "_nul.txt");
+        assert_eq!(sanitize_file_name("///"), "___");
+        assert_eq!(sanitize_file_name("..."), "Без названия");
+        assert_eq!(sanitize_file_name(&"я".repeat(300)).chars().count(), 100);
+    }
+
+    #[test]
+    fn test_unique_names_case_insensitive() {
+        // Тест уникальных имен без учета регистра
+        let mut names = UniqueNames::new();
+
+        assert_eq!(names.claim("Note", "md"), "Note.md");
+        assert_eq!(names.claim("note", "md"), "note (2).md");
+        assert_eq!(names.claim("Note", "md"), "Note (3).md");
+        assert_eq!(names.claim("Note", ""), "Note");
+    }
+
+    #[test]
+    fn test_export_group_tree() {
+        // Тест экспорта групп вложенными папками
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = create_test_app_state();
+        let (_root_id, child_id, _grandchild_id) = create_test_group_hierarchy(&mut app_state);
+        create_test_note_with_group(&mut app_state, "Nested", "Content", Some(child_id));
+        create_test_note(&mut app_state, "Top", "Content");
+
+        let report = app_state.export_markdown(temp_dir.path()).unwrap();
+
+        assert_eq!(report.notes_exported, 2);
+        assert_eq!(report.groups_exported, 3);
+        let root = temp_dir.path();
+        assert!(root.join("Top.md").is_file());
+        assert!(root.join("Root Group/Child Group/Nested.md").is_file());
+        assert!(root.join("Root Group/Child Group/Grandchild Group").is_dir());
+    }
+
+    #[test]
+    fn test_export_name_collisions() {
+        // Тест что заметки с одинаковыми названиями не перезаписывают друг друга
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Same", "First");
+        create_test_note(&mut app_state, "Same", "Second");
+
+        app_state.export_markdown(temp_dir.path()).unwrap();
+
+        assert!(temp_dir.path().join("Same.md").is_file());
+        assert!(temp_dir.path().join("Same (2).md").is_file());
+    }
+
+    #[test]
+    fn test_export_does_not_overwrite_existing_files() {
+        // Тест что повторный экспорт в ту же папку не затирает прежние файлы
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Note.md"), "keep").unwrap();
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Note", "New");
+
+        app_state.export_markdown(temp_dir.path()).unwrap();
+
+        assert_eq!(fs::read_to_string(temp_dir.path().join("Note.md")).unwrap(), "keep");
+        assert!(fs::read_to_string(temp_dir.path().join("Note (2).md")).unwrap().contains("New"));
+    }
+
+    #[test]
+    fn test_export_orphans_and_cycles_go_to_root() {
+        // Тест групп с потерянным родителем и циклом parent_id
+        let temp_dir = TempDir::new().unwrap();
+        let orphan = group("Orphan", Some(Uuid::new_v4()), 1);
+        let mut first = group("First", None, 1);
+        let second = group("Second", Some(first.id), 1);
+        first.parent_id = Some(second.id);
+        let note = Note {
+            id: Uuid::new_v4(),
+            title: "Lost".to_string(),
+            content: "Content".to_string(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            pinned: false,
+            group_id: Some(Uuid::new_v4()),
+            tags: Vec::new(),
+            copy_count: 0,
+            last_used_at: None,
+            sort_index: 0,
+        };
+
+        let report = export_markdown(&[note], &[orphan, first, second], temp_dir.path()).unwrap();
+
+        assert_eq!(report.groups_exported, 3);
+        assert!(temp_dir.path().join("Orphan").is_dir());
+        assert!(temp_dir.path().join("First/Second").is_dir());
+        assert!(temp_dir.path().join("Lost.md").is_file());
+    }
+}
@@ -1,7 +1,23 @@
+use std::fs;
+use fast_bind::fs_utils;
+use fast_bind::notes::NotesManager;
+use fast_bind::state::AppState;
+use fast_bind::storage::NoteStore;
+use tempfile::TempDir;
+
 mod common;
+use common::*;
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    /// Состояние приложения поверх файлового хранилища во временной директории
+    fn create_file_app_state() -> (AppState, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let app_state = AppState::new(NotesManager::new(temp_dir.path().join("notes")));
+        (app_state, temp_dir)
+    }
 
     #[test]
     fn test_save_note() {
@@ -62,4 +78,86 @@ mod tests {
     fn test_disk_full_scenario() {
         // Тест обработки нехватки места на диске (симуляция)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_atomic_write_replaces_file_without_leftovers() {
+        // Тест атомарной записи: новое содержимое на месте, временный файл удален
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data.json");
+        fs::write(&path, "old").unwrap();
+
+        fs_utils::atomic_write(&path, "new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert!(!fs_utils::temp_path_for(&path).exists());
+    }
+
+    #[test]
+    fn test_interrupted_note_write_keeps_previous_version() {
+        // Тест сбоя посреди записи: недописанный временный файл не портит заметку
+        let temp_dir = TempDir::new().unwrap();
+        let note_path;
+        {
+            let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+            create_test_note(&mut app_state, "Title", "Original content");
+            note_path = temp_dir.path().join(format!("{}.json", app_state.notes[0].id));
+        }
+        // Процесс "упал", успев записать только часть новой версии
+        let temp_path = fs_utils::temp_path_for(&note_path);
+        fs::write(&temp_path, r#"{"id": "trunc"#).unwrap();
+
+        let app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+
+        assert_eq!(app_state.notes.len(), 1);
+        assert_eq!(app_state.notes[0].content, "Original content");
+        assert!(!temp_path.exists());
+    }
+
+    #[test]
+    fn test_failed_note_write_keeps_previous_version() {
+        // Тест ошибки записи: предыдущая версия заметки остается целой
+        let (mut app_state, temp_dir) = create_file_app_state();
+        create_test_note(&mut app_state, "Title", "Original content");
+        let note_path = temp_dir.path().join("notes").join(format!("{}.json", app_state.notes[0].id));
+        // Директория на месте временного файла не дает начать запись
+        fs::create_dir(fs_utils::temp_path_for(&note_path)).unwrap();
+
+        let result = app_state.update_note(app_state.notes[0].id, None, Some("New content".to_string()));
+
+        assert!(result.is_err());
+        let notes = NotesManager::new(temp_dir.path().join("notes")).get_all_notes().unwrap();
+        assert_eq!(notes[0].content, "Original content");
+    }
+
+    #[test]
+    fn test_failed_groups_write_keeps_previous_version() {
+        // Тест ошибки записи групп: groups.json не обрезается
+        let (mut app_state, temp_dir) = create_file_app_state();
+        create_test_group(&mut app_state, "Group");
+        let groups_path = temp_dir.path().join("notes").join("groups.json");
+        fs::create_dir(fs_utils::temp_path_for(&groups_path)).unwrap();
+
+        let result = app_state.create_group("Second".to_string(), None, vec![]);
+
+        assert!(result.is_err());
+        let groups = NotesManager::new(temp_dir.path().join("notes")).load_groups().unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "Group");
+    }
+
+    #[test]
+    fn test_failed_persistent_text_write_keeps_previous_version() {
+        // Тест ошибки записи черновика: старый текст сохраняется
+        let (mut app_state, temp_dir) = create_file_app_state();
+        app_state.persistent_text = "Original draft".to_string();
+        app_state.save_persistent_text().unwrap();
+        let draft_path = temp_dir.path().join("persistent_text.txt");
+        fs::create_dir(fs_utils::temp_path_for(&draft_path)).unwrap();
+
+        app_state.persistent_text = "New draft".to_string();
+        let result = app_state.save_persistent_text();
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&draft_path).unwrap(), "Original draft");
+    }
+}
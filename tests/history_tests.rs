@@ -0,0 +1,198 @@
+use fast_bind::error::AppError;
+use fast_bind::history::{diff_lines, DiffLine, MAX_REVISIONS_PER_NOTE};
+use fast_bind::notes::NotesManager;
+use fast_bind::state::AppState;
+use fast_bind::validation::ValidationError;
+use tempfile::TempDir;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_note_records_previous_version() {
+        // Тест что перед изменением сохраняется предыдущая версия
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Original");
+        let note_id = app_state.notes[0].id;
+
+        app_state.update_note(app_state.notes[0].id, None, Some("Changed".to_string())).unwrap();
+
+        let revisions = app_state.note_revisions(note_id);
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].content, "Original");
+    }
+
+    #[test]
+    fn test_invalid_title_is_rejected() {
+        // Тест что пустой или слишком длинный заголовок не сохраняется и не попадает в историю
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Original");
+        let note_id = app_state.notes[0].id;
+
+        assert!(matches!(
+            app_state.update_note(note_id, Some("  ".to_string()), Some("Changed".to_string())),
+            Err(AppError::Validation(ref errors)) if errors == &[ValidationError::EmptyTitle]
+        ));
+        assert!(matches!(
+            app_state.update_note(note_id, Some("x".repeat(300)), None),
+            Err(AppError::Validation(ref errors)) if errors == &[ValidationError::TitleTooLong(300)]
+        ));
+
+        assert_eq!((app_state.notes[0].title.as_str(), app_state.notes[0].content.as_str()), ("Title", "Original"));
+        assert!(app_state.note_revisions(note_id).is_empty());
+        assert_eq!(app_state.undo_stack.undo_label(), Some("Создание заметки"));
+    }
+
+    #[test]
+    fn test_failed_save_keeps_note_unchanged() {
+        // Тест что при сбое записи заметка в памяти остается прежней, без версии и отмены
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+        create_test_note(&mut app_state, "Title", "Original");
+        let note_id = app_state.notes[0].id;
+        // На месте файла заметки папка - записать заметку не получится
+        let blocked = temp_dir.path().join(format!("{}.json", note_id));
+        std::fs::remove_file(&blocked).unwrap();
+        std::fs::create_dir_all(blocked.join("blocked")).unwrap();
+
+        assert!(app_state.update_note(note_id, None, Some("Changed".to_string())).is_err());
+
+        assert_eq!(app_state.notes[0].content, "Original");
+        assert!(app_state.note_revisions(note_id).is_empty());
+        assert_eq!(app_state.undo_stack.undo_label(), Some("Создание заметки"));
+    }
+
+    #[test]
+    fn test_unchanged_save_does_not_record_version() {
+        // Тест что сохранение без изменений не засоряет историю
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Same");
+        let note_id = app_state.notes[0].id;
+
+        app_state.update_note(app_state.notes[0].id, None, Some("Same".to_string())).unwrap();
+
+        assert!(app_state.note_revisions(note_id).is_empty());
+    }
+
+    #[test]
+    fn test_revisions_are_newest_first() {
+        // Тест порядка версий: новые сверху
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "v1");
+        let note_id = app_state.notes[0].id;
+        app_state.update_note(app_state.notes[0].id, None, Some("v2".to_string())).unwrap();
+        app_state.update_note(app_state.notes[0].id, None, Some("v3".to_string())).unwrap();
+
+        let contents: Vec<String> = app_state.note_revisions(note_id).into_iter().map(|r| r.content).collect();
+
+        assert_eq!(contents, vec!["v2", "v1"]);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        // Тест ограничения количества версий
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "v0");
+        let note_id = app_state.notes[0].id;
+
+        for i in 1..=MAX_REVISIONS_PER_NOTE + 5 {
+            app_state.update_note(app_state.notes[0].id, None, Some(format!("v{}", i))).unwrap();
+        }
+
+        let revisions = app_state.note_revisions(note_id);
+        assert_eq!(revisions.len(), MAX_REVISIONS_PER_NOTE);
+        // Самые старые версии удалены
+        assert!(!revisions.iter().any(|r| r.content == "v0"));
+    }
+
+    #[test]
+    fn test_restore_revision_recovers_content() {
+        // Тест восстановления случайно удаленного содержимого
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Important text");
+        let note_id = app_state.notes[0].id;
+        app_state.update_note(app_state.notes[0].id, None, Some(String::new())).unwrap();
+        let revision_id = app_state.note_revisions(note_id)[0].id;
+
+        app_state.restore_revision(app_state.notes[0].id, revision_id).unwrap();
+
+        assert_eq!(app_state.notes[0].content, "Important text");
+        // Пустая версия тоже сохранена в истории
+        assert!(app_state.note_revisions(note_id).iter().any(|r| r.content.is_empty()));
+    }
+
+    #[test]
+    fn test_restore_unknown_revision_fails() {
+        // Тест восстановления несуществующей версии
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Content");
+
+        let result = app_state.restore_revision(app_state.notes[0].id, uuid::Uuid::new_v4());
+
+        assert!(result.is_err());
+        assert_eq!(app_state.notes[0].content, "Content");
+    }
+
+    #[test]
+    fn test_purge_note_removes_history() {
+        // Тест удаления истории при окончательном удалении заметки из корзины
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "v1");
+        let note_id = app_state.notes[0].id;
+        app_state.update_note(app_state.notes[0].id, None, Some("v2".to_string())).unwrap();
+
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+        assert_eq!(app_state.note_revisions(note_id).len(), 1);
+
+        app_state.purge_trashed_note(note_id).unwrap();
+        assert!(app_state.note_revisions(note_id).is_empty());
+    }
+
+    #[test]
+    fn test_history_stored_alongside_notes_on_disk() {
+        // Тест что история хранится рядом с заметками и не считается заметкой
+        let temp_dir = TempDir::new().unwrap();
+        let note_id;
+        {
+            let mut app_state = AppState::new(NotesManager::new(temp_dir.path().join("data")));
+            create_test_note(&mut app_state, "Title", "v1");
+            note_id = app_state.notes[0].id;
+            app_state.update_note(app_state.notes[0].id, None, Some("v2".to_string())).unwrap();
+        }
+
+        let app_state = AppState::new(NotesManager::new(temp_dir.path().join("data")));
+
+        assert_eq!(app_state.notes.len(), 1);
+        assert!(temp_dir.path().join("data").join("history").join(format!("{}.json", note_id)).exists());
+        assert_eq!(app_state.note_revisions(note_id)[0].content, "v1");
+    }
+
+    #[test]
+    fn test_diff_lines_marks_changes() {
+        // Тест построчного сравнения
+        let diff = diff_lines("a\nb\nc", "a\nx\nc\nd");
+
+        assert_eq!(diff, vec![
+            DiffLine::Unchanged("a".to_string()),
+            DiffLine::Removed("b".to_string()),
+            DiffLine::Added("x".to_string()),
+            DiffLine::Unchanged("c".to_string()),
+            DiffLine::Added("d".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_diff_lines_against_empty_text() {
+        // Тест сравнения с пустым текстом
+        let diff = diff_lines("one\ntwo", "");
+
+        assert_eq!(diff, vec![
+            DiffLine::Removed("one".to_string()),
+            DiffLine::Removed("two".to_string()),
+        ]);
+    }
+}
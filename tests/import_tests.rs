@@ -0,0 +1,320 @@
+use std::collections::HashSet;
+use std::fs;
+use fast_bind::import::{import_markdown_folder, import_notes, ImportSource, SkipReason};
+use fast_bind::markdown::{first_heading, split_front_matter};
+use fast_bind::validation::ValidationRules;
+use tempfile::TempDir;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_front_matter_variants() {
+        // Тест разбора front matter в разных записях
+        let text = "---\ntitle: 'It''s'\ndate: 2024-01-31\npinned: yes\ntags: [rust, \"work\"]\nextra:\n  nested: 1\n---\nBody";
+        let (front_matter, body) = split_front_matter(text);
+
+        assert_eq!(front_matter.title.as_deref(), Some("It's"));
+        assert_eq!(front_matter.created.unwrap().to_rfc3339(), "2024-01-31T00:00:00+00:00");
+        assert_eq!(front_matter.pinned, Some(true));
+        assert_eq!(front_matter.tags, vec!["rust", "work"]);
+        assert_eq!(body, "Body");
+
+        let (front_matter, body) = split_front_matter("---\nunclosed: true\nBody");
+        assert_eq!(front_matter.title, None);
+        assert_eq!(body, "---\nunclosed: true\nBody");
+
+        assert_eq!(first_heading("\n# Heading\ntext").as_deref(), Some("Heading"));
+        assert_eq!(first_heading("text\n# Heading"), None);
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        // Тест: экспортированная папка импортируется обратно с теми же полями
+        let mut source = create_test_app_state();
+        let group_id = create_test_group(&mut source, "Work");
+        create_test_note_with_group(&mut source, "Plan", "Line 1\nLine 2", Some(group_id));
+        source.set_note_tags(source.notes[0].id, vec!["rust".to_string(), "todo".to_string()]).unwrap();
+        source.toggle_pin(source.notes[0].id).unwrap();
+        let original = source.notes[0].clone();
+
+        let dir = TempDir::new().unwrap();
+        source.export_markdown(dir.path()).unwrap();
+
+        let mut target = create_test_app_state();
+        let report = target.import_notes(ImportSource::MarkdownFolder, dir.path()).unwrap();
+
+        assert_eq!(report.notes_imported, 1);
+        assert_eq!(report.groups_created, 1);
+        assert!(report.skipped.is_empty());
+        let note = &target.notes[0];
+        assert_eq!(note.id, original.id);
+        assert_eq!(note.title, original.title);
+        assert_eq!(note.content, original.content);
+        assert_eq!(note.tags, original.tags);
+        assert!(note.pinned);
+        assert_eq!(note.created_at.timestamp(), original.created_at.timestamp());
+        assert_eq!(note.group_id, Some(target.groups[0].id));
+        assert_eq!(target.groups[0].name, "Work");
+    }
+
+    #[test]
+    fn test_title_from_heading_or_file_name() {
+        // Тест выбора заголовка: первый заголовок, затем имя файла
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "# From heading\ntext").unwrap();
+        fs::write(dir.path().join("From file.txt"), "plain text").unwrap();
+
+        let result = import_markdown_folder(dir.path(), &[], &HashSet::new()).unwrap();
+        let titles: Vec<&str> = result.notes.iter().map(|n| n.title.as_str()).collect();
+
+        assert_eq!(titles, vec!["From file", "From heading"]);
+        assert_eq!(result.notes[1].content, "# From heading\ntext");
+    }
+
+    #[test]
+    fn test_nested_dirs_become_groups() {
+        // Тест: подпапки становятся вложенными группами, существующая группа переиспользуется
+        let mut app_state = create_test_app_state();
+        let existing_id = create_test_group(&mut app_state, "Work");
+
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("Work/Sub")).unwrap();
+        fs::write(dir.path().join("Work/top.md"), "top").unwrap();
+        fs::write(dir.path().join("Work/Sub/deep.md"), "deep").unwrap();
+
+        let report = app_state.import_notes(ImportSource::MarkdownFolder, dir.path()).unwrap();
+
+        assert_eq!(report.groups_created, 1);
+        assert_groups_count(&app_state, 2);
+        let sub = app_state.groups.iter().find(|g| g.name == "Sub").unwrap();
+        assert_eq!(sub.parent_id, Some(existing_id));
+        assert_eq!(sub.level, 1);
+        let top = app_state.notes.iter().find(|n| n.title == "top").unwrap();
+        let deep = app_state.notes.iter().find(|n| n.title == "deep").unwrap();
+        assert_eq!(top.group_id, Some(existing_id));
+        assert_eq!(deep.group_id, Some(sub.id));
+    }
+
+    #[test]
+    fn test_depth_limit_reported() {
+        // Тест: папки глубже MAX_GROUP_DEPTH не импортируются и попадают в отчет
+        let dir = TempDir::new().unwrap();
+        let mut path = dir.path().to_path_buf();
+        for level in 0..=ValidationRules::MAX_GROUP_DEPTH + 1 {
+            path = path.join(format!("level{}", level));
+        }
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("lost.md"), "lost").unwrap();
+
+        let result = import_markdown_folder(dir.path(), &[], &HashSet::new()).unwrap();
+
+        assert!(result.groups.len() <= ValidationRules::MAX_GROUP_DEPTH as usize + 1);
+        assert!(result.notes.is_empty());
+        assert_eq!(result.report.skipped.len(), 1);
+        assert_eq!(result.report.skipped[0].reason, SkipReason::TooDeep);
+    }
+
+    #[test]
+    fn test_problem_files_reported() {
+        // Тест: неподдерживаемые, слишком большие и не-UTF-8 файлы не прерывают импорт
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("ok.md"), "ok").unwrap();
+        fs::write(dir.path().join("image.png"), [0u8, 1, 2]).unwrap();
+        fs::write(dir.path().join("binary.txt"), [0xffu8, 0xfe, 0x00]).unwrap();
+        fs::write(dir.path().join("big.md"), "x".repeat(ValidationRules::MAX_CONTENT_LENGTH + 70 * 1024)).unwrap();
+        fs::write(dir.path().join(".hidden.md"), "hidden").unwrap();
+
+        let result = import_markdown_folder(dir.path(), &[], &HashSet::new()).unwrap();
+
+        assert_eq!(result.notes.len(), 1);
+        let reasons: Vec<(String, &SkipReason)> = result.report.skipped.iter()
+            .map(|s| (s.path.file_name().unwrap().to_string_lossy().into_owned(), &s.reason))
+            .collect();
+        assert_eq!(reasons.len(), 3);
+        assert!(reasons.iter().any(|(name, r)| name == "image.png" && **r == SkipReason::UnsupportedType));
+        assert!(reasons.iter().any(|(name, r)| name == "binary.txt" && **r == SkipReason::NotUtf8));
+        assert!(reasons.iter().any(|(name, r)| name == "big.md" && matches!(r, SkipReason::TooLarge(_))));
+    }
+
+    #[test]
+    fn test_duplicate_id_skipped() {
+        // Тест: повторный импорт той же папки не создает дубликаты
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Note", "Content");
+        let dir = TempDir::new().unwrap();
+        app_state.export_markdown(dir.path()).unwrap();
+
+        let report = app_state.import_notes(ImportSource::MarkdownFolder, dir.path()).unwrap();
+
+        assert_eq!(report.notes_imported, 0);
+        assert_eq!(report.skipped[0].reason, SkipReason::AlreadyExists);
+        assert_notes_count(&app_state, 1);
+    }
+
+    #[test]
+    fn test_invalid_tags_dropped() {
+        // Тест: невалидные теги отбрасываются, остальные нормализуются
+        let dir = TempDir::new().unwrap();
+        let long_tag = "t".repeat(ValidationRules::MAX_TAG_LENGTH + 1);
+        fs::write(dir.path().join("n.md"), format!("---\ntags: [Rust, rust, {}]\n---\nBody", long_tag)).unwrap();
+
+        let result = import_markdown_folder(dir.path(), &[], &HashSet::new()).unwrap();
+
+        assert_eq!(result.notes[0].tags, vec!["rust"]);
+    }
+
+    #[test]
+    fn test_undo_import() {
+        // Тест: импорт отменяется одним шагом
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Existing", "Content");
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("Group")).unwrap();
+        fs::write(dir.path().join("Group/a.md"), "a").unwrap();
+        fs::write(dir.path().join("b.md"), "b").unwrap();
+
+        app_state.import_notes(ImportSource::MarkdownFolder, dir.path()).unwrap();
+        assert_notes_count(&app_state, 3);
+        assert_groups_count(&app_state, 1);
+
+        assert!(app_state.undo().unwrap());
+        assert_notes_count(&app_state, 1);
+        assert_groups_count(&app_state, 0);
+        assert_eq!(app_state.store.get_all_notes().unwrap().len(), 1);
+
+        assert!(app_state.redo().unwrap());
+        assert_notes_count(&app_state, 3);
+    }
+
+    #[test]
+    fn test_missing_source_dir_is_error() {
+        // Тест: отсутствующая папка - ошибка всего импорта
+        let mut app_state = create_test_app_state();
+        let dir = TempDir::new().unwrap();
+
+        assert!(app_state.import_notes(ImportSource::MarkdownFolder, &dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_obsidian_tags_and_wikilinks() {
+        // Тест Obsidian: теги из текста и вики-ссылки на названия импортированных заметок
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".obsidian")).unwrap();
+        fs::write(dir.path().join(".obsidian/app.json"), "{}").unwrap();
+        fs::create_dir(dir.path().join("Projects")).unwrap();
+        fs::write(dir.path().join("Projects/plan-2024.md"), "# Plan\nSee [[Ideas#Top|ideas]] and [[Missing]] #Work #1\n```\n#not-a-tag\n```").unwrap();
+        fs::write(dir.path().join("Ideas.md"), "---\ntitle: Big ideas\ntags: [draft]\n---\nBack to [[Projects/plan-2024]] #project/alpha ![[Ideas]]").unwrap();
+
+        let result = import_notes(ImportSource::Obsidian, dir.path(), &[], &HashSet::new()).unwrap();
+
+        assert!(result.report.skipped.is_empty());
+        assert_eq!(result.groups.len(), 1);
+        let plan = result.notes.iter().find(|n| n.title == "Plan").unwrap();
+        let ideas = result.notes.iter().find(|n| n.title == "Big ideas").unwrap();
+        assert_eq!(plan.tags, vec!["work"]);
+        assert_eq!(ideas.tags, vec!["draft", "project/alpha"]);
+        assert!(plan.content.contains("See [[Big ideas|ideas]] and [[Missing]]"));
+        assert!(ideas.content.contains("Back to [[Plan]]"));
+        assert!(ideas.content.contains("![[Ideas]]"));
+    }
+
+    #[test]
+    fn test_joplin_raw_import() {
+        // Тест Joplin RAW: блокноты с вложенностью, теги и даты заметок
+        let dir = TempDir::new().unwrap();
+        let parent = "11111111111111111111111111111111";
+        let child = "22222222222222222222222222222222";
+        let note_id = "33333333333333333333333333333333";
+        let tag_id = "44444444444444444444444444444444";
+        fs::write(dir.path().join(format!("{}.md", child)), format!("Child\n\nid: {}\nparent_id: {}\ntype_: 2", child, parent)).unwrap();
+        fs::write(dir.path().join(format!("{}.md", parent)), format!("Parent\n\nid: {}\nparent_id: \ntype_: 2", parent)).unwrap();
+        fs::write(dir.path().join(format!("{}.md", note_id)), format!(
+            "Joplin note\n\nFirst line\nkey: value in body\n\nLast line\n\nid: {}\nparent_id: {}\ncreated_time: 2020-01-02T03:04:05.000Z\nupdated_time: 2021-01-01T00:00:00.000Z\nuser_updated_time: 2020-06-01T00:00:00.000Z\nis_todo: 0\ntype_: 1",
+            note_id, child
+        )).unwrap();
+        fs::write(dir.path().join(format!("{}.md", tag_id)), format!("Reference Docs\n\nid: {}\ntype_: 5", tag_id)).unwrap();
+        fs::write(dir.path().join("55555555555555555555555555555555.md"), format!("id: 55555555555555555555555555555555\nnote_id: {}\ntag_id: {}\ntype_: 6", note_id, tag_id)).unwrap();
+
+        let mut app_state = create_test_app_state();
+        let report = app_state.import_notes(ImportSource::JoplinRaw, dir.path()).unwrap();
+
+        assert_eq!(report.notes_imported, 1);
+        assert_eq!(report.groups_created, 2);
+        let child_group = app_state.groups.iter().find(|g| g.name == "Child").unwrap();
+        let parent_group = app_state.groups.iter().find(|g| g.name == "Parent").unwrap();
+        assert_eq!(child_group.parent_id, Some(parent_group.id));
+        assert_eq!(child_group.level, 1);
+
+        let note = &app_state.notes[0];
+        assert_eq!(note.id.simple().to_string(), note_id);
+        assert_eq!(note.title, "Joplin note");
+        assert_eq!(note.content, "First line\nkey: value in body\n\nLast line");
+        assert_eq!(note.group_id, Some(child_group.id));
+        assert_eq!(note.tags, vec!["reference-docs"]);
+        assert_eq!(note.created_at.to_rfc3339(), "2020-01-02T03:04:05+00:00");
+        assert_eq!(note.updated_at.to_rfc3339(), "2020-06-01T00:00:00+00:00");
+
+        // Повторный импорт не создает дубликатов
+        let report = app_state.import_notes(ImportSource::JoplinRaw, dir.path()).unwrap();
+        assert_eq!(report.notes_imported, 0);
+        assert_eq!(report.groups_created, 0);
+        assert_notes_count(&app_state, 1);
+    }
+
+    #[test]
+    fn test_enex_import() {
+        // Тест Evernote: блокнот - группа, ENML переводится в Markdown, даты и теги сохраняются
+        let dir = TempDir::new().unwrap();
+        let enex = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE en-export SYSTEM "http://xml.evernote.com/pub/evernote-export3.dtd">
+<en-export export-date="20240101T000000Z" application="Evernote" version="10">
+  <note>
+    <title>Recipes &amp; tips</title>
+    <content><![CDATA[<?xml version="1.0" encoding="UTF-8"?><!DOCTYPE en-note SYSTEM "http://xml.evernote.com/pub/enml2.dtd"><en-note><h1>Soup</h1><div>Boil <b>water</b>&nbsp;first.</div><ul><li>Salt</li><li><en-todo checked="true"/>Pepper</li></ul><ol><li>One</li><li>Two</li></ol><div><a href="https://example.com">link</a><br/></div><en-media type="image/png" hash="abc"/></en-note>]]></content>
+    <created>20200115T101500Z</created>
+    <updated>20200116T000000Z</updated>
+    <tag>kitchen stuff</tag>
+    <tag>Food</tag>
+    <resource><data encoding="base64">AAAA</data></resource>
+  </note>
+</en-export>"#;
+        fs::write(dir.path().join("Cooking.enex"), enex).unwrap();
+
+        let result = import_notes(ImportSource::Enex, dir.path(), &[], &HashSet::new()).unwrap();
+
+        assert!(result.report.skipped.is_empty());
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.groups[0].name, "Cooking");
+        let note = &result.notes[0];
+        assert_eq!(note.title, "Recipes & tips");
+        assert_eq!(note.group_id, Some(result.groups[0].id));
+        assert_eq!(note.tags, vec!["kitchen-stuff", "food"]);
+        assert_eq!(note.created_at.to_rfc3339(), "2020-01-15T10:15:00+00:00");
+        assert_eq!(note.updated_at.to_rfc3339(), "2020-01-16T00:00:00+00:00");
+        assert_eq!(
+            note.content,
+            "# Soup\nBoil **water** first.\n- Salt\n- [x] Pepper\n1. One\n2. Two\n[link](https://example.com)\n[вложение]"
+        );
+    }
+
+    #[test]
+    fn test_enex_broken_file_reported() {
+        // Тест: поврежденный .enex попадает в отчет, прочитанные до ошибки заметки сохраняются
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Broken.enex");
+        fs::write(&path, "<en-export><note><title>Ok</title><content>text</content></note><note><title>Cut").unwrap();
+
+        let result = import_notes(ImportSource::Enex, &path, &[], &HashSet::new()).unwrap();
+
+        assert_eq!(result.notes.len(), 1);
+        assert_eq!(result.notes[0].content, "text");
+        assert_eq!(result.report.skipped.len(), 1);
+        assert!(matches!(result.report.skipped[0].reason, SkipReason::Invalid(_)));
+        assert!(import_notes(ImportSource::Enex, &dir.path().join("missing.enex"), &[], &HashSet::new()).is_err());
+    }
+}
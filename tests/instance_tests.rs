@@ -0,0 +1,161 @@
+use fast_bind::cli::{self, CliError, Command, STDIN_MARKER};
+use fast_bind::error::AppError;
+use fast_bind::instance::{self, Instance, InstanceLock, IpcRequest, IpcServer};
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use eframe::egui;
+    use uuid::Uuid;
+
+    /// Ждет запрос на сервере и отвечает на него
+    fn answer_next(server: &IpcServer, reply: fast_bind::instance::IpcReply) -> IpcRequest {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(call) = server.try_recv() {
+                let request = call.request.clone();
+                call.reply(reply);
+                return request;
+            }
+            assert!(Instant::now() < deadline, "запрос не пришел");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_lock_is_exclusive_until_dropped() {
+        // Тест что второй захват блокировки не удается, пока первая не освобождена
+        let temp_dir = TempDir::new().unwrap();
+
+        let lock = InstanceLock::acquire(temp_dir.path()).unwrap();
+        assert!(lock.is_some());
+        assert!(InstanceLock::acquire(temp_dir.path()).unwrap().is_none());
+
+        drop(lock);
+        assert!(InstanceLock::acquire(temp_dir.path()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_acquire_or_forward_takes_free_lock() {
+        // Тест что без запущенного приложения процесс становится основным экземпляром
+        let temp_dir = TempDir::new().unwrap();
+
+        let instance = instance::acquire_or_forward(temp_dir.path(), &IpcRequest::ShowWindow).unwrap();
+
+        assert!(matches!(instance, Instance::Primary(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_second_launch_forwards_request() {
+        // Тест что при занятой блокировке запрос передается запущенному приложению
+        let temp_dir = TempDir::new().unwrap();
+        let _lock = InstanceLock::acquire(temp_dir.path()).unwrap().unwrap();
+        let server = IpcServer::start(temp_dir.path(), &egui::Context::default()).unwrap();
+        let note_id = Uuid::new_v4();
+
+        let base_dir = temp_dir.path().to_path_buf();
+        let client = std::thread::spawn(move || {
+            instance::acquire_or_forward(&base_dir, &IpcRequest::OpenNote { note: "Todo".to_string() })
+        });
+        let request = answer_next(&server, Ok(Some(note_id)));
+
+        assert_eq!(request, IpcRequest::OpenNote { note: "Todo".to_string() });
+        match client.join().unwrap().unwrap() {
+            Instance::Forwarded(reply) => assert_eq!(reply, Ok(Some(note_id))),
+            Instance::Primary(_) => panic!("блокировка должна быть занята"),
+        }
+    }
+
+    #[test]
+    fn test_silent_lock_owner_reports_lock_held() {
+        // Тест что блокировка без отвечающего приложения дает ошибку занятой папки
+        let temp_dir = TempDir::new().unwrap();
+        let _lock = InstanceLock::acquire(temp_dir.path()).unwrap().unwrap();
+
+        let result = instance::acquire_or_forward(temp_dir.path(), &IpcRequest::ShowWindow);
+
+        assert!(matches!(result, Err(AppError::LockHeld(ref path)) if path == temp_dir.path()));
+        assert_eq!(CliError::from(result.err().unwrap()).exit_code(), cli::EXIT_BUSY);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_error_reply_keeps_exit_code() {
+        // Тест что ошибка приложения возвращается клиенту с тем же видом
+        let temp_dir = TempDir::new().unwrap();
+        let server = IpcServer::start(temp_dir.path(), &egui::Context::default()).unwrap();
+
+        let base_dir = temp_dir.path().to_path_buf();
+        let client = std::thread::spawn(move || {
+            instance::send_request(&base_dir, &IpcRequest::CopyNote { note: "missing".to_string(), fields: vec![] })
+        });
+        answer_next(&server, Err(CliError::NotFound("заметка 'missing' не найдена".to_string())));
+
+        let reply = client.join().unwrap().unwrap();
+        assert_eq!(reply.unwrap_err().exit_code(), cli::EXIT_NOT_FOUND);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_malformed_request_is_rejected() {
+        // Тест что неразобранный запрос получает ошибку, не доходя до приложения
+        use std::io::{BufRead, BufReader, Write};
+        use std::os::unix::net::UnixStream;
+
+        let temp_dir = TempDir::new().unwrap();
+        let server = IpcServer::start(temp_dir.path(), &egui::Context::default()).unwrap();
+
+        let mut stream = UnixStream::connect(temp_dir.path().join(instance::SOCKET_FILE_NAME)).unwrap();
+        stream.write_all(b"{\"command\":\"format_disk\"}\n").unwrap();
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line).unwrap();
+
+        let reply: fast_bind::instance::IpcReply = serde_json::from_str(&line).unwrap();
+        assert_eq!(reply.unwrap_err().exit_code(), cli::EXIT_USAGE);
+        assert!(server.try_recv().is_none());
+    }
+
+    #[test]
+    fn test_socket_removed_with_server() {
+        // Тест что сокет удаляется при закрытии приложения
+        let temp_dir = TempDir::new().unwrap();
+        let socket_path = temp_dir.path().join(instance::SOCKET_FILE_NAME);
+
+        if let Ok(server) = IpcServer::start(temp_dir.path(), &egui::Context::default()) {
+            assert!(socket_path.exists());
+            drop(server);
+        }
+        assert!(!socket_path.exists());
+    }
+
+    #[test]
+    fn test_forwarded_request_reads_stdin_once() {
+        // Тест что содержимое из stdin попадает в запрос и сохраняется в команде
+        let mut command = Command::Add {
+            title: "Piped".to_string(),
+            group: None,
+            tags: vec![],
+            content: Some(STDIN_MARKER.to_string()),
+        };
+
+        let request = cli::forwarded_request(&mut command, &mut "text".as_bytes()).unwrap();
+
+        assert!(matches!(request, Some(IpcRequest::CreateNote { ref content, .. }) if content == "text"));
+        assert!(matches!(command, Command::Add { content: Some(ref content), .. } if content == "text"));
+    }
+
+    #[test]
+    fn test_only_some_commands_are_forwarded() {
+        // Тест что изменение и удаление не передаются приложению, а чтение не требует блокировки
+        let mut rm = Command::Rm { note: "x".to_string() };
+        let mut open = Command::Open { note: None };
+
+        assert_eq!(cli::forwarded_request(&mut rm, &mut std::io::empty()).unwrap(), None);
+        assert_eq!(cli::forwarded_request(&mut open, &mut std::io::empty()).unwrap(), Some(IpcRequest::ShowWindow));
+        assert!(!rm.is_read_only());
+        assert!(Command::Groups.is_read_only());
+    }
+}
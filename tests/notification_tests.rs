@@ -0,0 +1,114 @@
+use fast_bind::error::AppError;
+use fast_bind::notifications::{NotificationCenter, NotificationLevel, HISTORY_LIMIT, TOAST_DURATION};
+use fast_bind::validation::{FormField, ValidationError, ValidationRules};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage_error() -> AppError {
+        AppError::Io(std::io::Error::other("disk full"))
+    }
+
+    #[test]
+    fn test_toast_expires_but_stays_in_history() {
+        // Тест что всплывающее уведомление исчезает через заданное время, а в истории остается
+        let mut center = NotificationCenter::new();
+        center.success("Заметка создана");
+
+        center.expire_toasts(Instant::now());
+        assert_eq!(center.toasts().count(), 1);
+        assert_eq!(center.next_expiry(Instant::now()).map(|d| d <= TOAST_DURATION), Some(true));
+
+        center.expire_toasts(Instant::now() + TOAST_DURATION + Duration::from_millis(1));
+        assert_eq!(center.toasts().count(), 0);
+        assert_eq!(center.next_expiry(Instant::now()), None);
+        let history: Vec<_> = center.history().collect();
+        assert_eq!(history.len(), 1);
+        assert_eq!((history[0].level, history[0].message.as_str()), (NotificationLevel::Success, "Заметка создана"));
+    }
+
+    #[test]
+    fn test_storage_failure_is_pinned() {
+        // Тест что сбой хранилища закрепляется до закрытия, а ошибка ввода всплывает на время
+        let mut center = NotificationCenter::new();
+        center.error("Ошибка сохранения заметки", &storage_error());
+        center.error("Ошибка создания группы", &AppError::GroupCycle(Uuid::new_v4()));
+
+        center.expire_toasts(Instant::now() + TOAST_DURATION * 2);
+        let banners: Vec<_> = center.banners().collect();
+        assert_eq!(banners.len(), 1);
+        assert_eq!(banners[0].code, Some("io"));
+        assert!(banners[0].message.starts_with("Ошибка сохранения заметки: "));
+        assert_eq!(center.toasts().count(), 0);
+
+        let banner_id = banners[0].id;
+        center.dismiss(banner_id);
+        assert_eq!(center.banners().count(), 0);
+        assert_eq!(center.history().count(), 2);
+    }
+
+    #[test]
+    fn test_repeated_notification_is_collapsed() {
+        // Тест что одинаковые уведомления подряд не засоряют историю
+        let mut center = NotificationCenter::new();
+        for _ in 0..3 {
+            center.error("Ошибка дозагрузки заметок", &storage_error());
+        }
+
+        let history: Vec<_> = center.history().collect();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].repeats, 3);
+        assert_eq!(center.banners().count(), 1);
+        assert_eq!(center.unread_count(), 1);
+
+        // Закрытая ошибка появляется снова, если повторится
+        let id = history[0].id;
+        center.dismiss(id);
+        center.error("Ошибка дозагрузки заметок", &storage_error());
+        assert_eq!(center.banners().map(|n| n.id).collect::<Vec<_>>(), [id]);
+    }
+
+    #[test]
+    fn test_history_limit_and_clear() {
+        // Тест ограничения истории, отметки прочитанного и очистки
+        let mut center = NotificationCenter::new();
+        center.error("Старая ошибка", &storage_error());
+        for i in 0..HISTORY_LIMIT {
+            center.info(format!("Событие {}", i));
+        }
+
+        assert_eq!(center.history().count(), HISTORY_LIMIT);
+        assert_eq!(center.unread_count(), HISTORY_LIMIT);
+        assert_eq!(center.history().next().unwrap().message, format!("Событие {}", HISTORY_LIMIT - 1));
+        // Вытесненная из истории ошибка больше не закреплена
+        assert_eq!(center.banners().count(), 0);
+
+        center.mark_read();
+        assert_eq!(center.unread_count(), 0);
+        center.success("Готово");
+        assert_eq!(center.unread_count(), 1);
+
+        center.clear_history();
+        assert_eq!((center.history().count(), center.toasts().count(), center.unread_count()), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_validation_errors_map_to_form_fields() {
+        // Тест что ошибки проверки формы относятся к своим полям
+        let long_title = "x".repeat(300);
+        let error = ValidationRules::validate_note_creation(&long_title, "").into_result().unwrap_err();
+        let fields: Vec<_> = error.validation_errors().iter().map(ValidationError::field).collect();
+        assert_eq!(fields, [FormField::Title]);
+
+        let group_id = Uuid::new_v4();
+        assert_eq!(AppError::GroupNotFound(group_id).validation_errors()[0].field(), FormField::Group);
+        assert_eq!(AppError::GroupCycle(group_id).validation_errors()[0].field(), FormField::Group);
+        assert_eq!(ValidationError::EmptyGroupName.field(), FormField::GroupName);
+        assert!(storage_error().validation_errors().is_empty());
+        assert!(storage_error().is_storage_failure());
+        assert!(!AppError::NoteNotFound.is_storage_failure());
+    }
+}
@@ -0,0 +1,116 @@
+use chrono::{Duration, Utc};
+use fast_bind::notes::{NoteMetadata, NotesManager};
+use fast_bind::quick_picker::{fuzzy_score, parse_hotkey, rank_notes, DEFAULT_QUICK_PICKER_HOTKEY};
+use fast_bind::state::{AppState, LoadMode};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(title: &str, pinned: bool, age_minutes: i64) -> NoteMetadata {
+        let time = Utc::now() - Duration::minutes(age_minutes);
+        NoteMetadata {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            created_at: time,
+            updated_at: time,
+            pinned,
+            group_id: None,
+            tags: Vec::new(),
+            content_length: 0,
+            copy_count: 0,
+            last_used_at: None,
+        }
+    }
+
+    fn titles(notes: Vec<NoteMetadata>, query: &str) -> Vec<String> {
+        rank_notes(notes, query, 10).into_iter().map(|m| m.note.title).collect()
+    }
+
+    #[test]
+    fn test_fuzzy_score_subsequence() {
+        // Тест нечеткого совпадения: символы по порядку, без учета регистра
+        assert!(fuzzy_score("пдп", "Подпись письма").is_some());
+        assert!(fuzzy_score("SIG", "email signature").is_some());
+        assert!(fuzzy_score("gis", "signature").is_none());
+        assert_eq!(fuzzy_score("", "что угодно"), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_starts_and_runs() {
+        // Тест что совпадение подряд и с начала слова ценнее разбросанного
+        let prefix = fuzzy_score("sig", "signature").unwrap();
+        let word_start = fuzzy_score("sig", "email signature").unwrap();
+        let scattered = fuzzy_score("sig", "ssh config").unwrap();
+
+        assert!(prefix >= word_start);
+        assert!(word_start > scattered);
+    }
+
+    #[test]
+    fn test_rank_pinned_first_then_score() {
+        // Тест порядка: закрепленные, затем лучшее совпадение, затем недавно измененные
+        let notes = vec![
+            meta("ssh config", false, 0),
+            meta("signature", false, 10),
+            meta("sig old", false, 20),
+            meta("pinned sig", true, 30),
+            meta("unrelated", false, 0),
+        ];
+
+        assert_eq!(titles(notes, "sig"), vec!["pinned sig", "signature", "sig old", "ssh config"]);
+    }
+
+    #[test]
+    fn test_rank_empty_query_by_recency() {
+        // Тест пустого запроса: все заметки, недавние выше
+        let notes = vec![meta("old", false, 60), meta("new", false, 1), meta("pin", true, 120)];
+
+        assert_eq!(titles(notes, ""), vec!["pin", "new", "old"]);
+    }
+
+    #[test]
+    fn test_rank_matches_tags_and_limit() {
+        // Тест поиска по тегам и ограничения длины списка
+        let mut tagged = meta("Ответ клиенту", false, 0);
+        tagged.tags = vec!["support".to_string()];
+        let mut notes = vec![tagged];
+        notes.extend((0..30).map(|i| meta(&format!("note {}", i), false, i)));
+
+        assert_eq!(titles(notes.clone(), "#supp"), vec!["Ответ клиенту"]);
+        assert_eq!(rank_notes(notes, "note", 5).len(), 5);
+    }
+
+    #[test]
+    fn test_parse_hotkey() {
+        // Тест разбора глобального сочетания из настроек
+        assert!(parse_hotkey(DEFAULT_QUICK_PICKER_HOTKEY).unwrap().is_some());
+        assert!(parse_hotkey("  ").unwrap().is_none());
+        assert!(parse_hotkey("Ctrl+Banana").is_err());
+    }
+
+    #[test]
+    fn test_candidates_include_unloaded_notes_in_lazy_mode() {
+        // Тест что в ленивом режиме в быстрый выбор попадают и незагруженные заметки
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+            create_test_note(&mut app_state, "Уникальная заметка", "Content");
+            create_multiple_test_notes(&mut app_state, 30);
+            assert_eq!(app_state.quick_picker_candidates().len(), 31);
+        }
+
+        let app_state = AppState::with_load_mode(NotesManager::new(temp_dir.path().to_path_buf()), LoadMode::Lazy);
+        assert!(!app_state.notes.iter().any(|n| n.title == "Уникальная заметка"));
+
+        let found = rank_notes(app_state.quick_picker_candidates(), "уникальная", 10);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].note.title, "Уникальная заметка");
+    }
+}
@@ -0,0 +1,179 @@
+use fast_bind::error::AppError;
+use fast_bind::notes::{NoteSortMode, NotesManager};
+use fast_bind::sqlite_store::SqliteNoteStore;
+use fast_bind::state::{AppState, LoadMode};
+use fast_bind::validation::ValidationRules;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Заголовки заметок группы в ручном порядке
+    fn manual_titles(app_state: &mut AppState, group_id: Option<Uuid>) -> Vec<String> {
+        app_state.sort_mode = NoteSortMode::Manual;
+        app_state.group_note_indices(group_id).into_iter()
+            .map(|i| app_state.notes[i].title.clone())
+            .collect()
+    }
+
+    fn note_id(app_state: &AppState, title: &str) -> Uuid {
+        app_state.notes.iter().find(|n| n.title == title).unwrap().id
+    }
+
+    fn group_level(app_state: &AppState, group_id: Uuid) -> u32 {
+        app_state.groups.iter().find(|g| g.id == group_id).unwrap().level
+    }
+
+    #[test]
+    fn test_new_notes_appended_to_manual_order() {
+        // Тест что новые заметки добавляются в конец ручного порядка своей группы
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Group");
+        create_test_note_with_group(&mut app_state, "First", "Content", Some(group_id));
+        create_test_note_with_group(&mut app_state, "Second", "Content", Some(group_id));
+        create_test_note(&mut app_state, "Ungrouped", "Content");
+
+        assert_eq!(manual_titles(&mut app_state, Some(group_id)), ["First", "Second"]);
+        assert_eq!(app_state.notes.iter().find(|n| n.title == "Ungrouped").unwrap().sort_index, 0);
+    }
+
+    #[test]
+    fn test_move_note_within_group() {
+        // Тест перестановки заметок внутри группы
+        let mut app_state = create_test_app_state();
+        for title in ["A", "B", "C"] {
+            create_test_note(&mut app_state, title, "Content");
+        }
+
+        let c = note_id(&app_state, "C");
+        let a = note_id(&app_state, "A");
+        app_state.move_note(c, None, Some(a)).unwrap();
+        assert_eq!(manual_titles(&mut app_state, None), ["C", "A", "B"]);
+
+        app_state.move_note(c, None, None).unwrap();
+        assert_eq!(manual_titles(&mut app_state, None), ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_move_note_between_groups_is_single_undo() {
+        // Тест переноса заметки в другую группу и его отмены одним действием
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Target");
+        create_test_note_with_group(&mut app_state, "X", "Content", Some(group_id));
+        create_test_note_with_group(&mut app_state, "Y", "Content", Some(group_id));
+        create_test_note(&mut app_state, "Moved", "Content");
+
+        let moved = note_id(&app_state, "Moved");
+        let y = note_id(&app_state, "Y");
+        app_state.move_note(moved, Some(group_id), Some(y)).unwrap();
+        assert_eq!(manual_titles(&mut app_state, Some(group_id)), ["X", "Moved", "Y"]);
+        assert!(manual_titles(&mut app_state, None).is_empty());
+
+        assert!(app_state.undo().unwrap());
+        assert_eq!(manual_titles(&mut app_state, Some(group_id)), ["X", "Y"]);
+        assert_eq!(manual_titles(&mut app_state, None), ["Moved"]);
+
+        assert!(app_state.redo().unwrap());
+        assert_eq!(manual_titles(&mut app_state, Some(group_id)), ["X", "Moved", "Y"]);
+    }
+
+    #[test]
+    fn test_move_note_to_missing_group_fails() {
+        // Тест что заметку нельзя перенести в несуществующую группу
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Note", "Content");
+        let id = note_id(&app_state, "Note");
+
+        let missing = Uuid::new_v4();
+        assert!(matches!(app_state.move_note(id, Some(missing), None), Err(AppError::GroupNotFound(group_id)) if group_id == missing));
+        assert_eq!(app_state.notes[0].group_id, None);
+    }
+
+    #[test]
+    fn test_manual_order_persists() {
+        // Тест что ручной порядок сохраняется в JSON и SQLite
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("notes.db");
+        let json_dir = temp_dir.path().join("data");
+
+        for sqlite in [false, true] {
+            let open = || if sqlite {
+                AppState::with_load_mode(SqliteNoteStore::open(&db_path).unwrap(), LoadMode::Auto)
+            } else {
+                AppState::with_load_mode(NotesManager::new(json_dir.clone()), LoadMode::Auto)
+            };
+            {
+                let mut app_state = open();
+                for title in ["A", "B", "C"] {
+                    create_test_note(&mut app_state, title, "Content");
+                }
+                let (b, a) = (note_id(&app_state, "B"), note_id(&app_state, "A"));
+                app_state.move_note(b, None, Some(a)).unwrap();
+            }
+
+            let mut app_state = open();
+            assert_eq!(manual_titles(&mut app_state, None), ["B", "A", "C"]);
+        }
+    }
+
+    #[test]
+    fn test_move_group_updates_levels() {
+        // Тест переноса группы вместе с подгруппами и его отмены
+        let mut app_state = create_test_app_state();
+        let (root, child, grandchild) = create_test_group_hierarchy(&mut app_state);
+        let other = create_test_group(&mut app_state, "Other");
+
+        app_state.move_group(child, Some(other)).unwrap();
+        assert_eq!(app_state.groups.iter().find(|g| g.id == child).unwrap().parent_id, Some(other));
+        assert_eq!(group_level(&app_state, child), 1);
+        assert_eq!(group_level(&app_state, grandchild), 2);
+
+        app_state.move_group(child, None).unwrap();
+        assert_eq!(group_level(&app_state, child), 0);
+        assert_eq!(group_level(&app_state, grandchild), 1);
+
+        assert!(app_state.undo().unwrap());
+        assert!(app_state.undo().unwrap());
+        assert_eq!(app_state.groups.iter().find(|g| g.id == child).unwrap().parent_id, Some(root));
+        assert_eq!(group_level(&app_state, grandchild), 2);
+    }
+
+    #[test]
+    fn test_move_group_into_own_subgroup_rejected() {
+        // Тест что группу нельзя перенести внутрь ее собственной подгруппы
+        let mut app_state = create_test_app_state();
+        let (root, _child, grandchild) = create_test_group_hierarchy(&mut app_state);
+
+        assert!(matches!(app_state.move_group(root, Some(grandchild)), Err(AppError::GroupCycle(_))));
+        assert!(matches!(app_state.move_group(root, Some(root)), Err(AppError::GroupCycle(_))));
+        assert_eq!(app_state.groups.iter().find(|g| g.id == root).unwrap().parent_id, None);
+        assert_ne!(app_state.undo_stack.undo_label(), Some("Перемещение группы"));
+    }
+
+    #[test]
+    fn test_move_group_respects_depth_limit() {
+        // Тест что перенос не выводит подгруппы за предел вложенности
+        let mut app_state = create_test_app_state();
+        let mut parent = None;
+        let mut deepest = Uuid::nil();
+        for level in 0..=ValidationRules::MAX_GROUP_DEPTH {
+            deepest = create_test_group_full(&mut app_state, &format!("Level {}", level), parent, vec![]);
+            parent = Some(deepest);
+        }
+        let branch = create_test_group(&mut app_state, "Branch");
+        create_test_group_full(&mut app_state, "Leaf", Some(branch), vec![]);
+
+        let level = |l: u32| app_state.groups.iter().find(|g| g.level == l && g.id != branch).unwrap().id;
+        let (fits, too_deep) = (level(ValidationRules::MAX_GROUP_DEPTH - 2), level(ValidationRules::MAX_GROUP_DEPTH - 1));
+
+        // Сама группа поместилась бы, но ее подгруппа - уже нет
+        assert!(matches!(app_state.move_group(branch, Some(too_deep)), Err(AppError::Validation(_))));
+        assert!(app_state.move_group(branch, Some(deepest)).is_err());
+        assert!(app_state.move_group(branch, Some(fits)).is_ok());
+    }
+}
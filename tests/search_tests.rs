@@ -0,0 +1,129 @@
+use fast_bind::notes::NotesManager;
+use fast_bind::search::NoteSearch;
+use fast_bind::state::{AppState, LoadMode};
+use tempfile::TempDir;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_empty_query_returns_nothing() {
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Test Note", "Content");
+
+        assert!(app_state.search_notes("   ").is_empty());
+    }
+
+    #[test]
+    fn test_search_is_case_insensitive_for_cyrillic() {
+        // Тест поиска кириллицы без учета регистра
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Привет Мир", "Содержимое");
+
+        let results = app_state.search_notes("пРИВЕТ");
+
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_search_treats_yo_as_ye() {
+        // Тест что "ё" и "е" считаются одной буквой
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Заметка", "Ёлка в лесу");
+
+        assert_eq!(app_state.search_notes("елка").len(), 1);
+    }
+
+    #[test]
+    fn test_search_matches_content() {
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "First", "SELECT * FROM users");
+        create_test_note(&mut app_state, "Second", "Nothing here");
+
+        let results = app_state.search_notes("from users");
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "First");
+    }
+
+    #[test]
+    fn test_search_requires_all_terms() {
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Docker", "docker compose up");
+
+        assert!(app_state.search_notes("docker kubernetes").is_empty());
+    }
+
+    #[test]
+    fn test_search_ranks_title_matches_first() {
+        // Тест что совпадение в заголовке важнее совпадения в содержимом
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Other", "git rebase");
+        create_test_note(&mut app_state, "Git commands", "misc");
+
+        let results = app_state.search_notes("git");
+
+        assert_eq!(results[0].title, "Git commands");
+    }
+
+    #[test]
+    fn test_search_highlight_ranges_point_to_match() {
+        // Тест что диапазоны подсветки указывают на найденный текст
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Команды Git", "Текст");
+
+        let results = app_state.search_notes("git");
+        let result = &results[0];
+        let range = result.title_matches[0].clone();
+
+        assert_eq!(&result.title[range], "Git");
+    }
+
+    #[test]
+    fn test_search_snippet_highlight_ranges_point_to_match() {
+        let search = NoteSearch::new("нужное");
+        let mut app_state = create_test_app_state();
+        let content = format!("{} нужное слово", "длинный текст ".repeat(10));
+        create_test_note(&mut app_state, "Заметка", &content);
+
+        let result = search.match_note(&app_state.notes[0]).unwrap();
+        let range = result.snippet_matches[0].clone();
+
+        assert_eq!(&result.snippet[range], "нужное");
+    }
+
+    #[test]
+    fn test_search_in_lazy_mode_finds_unloaded_notes() {
+        // Тест что в ленивом режиме поиск идет по заметкам на диске
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+            // Самая старая заметка попадает на последнюю страницу
+            create_test_note(&mut app_state, "Уникальная заметка", "Content");
+            create_multiple_test_notes(&mut app_state, 30);
+        }
+
+        let app_state = AppState::with_load_mode(NotesManager::new(temp_dir.path().to_path_buf()), LoadMode::Lazy);
+        assert!(!app_state.notes.iter().any(|n| n.title == "Уникальная заметка"));
+
+        let results = app_state.search_notes("уникальная");
+
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_ensure_note_loaded_loads_note_from_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+        create_test_note(&mut app_state, "On disk", "Content");
+        let note_id = app_state.notes[0].id;
+        app_state.notes.clear();
+
+        assert!(app_state.ensure_note_loaded(note_id));
+        assert_eq!(app_state.note(note_id).map(|n| n.id), Some(note_id));
+    }
+}
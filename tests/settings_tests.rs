@@ -0,0 +1,83 @@
+use fast_bind::backup::DEFAULT_DAILY_BACKUPS_TO_KEEP;
+use fast_bind::quick_picker::DEFAULT_QUICK_PICKER_HOTKEY;
+use fast_bind::settings::{AppSettings, SettingsManager, SETTINGS_FILE_NAME, SETTINGS_VERSION};
+use fast_bind::state::LoadMode;
+use fast_bind::storage::StorageBackend;
+use fast_bind::trash::DEFAULT_TRASH_RETENTION_DAYS;
+use fast_bind::ui::ThemeMode;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_settings_missing_file_returns_defaults() {
+        // Тест загрузки настроек при отсутствии файла
+        let temp_dir = TempDir::new().unwrap();
+        let manager = SettingsManager::new(temp_dir.path());
+
+        assert_eq!(manager.load(), AppSettings::default());
+    }
+
+    #[test]
+    fn test_save_and_load_settings_roundtrip() {
+        // Тест сохранения и повторной загрузки настроек
+        let temp_dir = TempDir::new().unwrap();
+        let manager = SettingsManager::new(temp_dir.path());
+
+        let settings = AppSettings {
+            theme_mode: ThemeMode::Dark,
+            load_mode: LoadMode::Lazy,
+            show_performance_stats: true,
+            storage_backend: StorageBackend::Sqlite,
+            trash_retention_days: 7,
+            daily_backups_to_keep: 3,
+            quick_picker_hotkey: "Ctrl+Shift+K".to_string(),
+            ..AppSettings::default()
+        };
+        manager.save(&settings).unwrap();
+
+        assert_eq!(manager.load(), settings);
+    }
+
+    #[test]
+    fn test_load_corrupted_settings_returns_defaults() {
+        // Тест что повреждённый файл не мешает запуску
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(SETTINGS_FILE_NAME), "{ not json").unwrap();
+        let manager = SettingsManager::new(temp_dir.path());
+
+        assert_eq!(manager.load(), AppSettings::default());
+    }
+
+    #[test]
+    fn test_load_corrupted_settings_keeps_backup() {
+        // Тест что повреждённый файл сохраняется как резервная копия
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(SETTINGS_FILE_NAME), "{ not json").unwrap();
+        let manager = SettingsManager::new(temp_dir.path());
+
+        let _ = manager.load();
+
+        assert!(temp_dir.path().join("settings.json.bak").exists());
+    }
+
+    #[test]
+    fn test_load_partial_settings_fills_defaults() {
+        // Тест что отсутствующие поля заполняются значениями по умолчанию
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(SETTINGS_FILE_NAME), r#"{"theme_mode": "Light"}"#).unwrap();
+        let manager = SettingsManager::new(temp_dir.path());
+
+        let settings = manager.load();
+
+        assert_eq!(settings.theme_mode, ThemeMode::Light);
+        assert_eq!(settings.load_mode, LoadMode::Auto);
+        assert_eq!(settings.storage_backend, StorageBackend::Json);
+        assert_eq!(settings.trash_retention_days, DEFAULT_TRASH_RETENTION_DAYS);
+        assert_eq!(settings.daily_backups_to_keep, DEFAULT_DAILY_BACKUPS_TO_KEEP);
+        assert_eq!(settings.quick_picker_hotkey, DEFAULT_QUICK_PICKER_HOTKEY);
+        assert_eq!(settings.version, SETTINGS_VERSION);
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use chrono::{Local, TimeZone};
+use fast_bind::snippet::{self, BuiltinValues, CopyTarget, Placeholder, Segment, SnippetForm};
+use uuid::Uuid;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed_builtins() -> BuiltinValues {
+        BuiltinValues {
+            now: Local.with_ymd_and_hms(2024, 3, 5, 9, 7, 0).unwrap(),
+            clipboard: "из буфера".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_placeholders() {
+        // Тест разбора встроенных подстановок и полей
+        let segments = snippet::parse("Дата: {{date}}, клиент {{ name: Клиент }}!");
+
+        assert_eq!(segments, vec![
+            Segment::Text("Дата: ".to_string()),
+            Segment::Placeholder(Placeholder::Date),
+            Segment::Text(", клиент ".to_string()),
+            Segment::Placeholder(Placeholder::Field("Клиент".to_string())),
+            Segment::Text("!".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_unknown_placeholders_stay_text() {
+        // Тест: неизвестные, пустые и многострочные {{...}} не считаются подстановками
+        for text in ["{{unknown}}", "{{name:}}", "{{da\nte}}", "{{date", "без подстановок"] {
+            assert!(!snippet::has_placeholders(text), "{:?}", text);
+            let empty = HashMap::new();
+            assert_eq!(snippet::expand(text, &fixed_builtins(), &empty), text);
+        }
+
+        assert!(snippet::has_placeholders("{{DATE}}"));
+        assert_eq!(snippet::parse("{{x}}{{time}}"), vec![
+            Segment::Text("{{x}}".to_string()),
+            Segment::Placeholder(Placeholder::Time),
+        ]);
+    }
+
+    #[test]
+    fn test_field_names_unique_in_order() {
+        // Тест списка полей: без повторов, в порядке появления
+        let names = snippet::field_names("{{name:Б}} {{name:А}} {{date}} {{name:Б}}");
+        assert_eq!(names, vec!["Б".to_string(), "А".to_string()]);
+    }
+
+    #[test]
+    fn test_expand_builtins_and_fields() {
+        // Тест раскрытия встроенных подстановок и введенных полей
+        let mut fields = HashMap::new();
+        fields.insert("Клиент".to_string(), "ООО Ромашка".to_string());
+
+        let text = snippet::expand(
+            "{{date}} {{time}} {{clipboard}} {{name:Клиент}} [{{name:Пусто}}]",
+            &fixed_builtins(),
+            &fields,
+        );
+
+        assert_eq!(text, "2024-03-05 09:07 из буфера ООО Ромашка []");
+    }
+
+    #[test]
+    fn test_expand_uuid_unique() {
+        // Тест: каждый {{uuid}} получает свой идентификатор
+        let text = snippet::expand("{{uuid}} {{uuid}}", &fixed_builtins(), &HashMap::new());
+        let ids: Vec<uuid::Uuid> = text.split(' ').map(|id| id.parse().unwrap()).collect();
+
+        assert_eq!(ids.len(), 2);
+        assert_ne!(ids[0], ids[1]);
+    }
+
+    #[test]
+    fn test_snippet_form() {
+        // Тест формы: создается только при наличии полей и собирает введенные значения
+        assert!(SnippetForm::new(Uuid::new_v4(), "Заметка", "{{date}} {{uuid}}", CopyTarget::Clipboard).is_none());
+
+        let mut form = SnippetForm::new(Uuid::new_v4(), "Заметка", "Привет, {{name:Имя}}!", CopyTarget::PersistentText).unwrap();
+        assert_eq!(form.fields, vec![("Имя".to_string(), String::new())]);
+
+        form.fields[0].1 = "Мир".to_string();
+        let text = snippet::expand(&form.content, &fixed_builtins(), &form.values());
+        assert_eq!(text, "Привет, Мир!");
+    }
+
+    #[test]
+    fn test_append_to_persistent() {
+        // Тест добавления раскрытого текста в постоянный текст без изменения заметки
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Шаблон", "Сегодня {{date}}");
+        app_state.persistent_text = "Начало. ".to_string();
+
+        let content = app_state.get_note_content(app_state.notes[0].id).unwrap();
+        let expanded = snippet::expand(&content, &fixed_builtins(), &HashMap::new());
+        app_state.append_to_persistent(&expanded).unwrap();
+
+        assert_eq!(app_state.persistent_text, "Начало. Сегодня 2024-03-05");
+        assert_eq!(app_state.notes[0].content, "Сегодня {{date}}");
+    }
+}
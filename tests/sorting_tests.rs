@@ -0,0 +1,168 @@
+use fast_bind::notes::{NoteSortMode, NotesManager};
+use fast_bind::sqlite_store::SqliteNoteStore;
+use fast_bind::state::{AppState, LoadMode};
+use fast_bind::storage::NoteStore;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Заголовки заметок группы в порядке отображения
+    fn titles(app_state: &AppState, group_id: Option<Uuid>) -> Vec<String> {
+        app_state.group_note_indices(group_id).into_iter()
+            .map(|i| app_state.notes[i].title.clone())
+            .collect()
+    }
+
+    fn note_id(app_state: &AppState, title: &str) -> Uuid {
+        app_state.notes.iter().find(|n| n.title == title).unwrap().id
+    }
+
+    /// Три заметки без группы: "Banana" (старая), "apple", "Cherry" (новая)
+    fn create_fruit_notes(app_state: &mut AppState) {
+        for title in ["Banana", "apple", "Cherry"] {
+            create_test_note(app_state, title, "Content");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_record_note_use_updates_stats_only() {
+        // Тест что копирование увеличивает счетчик, но не меняет дату изменения и не попадает в отмену
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Snippet", "Content");
+        let id = app_state.notes[0].id;
+        let updated_at = app_state.notes[0].updated_at;
+        while app_state.undo_stack.pop_undo().is_some() {}
+
+        app_state.record_note_use(id).unwrap();
+        app_state.record_note_use(id).unwrap();
+
+        let note = &app_state.notes[0];
+        assert_eq!(note.copy_count, 2);
+        assert!(note.last_used_at.is_some());
+        assert_eq!(note.updated_at, updated_at);
+        assert!(app_state.undo_stack.pop_undo().is_none());
+    }
+
+    #[test]
+    fn test_usage_stats_persist_with_note() {
+        // Тест что статистика сохраняется вместе с заметкой в JSON и SQLite
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("notes.db");
+        let stores: Vec<Box<dyn Fn() -> Box<dyn NoteStore>>> = vec![
+            Box::new(|| Box::new(NotesManager::new(temp_dir.path().join("data")))),
+            Box::new(|| Box::new(SqliteNoteStore::open(&db_path).unwrap())),
+        ];
+
+        for open_store in stores {
+            let id = {
+                let mut app_state = AppState::with_store(open_store(), LoadMode::Auto);
+                create_test_note(&mut app_state, "Snippet", "Content");
+                let id = app_state.notes[0].id;
+                app_state.record_note_use(id).unwrap();
+                id
+            };
+
+            let app_state = AppState::with_store(open_store(), LoadMode::Auto);
+            let note = app_state.notes.iter().find(|n| n.id == id).unwrap();
+            assert_eq!(note.copy_count, 1);
+            assert!(note.last_used_at.is_some());
+        }
+    }
+
+    #[test]
+    fn test_sort_modes() {
+        // Тест порядка заметок в каждом режиме сортировки
+        let mut app_state = create_test_app_state();
+        create_fruit_notes(&mut app_state);
+        let apple = note_id(&app_state, "apple");
+        let banana = note_id(&app_state, "Banana");
+        app_state.record_note_use(apple).unwrap();
+        app_state.record_note_use(apple).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        app_state.record_note_use(banana).unwrap();
+        app_state.update_note(banana, None, Some("Changed".to_string())).unwrap();
+
+        let expected = [
+            (NoteSortMode::Created, ["Cherry", "apple", "Banana"]),
+            (NoteSortMode::RecentlyUsed, ["Banana", "apple", "Cherry"]),
+            (NoteSortMode::MostUsed, ["apple", "Banana", "Cherry"]),
+            (NoteSortMode::Alphabetical, ["apple", "Banana", "Cherry"]),
+            (NoteSortMode::Updated, ["Banana", "Cherry", "apple"]),
+            (NoteSortMode::Manual, ["Banana", "apple", "Cherry"]),
+        ];
+        for (mode, order) in expected {
+            app_state.sort_mode = mode;
+            assert_eq!(titles(&app_state, None), order, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn test_pinned_notes_stay_first() {
+        // Тест что закрепленные заметки выше в любом режиме
+        let mut app_state = create_test_app_state();
+        create_fruit_notes(&mut app_state);
+        let cherry = note_id(&app_state, "Cherry");
+        app_state.toggle_pin(cherry).unwrap();
+
+        app_state.sort_mode = NoteSortMode::Alphabetical;
+
+        assert_eq!(titles(&app_state, None), ["Cherry", "apple", "Banana"]);
+    }
+
+    #[test]
+    fn test_group_sort_mode_overrides_global() {
+        // Тест собственной сортировки группы и ее сохранения
+        let temp_dir = TempDir::new().unwrap();
+        let group_id = {
+            let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+            let group_id = create_test_group(&mut app_state, "Fruits");
+            for title in ["Banana", "apple", "Cherry"] {
+                create_test_note_with_group(&mut app_state, title, "Content", Some(group_id));
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            }
+            app_state.set_group_sort_mode(group_id, Some(NoteSortMode::Alphabetical)).unwrap();
+            group_id
+        };
+
+        let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+        assert_eq!(app_state.effective_sort_mode(Some(group_id)), NoteSortMode::Alphabetical);
+        assert_eq!(titles(&app_state, Some(group_id)), ["apple", "Banana", "Cherry"]);
+
+        app_state.set_group_sort_mode(group_id, None).unwrap();
+        assert_eq!(titles(&app_state, Some(group_id)), ["Cherry", "apple", "Banana"]);
+    }
+
+    #[test]
+    fn test_sqlite_adds_columns_to_old_database() {
+        // Тест что база старой версии получает новые колонки и продолжает открываться
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("notes.db");
+        {
+            let conn = rusqlite::Connection::open(&db_path).unwrap();
+            conn.execute_batch("
+                CREATE TABLE notes (id TEXT PRIMARY KEY, title TEXT NOT NULL, content TEXT NOT NULL,
+                    created_at TEXT NOT NULL, updated_at TEXT NOT NULL, pinned INTEGER NOT NULL DEFAULT 0,
+                    group_id TEXT, tags TEXT NOT NULL DEFAULT '[]', content_length INTEGER NOT NULL DEFAULT 0);
+                CREATE TABLE groups (id TEXT PRIMARY KEY, name TEXT NOT NULL, collapsed INTEGER NOT NULL DEFAULT 0,
+                    parent_id TEXT, level INTEGER NOT NULL DEFAULT 0, position INTEGER NOT NULL);
+                INSERT INTO notes (id, title, content, created_at, updated_at)
+                    VALUES ('6f1c1f3e-8d5a-4a57-9a57-2b1a0e4c1d11', 'Old', 'Body', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z');
+                INSERT INTO groups (id, name, position) VALUES ('0b6d7c1e-2f0e-4c8e-8a3b-5d2e9f1a7c22', 'Group', 0);
+            ").unwrap();
+        }
+
+        let store = SqliteNoteStore::open(&db_path).unwrap();
+        let notes = store.get_all_notes().unwrap();
+        assert_eq!(notes[0].title, "Old");
+        assert_eq!(notes[0].copy_count, 0);
+        assert_eq!(notes[0].last_used_at, None);
+        assert_eq!(store.load_groups().unwrap()[0].sort_mode, None);
+    }
+}
@@ -0,0 +1,147 @@
+use fast_bind::notes::NotesManager;
+use fast_bind::sqlite_store::SqliteNoteStore;
+use fast_bind::state::{AppState, LoadMode};
+use fast_bind::storage::{self, NoteStore, StorageBackend};
+use tempfile::TempDir;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Заполняет JSON-хранилище в `temp_dir/data` заметками, группой и черновиком
+    fn create_json_data(temp_dir: &TempDir, notes_count: usize) {
+        let mut app_state = AppState::new(NotesManager::new(temp_dir.path().join("data")));
+        create_multiple_test_notes(&mut app_state, notes_count);
+        create_test_group(&mut app_state, "Group");
+        app_state.persistent_text = "Draft".to_string();
+        app_state.save_persistent_text().unwrap();
+    }
+
+    #[test]
+    fn test_sqlite_data_survives_reopen() {
+        // Тест что данные сохраняются в файле базы
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("notes.db");
+        {
+            let mut app_state = AppState::new(SqliteNoteStore::open(&db_path).unwrap());
+            create_test_note(&mut app_state, "Stored", "Content");
+            create_test_group(&mut app_state, "Group");
+        }
+
+        let app_state = AppState::new(SqliteNoteStore::open(&db_path).unwrap());
+
+        assert_eq!(app_state.notes.len(), 1);
+        assert_eq!(app_state.notes[0].title, "Stored");
+        assert_eq!(app_state.groups.len(), 1);
+    }
+
+    #[test]
+    fn test_migrate_from_json_copies_everything() {
+        // Тест переноса заметок, групп и черновика из JSON-файлов
+        let temp_dir = TempDir::new().unwrap();
+        create_json_data(&temp_dir, 3);
+        let json_store = NotesManager::new(temp_dir.path().join("data"));
+        let sqlite_store = SqliteNoteStore::open_in_memory().unwrap();
+
+        let migrated = sqlite_store.migrate_from(&json_store).unwrap();
+
+        assert_eq!(migrated, Some(3));
+        assert_eq!(sqlite_store.get_notes_count().unwrap(), 3);
+        assert_eq!(sqlite_store.load_groups().unwrap()[0].name, "Group");
+        assert_eq!(sqlite_store.load_persistent_text().unwrap(), "Draft");
+        // Исходные файлы остаются на месте
+        assert_eq!(json_store.get_all_notes().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_migrate_from_json_runs_once() {
+        // Тест что миграция выполняется только один раз
+        let temp_dir = TempDir::new().unwrap();
+        create_json_data(&temp_dir, 2);
+        let json_store = NotesManager::new(temp_dir.path().join("data"));
+        let sqlite_store = SqliteNoteStore::open_in_memory().unwrap();
+        sqlite_store.migrate_from(&json_store).unwrap();
+        let first_id = sqlite_store.get_note_ids().unwrap()[0];
+        sqlite_store.delete_note(first_id).unwrap();
+
+        let migrated = sqlite_store.migrate_from(&json_store).unwrap();
+
+        assert_eq!(migrated, None);
+        assert_eq!(sqlite_store.get_notes_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_read_only_open_writes_nothing() {
+        // Тест что открытие только для чтения не удаляет временные файлы и не переносит заметки в базу
+        let temp_dir = TempDir::new().unwrap();
+        create_json_data(&temp_dir, 2);
+        let temp_file = temp_dir.path().join("data").join("unfinished.json.tmp");
+        std::fs::write(&temp_file, "{").unwrap();
+        let db_path = temp_dir.path().join("notes.db");
+
+        let store = storage::open_store_read_only(temp_dir.path(), StorageBackend::Sqlite);
+        assert_eq!(store.get_all_notes().unwrap().len(), 2);
+        assert!(temp_file.exists());
+        assert!(!db_path.exists());
+
+        // После переноса читается уже база, а не JSON-файлы
+        let store = storage::open_store(temp_dir.path(), StorageBackend::Sqlite);
+        assert!(!temp_file.exists());
+        store.delete_note(store.get_note_ids().unwrap()[0]).unwrap();
+        let read_only = storage::open_store_read_only(temp_dir.path(), StorageBackend::Sqlite);
+        assert_eq!(read_only.get_notes_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_migrated_notes_keep_fields() {
+        // Тест что после миграции у заметок те же данные и время создания
+        let temp_dir = TempDir::new().unwrap();
+        create_json_data(&temp_dir, 5);
+        let json_store = NotesManager::new(temp_dir.path().join("data"));
+        let sqlite_store = SqliteNoteStore::open_in_memory().unwrap();
+        sqlite_store.migrate_from(&json_store).unwrap();
+
+        let mut json_notes: Vec<_> = json_store.get_all_notes().unwrap().into_iter()
+            .map(|n| (n.id, n.title, n.content, n.created_at, n.updated_at))
+            .collect();
+        let mut sqlite_notes: Vec<_> = sqlite_store.get_all_notes().unwrap().into_iter()
+            .map(|n| (n.id, n.title, n.content, n.created_at, n.updated_at))
+            .collect();
+        json_notes.sort();
+        sqlite_notes.sort();
+
+        assert_eq!(sqlite_notes, json_notes);
+    }
+
+    #[test]
+    fn test_sqlite_metadata_has_content_length() {
+        // Тест что метаданные берутся из индексированных колонок без содержимого
+        let mut app_state = AppState::new(SqliteNoteStore::open_in_memory().unwrap());
+        create_test_note(&mut app_state, "Title", "Привет");
+
+        let metadata = app_state.store.get_notes_metadata().unwrap();
+
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata[0].content_length, "Привет".len());
+    }
+
+    #[test]
+    fn test_lazy_mode_over_sqlite() {
+        // Тест ленивой загрузки поверх SQLite
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("notes.db");
+        {
+            let mut app_state = AppState::new(SqliteNoteStore::open(&db_path).unwrap());
+            create_multiple_test_notes(&mut app_state, 30);
+        }
+
+        let app_state = AppState::with_load_mode(SqliteNoteStore::open(&db_path).unwrap(), LoadMode::Lazy);
+
+        assert_eq!(app_state.load_mode, LoadMode::Lazy);
+        assert_eq!(app_state.total_notes_count(), 30);
+        assert!(app_state.notes.len() < 30);
+    }
+}
@@ -0,0 +1,169 @@
+use chrono::{Duration, Utc};
+use fast_bind::error::AppError;
+use fast_bind::history::NoteRevision;
+use fast_bind::notes::{Note, NoteGroup, NotesManager};
+use fast_bind::sqlite_store::SqliteNoteStore;
+use fast_bind::storage::{MemoryNoteStore, NoteStore};
+use fast_bind::trash::{Trash, TrashedNote};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Заметка, созданная `age_minutes` минут назад
+    fn note(title: &str, age_minutes: i64) -> Note {
+        let created_at = Utc::now() - Duration::minutes(age_minutes);
+        Note {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            content: format!("{} content", title),
+            created_at,
+            updated_at: created_at,
+            pinned: false,
+            group_id: None,
+            tags: Vec::new(),
+            copy_count: 0,
+            last_used_at: None,
+            sort_index: 0,
+        }
+    }
+
+    /// Прогоняет одну и ту же проверку для обеих реализаций хранилища
+    fn for_each_store(check: impl Fn(&dyn NoteStore)) {
+        check(&MemoryNoteStore::new());
+
+        let temp_dir = TempDir::new().unwrap();
+        check(&NotesManager::new(temp_dir.path().join("notes")));
+
+        check(&SqliteNoteStore::open_in_memory().unwrap());
+    }
+
+    #[test]
+    fn test_store_save_load_delete_note() {
+        // Тест сохранения, загрузки и удаления заметки
+        for_each_store(|store| {
+            let saved = note("First", 0);
+            store.save_note(&saved).unwrap();
+
+            let loaded = store.load_note_by_id(saved.id).unwrap().unwrap();
+            assert_eq!(loaded.title, "First");
+
+            store.delete_note(saved.id).unwrap();
+            assert!(store.load_note_by_id(saved.id).unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn test_store_delete_missing_note_returns_not_found() {
+        // Тест удаления несуществующей заметки
+        for_each_store(|store| {
+            let result = store.delete_note(Uuid::new_v4());
+
+            assert!(matches!(result, Err(AppError::NoteNotFound)));
+        });
+    }
+
+    #[test]
+    fn test_store_lists_newest_first() {
+        // Тест порядка заметок и метаданных: новые сверху
+        for_each_store(|store| {
+            store.save_note(&note("Old", 10)).unwrap();
+            store.save_note(&note("New", 0)).unwrap();
+
+            let titles: Vec<String> = store.get_all_notes().unwrap().into_iter().map(|n| n.title).collect();
+            let meta_titles: Vec<String> = store.get_notes_metadata().unwrap().into_iter().map(|m| m.title).collect();
+
+            assert_eq!(titles, vec!["New", "Old"]);
+            assert_eq!(meta_titles, titles);
+        });
+    }
+
+    #[test]
+    fn test_store_notes_page() {
+        // Тест постраничной загрузки
+        for_each_store(|store| {
+            for i in 0..5 {
+                store.save_note(&note(&format!("Note {}", i), i)).unwrap();
+            }
+
+            let page = store.get_notes_page(1, 2).unwrap();
+            let titles: Vec<&str> = page.iter().map(|n| n.title.as_str()).collect();
+
+            assert_eq!(titles, vec!["Note 2", "Note 3"]);
+            assert!(store.get_notes_page(3, 2).unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_store_groups_roundtrip() {
+        // Тест сохранения и загрузки групп
+        for_each_store(|store| {
+            assert!(store.load_groups().unwrap().is_empty());
+            let group = NoteGroup {
+                id: Uuid::new_v4(),
+                name: "Group".to_string(),
+                collapsed: false,
+                parent_id: None,
+                level: 0,
+                sort_mode: None,
+            };
+
+            store.save_groups(std::slice::from_ref(&group)).unwrap();
+
+            let groups = store.load_groups().unwrap();
+            assert_eq!(groups.len(), 1);
+            assert_eq!(groups[0].id, group.id);
+        });
+    }
+
+    #[test]
+    fn test_store_persistent_text_roundtrip() {
+        // Тест сохранения и загрузки постоянного текста
+        for_each_store(|store| {
+            assert_eq!(store.load_persistent_text().unwrap(), "");
+
+            store.save_persistent_text("Draft").unwrap();
+
+            assert_eq!(store.load_persistent_text().unwrap(), "Draft");
+        });
+    }
+
+    #[test]
+    fn test_store_revisions_roundtrip() {
+        // Тест сохранения, загрузки и удаления истории версий
+        for_each_store(|store| {
+            let saved = note("Note", 0);
+            assert!(store.load_revisions(saved.id).unwrap().is_empty());
+            let revisions = vec![NoteRevision::from_note(&saved), NoteRevision::from_note(&note("Other", 1))];
+
+            store.save_revisions(saved.id, &revisions).unwrap();
+            let loaded: Vec<String> = store.load_revisions(saved.id).unwrap().into_iter().map(|r| r.title).collect();
+            assert_eq!(loaded, vec!["Note", "Other"]);
+
+            store.delete_revisions(saved.id).unwrap();
+            assert!(store.load_revisions(saved.id).unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_store_trash_roundtrip() {
+        // Тест сохранения и загрузки корзины
+        for_each_store(|store| {
+            assert!(store.load_trash().unwrap().is_empty());
+            let trashed = note("Deleted", 0);
+            let trash = Trash {
+                notes: vec![TrashedNote { note: trashed.clone(), deleted_at: Utc::now() }],
+                groups: Vec::new(),
+            };
+
+            store.save_trash(&trash).unwrap();
+
+            let loaded = store.load_trash().unwrap();
+            assert_eq!(loaded.notes.len(), 1);
+            assert_eq!(loaded.notes[0].note.id, trashed.id);
+            assert!(store.get_all_notes().unwrap().is_empty());
+        });
+    }
+}
@@ -0,0 +1,163 @@
+use fast_bind::notes::{Note, NotesManager};
+use fast_bind::state::{AppState, LoadMode};
+use tempfile::TempDir;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_set_note_tags_normalizes_and_dedupes() {
+        // Тест нормализации тегов: регистр, '#', пробелы и дубликаты
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Note", "Content");
+
+        app_state.set_note_tags(app_state.notes[0].id, tags(&["  #Work ", "work", "Идеи"])).unwrap();
+
+        assert_eq!(app_state.notes[0].tags, tags(&["work", "идеи"]));
+    }
+
+    #[test]
+    fn test_set_note_tags_rejects_invalid_tag() {
+        // Тест отклонения тега с недопустимыми символами
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Note", "Content");
+
+        let result = app_state.set_note_tags(app_state.notes[0].id, tags(&["bad tag"]));
+
+        assert!(result.is_err());
+        assert!(app_state.notes[0].tags.is_empty());
+    }
+
+    #[test]
+    fn test_set_note_tags_rejects_too_many_tags() {
+        // Тест ограничения количества тегов у заметки
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Note", "Content");
+        let many: Vec<String> = (0..25).map(|i| format!("tag{}", i)).collect();
+
+        assert!(app_state.set_note_tags(app_state.notes[0].id, many).is_err());
+    }
+
+    #[test]
+    fn test_tags_are_persisted() {
+        // Тест сохранения тегов на диск
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+            create_test_note(&mut app_state, "Note", "Content");
+            app_state.set_note_tags(app_state.notes[0].id, tags(&["work"])).unwrap();
+        }
+
+        let app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+
+        assert_eq!(app_state.notes[0].tags, tags(&["work"]));
+    }
+
+    #[test]
+    fn test_all_tags_counts_notes() {
+        // Тест подсчета заметок для каждого тега
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "First", "Content");
+        create_test_note(&mut app_state, "Second", "Content");
+        app_state.set_note_tags(app_state.notes[0].id, tags(&["work", "home"])).unwrap();
+        app_state.set_note_tags(app_state.notes[1].id, tags(&["work"])).unwrap();
+
+        let all = app_state.all_tags();
+
+        assert_eq!(all, vec![("home".to_string(), 1), ("work".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_rename_tag() {
+        // Тест переименования тега во всех заметках
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "First", "Content");
+        create_test_note(&mut app_state, "Second", "Content");
+        app_state.set_note_tags(app_state.notes[0].id, tags(&["work"])).unwrap();
+        app_state.set_note_tags(app_state.notes[1].id, tags(&["work", "home"])).unwrap();
+
+        let updated = app_state.rename_tag("work", "job").unwrap();
+
+        assert_eq!(updated, 2);
+        assert!(app_state.notes.iter().all(|n| n.tags.contains(&"job".to_string())));
+        assert!(!app_state.all_tags().iter().any(|(tag, _)| tag == "work"));
+    }
+
+    #[test]
+    fn test_rename_tag_to_existing_merges_without_duplicates() {
+        // Тест что переименование в существующий тег объединяет теги
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Note", "Content");
+        app_state.set_note_tags(app_state.notes[0].id, tags(&["work", "job"])).unwrap();
+
+        app_state.rename_tag("work", "job").unwrap();
+
+        assert_eq!(app_state.notes[0].tags, tags(&["job"]));
+    }
+
+    #[test]
+    fn test_merge_tags() {
+        // Тест объединения нескольких тегов в один
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "First", "Content");
+        create_test_note(&mut app_state, "Second", "Content");
+        app_state.set_note_tags(app_state.notes[0].id, tags(&["a", "b"])).unwrap();
+        app_state.set_note_tags(app_state.notes[1].id, tags(&["c"])).unwrap();
+
+        let updated = app_state.merge_tags(&tags(&["a", "b", "c"]), "merged").unwrap();
+
+        assert_eq!(updated, 2);
+        assert_eq!(app_state.all_tags(), vec![("merged".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_note_without_tags_field_deserializes() {
+        // Тест совместимости со старыми файлами заметок без поля tags
+        let json = r#"{
+            "id": "6f1c2a4e-8b1d-4c3e-9f2a-1b2c3d4e5f60",
+            "title": "Old",
+            "content": "Content",
+            "pinned": false,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "group_id": null
+        }"#;
+
+        let note: Note = serde_json::from_str(json).unwrap();
+
+        assert!(note.tags.is_empty());
+    }
+
+    #[test]
+    fn test_rename_tag_in_lazy_mode_updates_unloaded_notes() {
+        // Тест что в ленивом режиме переименование затрагивает незагруженные заметки
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let mut app_state = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+            // Самая старая заметка попадает на последнюю страницу
+            create_test_note(&mut app_state, "Tagged", "Content");
+            let note_id = app_state.notes.iter().find(|n| n.title == "Tagged").unwrap().id;
+            app_state.set_note_tags(note_id, tags(&["old"])).unwrap();
+            create_multiple_test_notes(&mut app_state, 30);
+        }
+
+        let mut app_state = AppState::with_load_mode(NotesManager::new(temp_dir.path().to_path_buf()), LoadMode::Lazy);
+        assert!(!app_state.notes.iter().any(|n| n.title == "Tagged"));
+        assert_eq!(app_state.all_tags(), vec![("old".to_string(), 1)]);
+
+        app_state.rename_tag("old", "new").unwrap();
+
+        assert_eq!(app_state.all_tags(), vec![("new".to_string(), 1)]);
+        let reloaded = AppState::new(NotesManager::new(temp_dir.path().to_path_buf()));
+        let note = reloaded.notes.iter().find(|n| n.title == "Tagged").unwrap();
+        assert_eq!(note.tags, tags(&["new"]));
+    }
+}
@@ -0,0 +1,240 @@
+use chrono::{Duration, Utc};
+use fast_bind::notes::NotesManager;
+use fast_bind::state::{AppState, LoadMode};
+use fast_bind::storage::NoteStore;
+use tempfile::TempDir;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_note_moves_to_trash() {
+        // Тест что удаленная заметка попадает в корзину
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Content");
+        let note_id = app_state.notes[0].id;
+
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+
+        assert_notes_count(&app_state, 0);
+        assert_eq!(app_state.trash.notes.len(), 1);
+        assert_eq!(app_state.trash.notes[0].note.id, note_id);
+        assert!(app_state.store.load_note_by_id(note_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_restore_trashed_note() {
+        // Тест восстановления заметки из корзины
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Content");
+        let note_id = app_state.notes[0].id;
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+
+        app_state.restore_trashed_note(note_id).unwrap();
+
+        assert_notes_count(&app_state, 1);
+        assert_eq!(app_state.notes[0].content, "Content");
+        assert!(app_state.trash.is_empty());
+        assert!(app_state.store.load_note_by_id(note_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_restore_note_of_deleted_group_goes_to_root() {
+        // Тест что заметка удаленной группы восстанавливается в корень
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Group");
+        create_test_note_with_group(&mut app_state, "Title", "Content", Some(group_id));
+        let note_id = app_state.notes[0].id;
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+        app_state.delete_group(group_id).unwrap();
+        app_state.purge_trashed_group(group_id).unwrap();
+
+        app_state.restore_trashed_note(note_id).unwrap();
+
+        assert_eq!(app_state.notes[0].group_id, None);
+    }
+
+    #[test]
+    fn test_delete_group_moves_to_trash() {
+        // Тест что удаленная группа попадает в корзину вместе с иерархией
+        let mut app_state = create_test_app_state();
+        let (_root_id, child_id, grandchild_id) = create_test_group_hierarchy(&mut app_state);
+        create_test_note_with_group(&mut app_state, "Title", "Content", Some(child_id));
+        let note_id = app_state.notes[0].id;
+
+        app_state.delete_group(child_id).unwrap();
+
+        assert_eq!(app_state.trash.groups.len(), 1);
+        let trashed = &app_state.trash.groups[0];
+        assert_eq!(trashed.group.id, child_id);
+        assert_eq!(trashed.note_ids, vec![note_id]);
+        assert_eq!(trashed.subgroup_ids, vec![grandchild_id]);
+    }
+
+    #[test]
+    fn test_restore_trashed_group_restores_hierarchy() {
+        // Тест что восстановленная группа забирает обратно свои заметки и подгруппы
+        let mut app_state = create_test_app_state();
+        let (root_id, child_id, grandchild_id) = create_test_group_hierarchy(&mut app_state);
+        create_test_note_with_group(&mut app_state, "Title", "Content", Some(child_id));
+        app_state.delete_group(child_id).unwrap();
+
+        app_state.restore_trashed_group(child_id).unwrap();
+
+        let child = app_state.groups.iter().find(|g| g.id == child_id).unwrap();
+        assert_eq!(child.parent_id, Some(root_id));
+        assert_eq!(child.level, 1);
+        let grandchild = app_state.groups.iter().find(|g| g.id == grandchild_id).unwrap();
+        assert_eq!(grandchild.parent_id, Some(child_id));
+        assert_eq!(grandchild.level, 2);
+        assert_eq!(app_state.notes[0].group_id, Some(child_id));
+        assert!(app_state.trash.is_empty());
+    }
+
+    #[test]
+    fn test_restore_group_keeps_notes_moved_elsewhere() {
+        // Тест что заметки, перенесенные после удаления группы, остаются на новом месте
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Group");
+        let other_id = create_test_group(&mut app_state, "Other");
+        create_test_note_with_group(&mut app_state, "Title", "Content", Some(group_id));
+        app_state.delete_group(group_id).unwrap();
+        app_state.notes[0].group_id = Some(other_id);
+
+        app_state.restore_trashed_group(group_id).unwrap();
+
+        assert_eq!(app_state.notes[0].group_id, Some(other_id));
+    }
+
+    #[test]
+    fn test_empty_trash() {
+        // Тест полной очистки корзины
+        let mut app_state = create_test_app_state();
+        create_multiple_test_notes(&mut app_state, 3);
+        let group_id = create_test_group(&mut app_state, "Group");
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+        app_state.delete_group(group_id).unwrap();
+        assert_eq!(app_state.trash.len(), 3);
+
+        app_state.empty_trash().unwrap();
+
+        assert!(app_state.trash.is_empty());
+        assert!(app_state.store.load_trash().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_purge_expired_trash() {
+        // Тест автоматической очистки элементов старше срока хранения
+        let mut app_state = create_test_app_state();
+        create_multiple_test_notes(&mut app_state, 2);
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+        app_state.trash.notes[0].deleted_at = Utc::now() - Duration::days(31);
+
+        let purged = app_state.purge_expired_trash(30).unwrap();
+
+        assert_eq!(purged, 1);
+        assert_eq!(app_state.trash.notes.len(), 1);
+        assert_eq!(app_state.store.load_trash().unwrap().notes.len(), 1);
+    }
+
+    #[test]
+    fn test_zero_retention_keeps_trash() {
+        // Тест что нулевой срок хранения отключает автоочистку
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Content");
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+        app_state.trash.notes[0].deleted_at = Utc::now() - Duration::days(3650);
+
+        assert_eq!(app_state.purge_expired_trash(0).unwrap(), 0);
+        assert_eq!(app_state.trash.notes.len(), 1);
+    }
+
+    #[test]
+    fn test_trash_persists_between_sessions() {
+        // Тест что корзина сохраняется на диске и не считается заметкой
+        let temp_dir = TempDir::new().unwrap();
+        let note_id;
+        {
+            let mut app_state = AppState::new(NotesManager::new(temp_dir.path().join("data")));
+            create_test_note(&mut app_state, "Title", "Content");
+            note_id = app_state.notes[0].id;
+            app_state.delete_note(app_state.notes[0].id).unwrap();
+        }
+
+        let mut app_state = AppState::new(NotesManager::new(temp_dir.path().join("data")));
+        assert_notes_count(&app_state, 0);
+        assert_eq!(app_state.trash.notes.len(), 1);
+
+        app_state.restore_trashed_note(note_id).unwrap();
+        assert_notes_count(&app_state, 1);
+    }
+
+    #[test]
+    fn test_failed_delete_leaves_trash_unchanged() {
+        // Тест что заметка, которую не удалось удалить из хранилища, не остается в корзине
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        let mut app_state = AppState::new(NotesManager::new(data_dir.clone()));
+        create_test_note(&mut app_state, "Title", "Content");
+        let note_id = app_state.notes[0].id;
+        std::fs::remove_file(data_dir.join(format!("{}.json", note_id))).unwrap();
+
+        assert!(app_state.delete_note(note_id).is_err());
+
+        assert_notes_count(&app_state, 1);
+        assert!(app_state.trash.notes.is_empty());
+        assert!(NotesManager::new(data_dir).load_trash().unwrap().notes.is_empty());
+        assert_eq!(app_state.undo_stack.undo_label(), Some("Создание заметки"));
+    }
+
+    #[test]
+    fn test_delete_group_moves_unloaded_notes_in_lazy_mode() {
+        // Тест что удаление и восстановление группы в ленивом режиме касается и незагруженных заметок
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        let group_id;
+        {
+            let mut app_state = AppState::new(NotesManager::new(data_dir.clone()));
+            group_id = create_test_group(&mut app_state, "Group");
+            for i in 0..30 {
+                create_test_note_with_group(&mut app_state, &format!("Note {}", i), "Content", Some(group_id));
+            }
+        }
+        let mut app_state = AppState::with_load_mode(NotesManager::new(data_dir.clone()), LoadMode::Lazy);
+        assert!(app_state.notes.len() < 30);
+
+        app_state.delete_group(group_id).unwrap();
+
+        assert_eq!(app_state.trash.groups[0].note_ids.len(), 30);
+        let store = NotesManager::new(data_dir.clone());
+        assert!(store.get_all_notes().unwrap().iter().all(|n| n.group_id.is_none()));
+
+        app_state.restore_trashed_group(group_id).unwrap();
+        assert!(store.get_all_notes().unwrap().iter().all(|n| n.group_id == Some(group_id)));
+    }
+
+    #[test]
+    fn test_delete_group_fails_when_trash_cannot_be_saved() {
+        // Тест что группа не удаляется, если корзину не удалось записать
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        let mut app_state = AppState::new(NotesManager::new(data_dir.clone()));
+        let group_id = create_test_group(&mut app_state, "Group");
+        create_test_note_with_group(&mut app_state, "Title", "Content", Some(group_id));
+        // На месте файла корзины папка - записать корзину не получится
+        std::fs::create_dir_all(data_dir.join("trash").join("trash.json").join("blocked")).unwrap();
+
+        assert!(app_state.delete_group(group_id).is_err());
+
+        assert_groups_count(&app_state, 1);
+        assert_eq!(app_state.notes[0].group_id, Some(group_id));
+        assert!(app_state.trash.groups.is_empty());
+        assert_eq!(NotesManager::new(data_dir).load_groups().unwrap().len(), 1);
+    }
+}
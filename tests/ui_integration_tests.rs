@@ -1,4 +1,6 @@
-use fast_bind::ui::{SidePanelActions, CentralPanelActions};
+use fast_bind::ui::{SidePanelActions, CentralPanelActions, PanelManager};
+use fast_bind::ui::keymap::{self, Keymap, KeyBinding, ShortcutAction};
+use eframe::egui;
 use uuid::Uuid;
 
 mod common;
@@ -51,8 +53,9 @@ mod tests {
     #[test]
     fn side_panel_actions_select_note_sets_selected_note() {
         let mut actions = SidePanelActions::new();
-        actions.select_note(5);
-        assert_eq!(actions.selected_note, Some(5));
+        let note_id = Uuid::new_v4();
+        actions.select_note(note_id);
+        assert_eq!(actions.selected_note, Some(note_id));
     }
 
     #[test]
@@ -172,17 +175,19 @@ mod tests {
     }
 
     #[test]
-    fn central_panel_actions_toggle_pin_sets_index() {
+    fn central_panel_actions_toggle_pin_sets_note_id() {
         let mut actions = CentralPanelActions::new();
-        actions.toggle_pin(3);
-        assert_eq!(actions.toggle_pin, Some(3));
+        let note_id = Uuid::new_v4();
+        actions.toggle_pin(note_id);
+        assert_eq!(actions.toggle_pin, Some(note_id));
     }
 
     #[test]
-    fn central_panel_actions_update_title_sets_index_and_title() {
+    fn central_panel_actions_update_title_sets_note_id_and_title() {
         let mut actions = CentralPanelActions::new();
-        actions.update_title(2, "New Title".to_string());
-        assert_eq!(actions.update_title, Some((2, "New Title".to_string())));
+        let note_id = Uuid::new_v4();
+        actions.update_title(note_id, "New Title".to_string());
+        assert_eq!(actions.update_title, Some((note_id, "New Title".to_string())));
     }
 
     #[test]
@@ -206,7 +211,7 @@ mod tests {
         let mut side_actions = SidePanelActions::new();
         let central_actions = CentralPanelActions::new();
         
-        side_actions.select_note(1);
+        side_actions.select_note(Uuid::new_v4());
         side_actions.new_note();
         
         assert_eq!(central_actions.save_note_clicked, false);
@@ -227,7 +232,7 @@ mod tests {
     fn side_actions_preserve_other_fields_when_modified() {
         let mut side_actions = SidePanelActions::new();
         
-        side_actions.select_note(1);
+        side_actions.select_note(Uuid::new_v4());
         
         assert_eq!(side_actions.create_group_clicked, false);
     }
@@ -244,19 +249,19 @@ mod tests {
     // === Сценарии интеграции с AppState ===
 
     #[test] 
-    fn side_actions_select_note_with_valid_index() {
-        let (mut app_state, _temp_dir) = create_test_app_state();
+    fn side_actions_select_note_with_valid_id() {
+        let mut app_state = create_test_app_state();
         create_test_note(&mut app_state, "Test Note", "Content");
         
         let mut side_actions = SidePanelActions::new();
-        side_actions.select_note(0);
+        side_actions.select_note(app_state.notes[0].id);
         
-        assert!(side_actions.selected_note.unwrap() < app_state.notes.len());
+        assert!(app_state.note(side_actions.selected_note.unwrap()).is_some());
     }
 
     #[test]
     fn side_actions_toggle_group_stores_valid_group_id() {
-        let (mut app_state, _temp_dir) = create_test_app_state();
+        let mut app_state = create_test_app_state();
         let group_id = create_test_group(&mut app_state, "Test Group");
         
         let mut side_actions = SidePanelActions::new();
@@ -266,14 +271,110 @@ mod tests {
     }
 
     #[test]
-    fn central_actions_toggle_pin_with_valid_index() {
-        let (mut app_state, _temp_dir) = create_test_app_state();
+    fn central_actions_toggle_pin_with_valid_id() {
+        let mut app_state = create_test_app_state();
         create_test_note(&mut app_state, "Test Note 1", "Content 1");
         create_test_note(&mut app_state, "Test Note 2", "Content 2");
         
         let mut central_actions = CentralPanelActions::new();
-        central_actions.toggle_pin(1);
+        central_actions.toggle_pin(app_state.notes[1].id);
         
-        assert!(central_actions.toggle_pin.unwrap() < app_state.notes.len());
+        assert!(app_state.note(central_actions.toggle_pin.unwrap()).is_some());
     }
-}
\ No newline at end of file
+
+    // === Горячие клавиши ===
+
+    #[test]
+    fn keymap_default_new_note_is_ctrl_n() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.binding(ShortcutAction::NewNote).to_string(), "Ctrl+N");
+    }
+
+    #[test]
+    fn keymap_default_delete_has_no_modifiers() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.binding(ShortcutAction::DeleteNote).to_string(), "Delete");
+    }
+
+    #[test]
+    fn keymap_rebind_changes_binding() {
+        let mut keymap = Keymap::default();
+        let binding = KeyBinding::new(egui::Key::F2, egui::Modifiers::NONE);
+        keymap.rebind(ShortcutAction::TogglePin, binding.clone());
+        assert_eq!(keymap.binding(ShortcutAction::TogglePin), binding);
+    }
+
+    #[test]
+    fn keymap_rebind_conflict_swaps_bindings() {
+        let mut keymap = Keymap::default();
+        let save_binding = keymap.binding(ShortcutAction::SaveNote);
+        let new_note_binding = keymap.binding(ShortcutAction::NewNote);
+
+        keymap.rebind(ShortcutAction::NewNote, save_binding.clone());
+
+        assert_eq!(keymap.binding(ShortcutAction::NewNote), save_binding);
+        assert_eq!(keymap.binding(ShortcutAction::SaveNote), new_note_binding);
+    }
+
+    #[test]
+    fn keymap_missing_binding_falls_back_to_default() {
+        let mut keymap = Keymap::default();
+        keymap.bindings.remove(&ShortcutAction::SelectNext);
+        assert_eq!(keymap.binding(ShortcutAction::SelectNext), Keymap::default_binding(ShortcutAction::SelectNext));
+    }
+
+    /// Кадр с вводом в том виде, в каком его передает egui-winit
+    fn poll_frame(keymap: &Keymap, modifiers: egui::Modifiers, events: Vec<egui::Event>) -> Vec<ShortcutAction> {
+        let ctx = egui::Context::default();
+        let mut triggered = Vec::new();
+        let _ = ctx.run(egui::RawInput { modifiers, events, ..Default::default() }, |ctx| {
+            triggered = keymap.poll(ctx);
+        });
+        triggered
+    }
+
+    #[test]
+    fn keymap_copy_shortcuts_fire_from_copy_event() {
+        // Ctrl+C и Ctrl+Shift+C приходят событием Copy без Event::Key
+        let keymap = Keymap::default();
+        let ctrl_shift = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+
+        assert_eq!(poll_frame(&keymap, egui::Modifiers::COMMAND, vec![egui::Event::Copy]), vec![ShortcutAction::CopyToClipboard]);
+        assert_eq!(poll_frame(&keymap, ctrl_shift, vec![egui::Event::Copy]), vec![ShortcutAction::CopyToPersistent]);
+        assert!(poll_frame(&keymap, egui::Modifiers::COMMAND, vec![egui::Event::Paste("text".to_string())]).is_empty());
+    }
+
+    #[test]
+    fn keymap_captures_clipboard_command_as_binding() {
+        // Перехват сочетания в настройках распознает Ctrl+X, пришедшее событием Cut
+        let (key, modifiers) = keymap::key_press(&egui::Event::Cut, egui::Modifiers::COMMAND).unwrap();
+        let mut keymap = Keymap::default();
+        keymap.rebind(ShortcutAction::TogglePin, KeyBinding::new(key, modifiers));
+
+        assert_eq!(keymap.binding(ShortcutAction::TogglePin).to_string(), "Ctrl+X");
+        assert_eq!(poll_frame(&keymap, egui::Modifiers::COMMAND, vec![egui::Event::Cut]), vec![ShortcutAction::TogglePin]);
+    }
+
+    #[test]
+    fn note_display_order_lists_grouped_notes_before_ungrouped() {
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Group");
+        create_test_note(&mut app_state, "Ungrouped", "Content");
+        create_test_note_with_group(&mut app_state, "Grouped", "Content", Some(group_id));
+
+        let order = PanelManager::note_display_order(&app_state, None);
+
+        let titles: Vec<&str> = order.iter().map(|&i| app_state.notes[i].title.as_str()).collect();
+        assert_eq!(titles, vec!["Grouped", "Ungrouped"]);
+    }
+
+    #[test]
+    fn note_display_order_skips_collapsed_groups() {
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Group");
+        create_test_note_with_group(&mut app_state, "Grouped", "Content", Some(group_id));
+        let _ = app_state.toggle_group_collapsed(group_id);
+
+        assert!(PanelManager::note_display_order(&app_state, None).is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+use fast_bind::undo::MAX_UNDO_STEPS;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_with_empty_stack() {
+        // Тест что отмена без истории ничего не делает
+        let mut app_state = create_test_app_state();
+
+        assert!(!app_state.undo().unwrap());
+        assert!(!app_state.redo().unwrap());
+    }
+
+    #[test]
+    fn test_undo_redo_create_note() {
+        // Тест отмены и повтора создания заметки
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Content");
+        let note_id = app_state.notes[0].id;
+
+        assert!(app_state.undo().unwrap());
+        assert_notes_count(&app_state, 0);
+        assert!(app_state.store.load_note_by_id(note_id).unwrap().is_none());
+
+        assert!(app_state.redo().unwrap());
+        assert_notes_count(&app_state, 1);
+        assert!(app_state.store.load_note_by_id(note_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_undo_update_note() {
+        // Тест отмены изменения текста заметки
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Original");
+        app_state.update_note(app_state.notes[0].id, Some("New title".to_string()), Some("Changed".to_string())).unwrap();
+
+        app_state.undo().unwrap();
+
+        assert_eq!(app_state.notes[0].title, "Title");
+        assert_eq!(app_state.notes[0].content, "Original");
+        let stored = app_state.store.load_note_by_id(app_state.notes[0].id).unwrap().unwrap();
+        assert_eq!(stored.content, "Original");
+    }
+
+    #[test]
+    fn test_undo_delete_note_restores_from_trash() {
+        // Тест что отмена удаления возвращает заметку и убирает её из корзины
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Content");
+        app_state.delete_note(app_state.notes[0].id).unwrap();
+
+        app_state.undo().unwrap();
+
+        assert_notes_count(&app_state, 1);
+        assert!(app_state.trash.is_empty());
+
+        app_state.redo().unwrap();
+        assert_notes_count(&app_state, 0);
+        assert_eq!(app_state.trash.notes.len(), 1);
+    }
+
+    #[test]
+    fn test_undo_toggle_pin() {
+        // Тест отмены закрепления
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Content");
+        app_state.toggle_pin(app_state.notes[0].id).unwrap();
+
+        app_state.undo().unwrap();
+
+        assert!(!app_state.notes[0].pinned);
+    }
+
+    #[test]
+    fn test_undo_move_note_to_group() {
+        // Тест отмены перемещения заметки между группами
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Group");
+        create_test_note(&mut app_state, "Title", "Content");
+        app_state.move_note_to_group(app_state.notes[0].id, Some(group_id)).unwrap();
+        assert_eq!(app_state.notes[0].group_id, Some(group_id));
+
+        app_state.undo().unwrap();
+
+        assert_eq!(app_state.notes[0].group_id, None);
+    }
+
+    #[test]
+    fn test_undo_delete_group_restores_exact_tree() {
+        // Тест что отмена удаления группы возвращает иерархию, уровни и заметки
+        let mut app_state = create_test_app_state();
+        let (_root_id, child_id, grandchild_id) = create_test_group_hierarchy(&mut app_state);
+        create_test_note_with_group(&mut app_state, "Title", "Content", Some(child_id));
+        let groups_before: Vec<_> = app_state.groups.iter().map(|g| (g.id, g.parent_id, g.level)).collect();
+
+        app_state.delete_group(child_id).unwrap();
+        app_state.undo().unwrap();
+
+        let groups_after: Vec<_> = app_state.groups.iter().map(|g| (g.id, g.parent_id, g.level)).collect();
+        assert_eq!(groups_after, groups_before);
+        assert_eq!(app_state.notes[0].group_id, Some(child_id));
+        assert!(app_state.trash.is_empty());
+        let grandchild = app_state.groups.iter().find(|g| g.id == grandchild_id).unwrap();
+        assert_eq!(grandchild.parent_id, Some(child_id));
+        assert_eq!(app_state.store.load_groups().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_undo_create_group_with_notes() {
+        // Тест отмены создания группы с перемещением заметок
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "Title", "Content");
+        let note_id = app_state.notes[0].id;
+        create_test_group_full(&mut app_state, "Group", None, vec![note_id]);
+
+        app_state.undo().unwrap();
+
+        assert_groups_count(&app_state, 0);
+        assert_eq!(app_state.notes[0].group_id, None);
+    }
+
+    #[test]
+    fn test_undo_update_group() {
+        // Тест отмены переименования группы
+        let mut app_state = create_test_app_state();
+        let group_id = create_test_group(&mut app_state, "Old");
+        app_state.update_group(group_id, "New".to_string(), None).unwrap();
+
+        app_state.undo().unwrap();
+
+        assert_eq!(app_state.groups[0].name, "Old");
+    }
+
+    #[test]
+    fn test_undo_merge_tags() {
+        // Тест отмены объединения тегов во всех заметках
+        let mut app_state = create_test_app_state();
+        create_multiple_test_notes(&mut app_state, 2);
+        app_state.set_note_tags(app_state.notes[0].id, vec!["a".to_string()]).unwrap();
+        app_state.set_note_tags(app_state.notes[1].id, vec!["b".to_string()]).unwrap();
+        app_state.merge_tags(&["a".to_string(), "b".to_string()], "c").unwrap();
+
+        app_state.undo().unwrap();
+
+        let mut tags: Vec<String> = app_state.notes.iter().flat_map(|n| n.tags.clone()).collect();
+        tags.sort();
+        assert_eq!(tags, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_new_operation_clears_redo() {
+        // Тест что новая операция после отмены сбрасывает повтор
+        let mut app_state = create_test_app_state();
+        create_test_note(&mut app_state, "First", "Content");
+        app_state.undo().unwrap();
+        create_test_note(&mut app_state, "Second", "Content");
+
+        assert!(!app_state.redo().unwrap());
+        assert_notes_count(&app_state, 1);
+        assert_eq!(app_state.notes[0].title, "Second");
+    }
+
+    #[test]
+    fn test_undo_stack_is_bounded() {
+        // Тест ограничения глубины отмены
+        let mut app_state = create_test_app_state();
+        create_multiple_test_notes(&mut app_state, MAX_UNDO_STEPS + 5);
+
+        let mut undone = 0;
+        while app_state.undo().unwrap() {
+            undone += 1;
+        }
+
+        assert_eq!(undone, MAX_UNDO_STEPS);
+        assert_notes_count(&app_state, 5);
+    }
+}
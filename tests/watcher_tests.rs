@@ -0,0 +1,200 @@
+use fast_bind::notes::{NoteGroup, NotesManager, GROUPS_FILE_NAME};
+use fast_bind::state::{AppState, LoadMode};
+use fast_bind::storage::{MemoryNoteStore, NoteStore};
+use fast_bind::watcher::{DataWatcher, ExternalChanges};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod common;
+use common::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use eframe::egui;
+
+    fn notes_dir(temp_dir: &TempDir) -> PathBuf {
+        temp_dir.path().join("data")
+    }
+
+    fn changed(note_ids: &[Uuid]) -> ExternalChanges {
+        ExternalChanges { note_ids: note_ids.iter().copied().collect(), ..Default::default() }
+    }
+
+    /// Правка файла заметки "другой программой": меняет содержимое прямо в JSON
+    fn edit_note_file(dir: &Path, note_id: Uuid, content: &str) {
+        let path = dir.join(format!("{}.json", note_id));
+        let mut json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        json["content"] = content.into();
+        std::fs::write(&path, serde_json::to_string_pretty(&json).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_external_create_update_delete() {
+        // Тест что созданный, измененный и удаленный на диске файл попадает в состояние
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(notes_dir(&temp_dir)));
+        create_test_note(&mut app_state, "Edited", "old");
+        create_test_note(&mut app_state, "Removed", "gone");
+        let edited_id = app_state.notes[1].id;
+        let removed_id = app_state.notes[0].id;
+
+        // Заметка от синхронизации с другого компьютера
+        let mut other = AppState::new(MemoryNoteStore::new());
+        create_test_note(&mut other, "Synced", "from laptop");
+        let synced = other.notes[0].clone();
+        NotesManager::new(notes_dir(&temp_dir)).save_note(&synced).unwrap();
+        edit_note_file(&notes_dir(&temp_dir), edited_id, "new");
+        std::fs::remove_file(notes_dir(&temp_dir).join(format!("{}.json", removed_id))).unwrap();
+
+        let report = app_state.apply_external_changes(&changed(&[synced.id, edited_id, removed_id]));
+
+        assert_eq!((report.created, report.updated, report.deleted), (vec![synced.id], vec![edited_id], vec![removed_id]));
+        assert_eq!(app_state.note(edited_id).unwrap().content, "new");
+        assert_eq!(app_state.note(synced.id).unwrap().title, "Synced");
+        assert!(app_state.note(removed_id).is_none());
+        assert_notes_count(&app_state, 2);
+    }
+
+    #[test]
+    fn test_own_writes_are_noop() {
+        // Тест что события от собственных сохранений приложения ничего не меняют
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(notes_dir(&temp_dir)));
+        create_test_note(&mut app_state, "Mine", "text");
+        let note_id = app_state.notes[0].id;
+        app_state.update_note(note_id, None, Some("saved".to_string())).unwrap();
+        app_state.delete_note(note_id).unwrap();
+
+        let report = app_state.apply_external_changes(&changed(&[note_id]));
+
+        assert!(report.is_empty());
+        assert_notes_count(&app_state, 0);
+    }
+
+    #[test]
+    fn test_own_writes_are_noop_in_lazy_mode() {
+        // Тест что в ленивом режиме метаданные следуют за собственными правками и удалением
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let mut app_state = AppState::new(NotesManager::new(notes_dir(&temp_dir)));
+            create_multiple_test_notes(&mut app_state, 3);
+        }
+        let mut app_state = AppState::with_load_mode(NotesManager::new(notes_dir(&temp_dir)), LoadMode::Lazy);
+        let (edited_id, deleted_id) = (app_state.notes[0].id, app_state.notes[1].id);
+        app_state.update_note(edited_id, None, Some("edited in app".to_string())).unwrap();
+        app_state.delete_note(deleted_id).unwrap();
+
+        let report = app_state.apply_external_changes(&changed(&[edited_id, deleted_id]));
+
+        assert!(report.is_empty());
+        assert_eq!(app_state.total_notes_count(), 2);
+        let metadata = app_state.quick_picker_candidates();
+        assert!(metadata.iter().all(|m| m.id != deleted_id));
+        assert_eq!(metadata.iter().find(|m| m.id == edited_id).unwrap().content_length, "edited in app".len());
+    }
+
+    #[test]
+    fn test_unreadable_file_is_skipped() {
+        // Тест что недописанный файл не удаляет заметку из состояния
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(notes_dir(&temp_dir)));
+        create_test_note(&mut app_state, "Half", "written");
+        let note_id = app_state.notes[0].id;
+        std::fs::write(notes_dir(&temp_dir).join(format!("{}.json", note_id)), "{\"id\":").unwrap();
+
+        let report = app_state.apply_external_changes(&changed(&[note_id]));
+
+        assert!(report.is_empty());
+        assert_eq!(app_state.note(note_id).unwrap().content, "written");
+    }
+
+    #[test]
+    fn test_lazy_loader_metadata_follows_disk() {
+        // Тест что в ленивом режиме метаданные и счетчик заметок обновляются
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let mut app_state = AppState::new(NotesManager::new(notes_dir(&temp_dir)));
+            create_multiple_test_notes(&mut app_state, 3);
+        }
+        let mut app_state = AppState::with_load_mode(NotesManager::new(notes_dir(&temp_dir)), LoadMode::Lazy);
+        let note_id = app_state.quick_picker_candidates()[0].id;
+        edit_note_file(&notes_dir(&temp_dir), note_id, "longer external content");
+        let removed_id = app_state.quick_picker_candidates()[1].id;
+        std::fs::remove_file(notes_dir(&temp_dir).join(format!("{}.json", removed_id))).unwrap();
+
+        let report = app_state.apply_external_changes(&changed(&[note_id, removed_id]));
+
+        assert_eq!((report.updated, report.deleted), (vec![note_id], vec![removed_id]));
+        assert_eq!(app_state.total_notes_count(), 2);
+        let metadata = app_state.quick_picker_candidates();
+        let meta = metadata.iter().find(|m| m.id == note_id).unwrap();
+        assert_eq!(meta.content_length, "longer external content".len());
+    }
+
+    #[test]
+    fn test_groups_file_and_rescan() {
+        // Тест перечитывания групп и сверки всех заметок после потерянных событий
+        let temp_dir = TempDir::new().unwrap();
+        let mut app_state = AppState::new(NotesManager::new(notes_dir(&temp_dir)));
+        create_test_note(&mut app_state, "Known", "a");
+        let store = NotesManager::new(notes_dir(&temp_dir));
+        let mut groups = store.load_groups().unwrap();
+        groups.push(NoteGroup {
+            id: Uuid::new_v4(),
+            name: "Synced group".to_string(),
+            collapsed: false,
+            parent_id: None,
+            level: 0,
+            sort_mode: None,
+        });
+        store.save_groups(&groups).unwrap();
+        let mut other = AppState::new(MemoryNoteStore::new());
+        create_test_note(&mut other, "Unseen", "b");
+        store.save_note(&other.notes[0]).unwrap();
+
+        let report = app_state.apply_external_changes(&ExternalChanges { rescan: true, ..Default::default() });
+
+        assert!(report.groups_changed);
+        assert_eq!(report.created, vec![other.notes[0].id]);
+        assert_eq!(app_state.groups.len(), 1);
+        assert_notes_count(&app_state, 2);
+    }
+
+    #[test]
+    fn test_add_path_filters_files() {
+        // Тест что учитываются только файлы заметок и групп, без временных файлов записи
+        let note_id = Uuid::new_v4();
+        let mut changes = ExternalChanges::default();
+
+        changes.add_path(Path::new(&format!("/data/{}.json.tmp", note_id)));
+        changes.add_path(Path::new("/data/settings.json"));
+        changes.add_path(Path::new("/data/notes.txt"));
+        assert!(changes.is_empty());
+
+        changes.add_path(Path::new(&format!("/data/{}.json", note_id)));
+        changes.add_path(&Path::new("/data").join(GROUPS_FILE_NAME));
+        assert_eq!(changes, ExternalChanges { note_ids: [note_id].into(), groups_changed: true, rescan: false });
+    }
+
+    #[test]
+    fn test_watcher_reports_written_file() {
+        // Тест что запись файла в папку замечает фоновое слежение
+        let temp_dir = TempDir::new().unwrap();
+        let watcher = DataWatcher::start(temp_dir.path(), &egui::Context::default()).unwrap();
+        let note_id = Uuid::new_v4();
+        std::fs::write(temp_dir.path().join(format!("{}.json", note_id)), "{}").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let changes = loop {
+            if let Some(changes) = watcher.take_changes(&egui::Context::default()) {
+                break changes;
+            }
+            assert!(Instant::now() < deadline, "изменение не замечено");
+            std::thread::sleep(Duration::from_millis(50));
+        };
+        assert!(changes.note_ids.contains(&note_id));
+    }
+}